@@ -0,0 +1,66 @@
+//! Benchmarks of batch conversions against single conversions, of sorted and random timestamps.
+use chrono_light::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+const TIMESTAMPS: usize = 1_000_000;
+const START_MS: u64 = 946684800000;  // 01/01/2000 00:00:00:000
+const END_MS: u64 = 4102444800000;  // 01/01/2100 00:00:00:000
+
+/// Sorted timestamps over a century, eg. of an event log.
+fn sorted_timestamps() -> Vec<u64> {
+    let step_ms = (END_MS - START_MS) / TIMESTAMPS as u64;
+    (0..TIMESTAMPS as u64).map(|i| START_MS + i * step_ms).collect()
+}
+
+fn random_timestamps() -> Vec<u64> {
+    let mut seed: u64 = 42;
+    (0..TIMESTAMPS).map(|_| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        START_MS + (seed >> 16) % (END_MS - START_MS)
+    }).collect()
+}
+
+fn bench_from_unixtime(criterion: &mut Criterion) {
+    let c = Calendar::create();
+    let mut group = criterion.benchmark_group("from_unixtime 1M timestamps");
+    group.sample_size(10).throughput(Throughput::Elements(TIMESTAMPS as u64));
+    let mut datetimes = vec![DateTime::default(); TIMESTAMPS];
+    for (name, timestamps) in [("sorted", sorted_timestamps()), ("random", random_timestamps())] {
+        group.bench_function(format!("{} single", name), |b| b.iter(|| {
+            for (&ts, dt) in timestamps.iter().zip(datetimes.iter_mut()) {
+                *dt = c.from_unixtime(ts);
+            }
+            black_box(&datetimes);
+        }));
+        group.bench_function(format!("{} batch", name), |b| b.iter(|| {
+            c.from_unixtime_batch(&timestamps, &mut datetimes);
+            black_box(&datetimes);
+        }));
+    }
+    group.finish();
+}
+
+fn bench_to_unixtime(criterion: &mut Criterion) {
+    let c = Calendar::create();
+    let mut group = criterion.benchmark_group("to_unixtime 1M datetimes");
+    group.sample_size(10).throughput(Throughput::Elements(TIMESTAMPS as u64));
+    let mut timestamps = vec![0; TIMESTAMPS];
+    for (name, sources) in [("sorted", sorted_timestamps()), ("random", random_timestamps())] {
+        let mut datetimes = vec![DateTime::default(); TIMESTAMPS];
+        c.from_unixtime_batch(&sources, &mut datetimes);
+        group.bench_function(format!("{} single", name), |b| b.iter(|| {
+            for (dt, ts) in datetimes.iter().zip(timestamps.iter_mut()) {
+                *ts = c.to_unixtime(dt);
+            }
+            black_box(&timestamps);
+        }));
+        group.bench_function(format!("{} batch", name), |b| b.iter(|| {
+            c.to_unixtime_batch(&datetimes, &mut timestamps);
+            black_box(&timestamps);
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_from_unixtime, bench_to_unixtime);
+criterion_main!(benches);
@@ -0,0 +1,104 @@
+//! Benchmarks of `TimingWheel` against a `BinaryHeap` of next triggers, and `SchedulerQueue`, for large numbers of schedules.
+use chrono_light::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+const JOBS: usize = 100_000;
+const NOW_MS: u64 = 1650412800000;  // 20/04/2022 00:00:00:000
+const TICK_MS: u64 = 10;
+const TICKS: u64 = 6_000;  // 1 minute
+
+/// Schedules of periods between 1s and 1h, starting within the first minute.
+fn schedules() -> Vec<Schedule> {
+    let c = Calendar::create();
+    let mut seed: u64 = 42;
+    (0..JOBS).map(|_| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Schedule {
+            start: c.from_unixtime(NOW_MS + (seed >> 33) % 60_000),
+            items: vec![(Frequency::Second, (seed >> 16) as u32 % 3_600 + 1)],
+            ..Default::default()
+        }
+    }).collect()
+}
+
+/// Heap of next triggers, validating schedules on insert and re-evaluating them as they trigger, as per `TimingWheel`.
+struct HeapScheduler {
+    calendar:  Calendar,
+    schedules: Vec<Schedule>,
+    heap:      BinaryHeap<Reverse<(u64, usize)>>,
+}
+
+impl HeapScheduler {
+    fn new(schedules: Vec<Schedule>) -> Self {
+        let calendar = Calendar::create();
+        let now = calendar.from_unixtime(NOW_MS);
+        let mut heap = BinaryHeap::new();
+        for (id, schedule) in schedules.iter().enumerate() {
+            calendar.validate_schedule(schedule).unwrap();
+            if let Some(delta) = calendar.next_occurrence_ms(&now, schedule) {
+                heap.push(Reverse((NOW_MS + delta, id)));
+            }
+        }
+        Self { calendar, schedules, heap }
+    }
+
+    fn advance(&mut self, now_ms: u64) -> Vec<(u64, usize)> {
+        let mut due = Vec::new();
+        while let Some(&Reverse((trigger_ms, id))) = self.heap.peek() {
+            if trigger_ms > now_ms {
+                break;
+            }
+            self.heap.pop();
+            due.push((trigger_ms, id));
+            if let Some(delta) = self.calendar.next_occurrence_ms(&self.calendar.from_unixtime(trigger_ms), &self.schedules[id]) {
+                self.heap.push(Reverse((trigger_ms + delta, id)));
+            }
+        }
+        due
+    }
+}
+
+fn wheel(schedules: Vec<Schedule>) -> TimingWheel<usize> {
+    let mut wheel = TimingWheel::new(NOW_MS);
+    wheel.reserve(schedules.len());
+    for (id, schedule) in schedules.into_iter().enumerate() {
+        wheel.insert(id, schedule).unwrap();
+    }
+    wheel
+}
+
+fn queue(schedules: Vec<Schedule>) -> SchedulerQueue<usize> {
+    let mut queue = SchedulerQueue::new();
+    for (id, schedule) in schedules.into_iter().enumerate() {
+        queue.insert(id, schedule, NOW_MS).unwrap();
+    }
+    queue
+}
+
+fn bench_insert(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("insert 100k schedules");
+    group.sample_size(10);
+    group.bench_function("timing wheel", |b| b.iter_batched(schedules, |schedules| black_box(wheel(schedules)), BatchSize::LargeInput));
+    group.bench_function("binary heap", |b| b.iter_batched(schedules, |schedules| black_box(HeapScheduler::new(schedules)), BatchSize::LargeInput));
+    group.bench_function("scheduler queue", |b| b.iter_batched(schedules, |schedules| black_box(queue(schedules)), BatchSize::LargeInput));
+    group.finish();
+}
+
+fn bench_advance(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("advance 100k schedules by 1 minute in 10ms ticks");
+    group.sample_size(10);
+    group.bench_function("timing wheel", |b| b.iter_batched(|| wheel(schedules()), |mut wheel| {
+        (1..=TICKS).map(|tick| wheel.advance(NOW_MS + tick * TICK_MS).len()).sum::<usize>()
+    }, BatchSize::LargeInput));
+    group.bench_function("binary heap", |b| b.iter_batched(|| HeapScheduler::new(schedules()), |mut heap| {
+        (1..=TICKS).map(|tick| heap.advance(NOW_MS + tick * TICK_MS).len()).sum::<usize>()
+    }, BatchSize::LargeInput));
+    group.bench_function("scheduler queue", |b| b.iter_batched(|| queue(schedules()), |mut queue| {
+        (1..=TICKS).map(|tick| queue.pop_due(NOW_MS + tick * TICK_MS).len()).sum::<usize>()
+    }, BatchSize::LargeInput));
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_advance);
+criterion_main!(benches);
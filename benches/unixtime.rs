@@ -0,0 +1,32 @@
+//! Benchmarks of single conversions, of timestamps spread over the scope of `Calendar`, and near its ends.
+use chrono_light::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+const TIMESTAMPS: usize = 100_000;
+const SCOPE_END_MS: u64 = 64060588800000;  // 01/01/4000 00:00:00:000
+
+fn timestamps(from_ms: u64, to_ms: u64) -> Vec<u64> {
+    let mut seed: u64 = 42;
+    (0..TIMESTAMPS).map(|_| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        from_ms + (seed >> 16) % (to_ms - from_ms)
+    }).collect()
+}
+
+fn bench_from_unixtime(criterion: &mut Criterion) {
+    let c = Calendar::create();
+    let mut group = criterion.benchmark_group("from_unixtime 100k timestamps");
+    group.throughput(Throughput::Elements(TIMESTAMPS as u64));
+    for (name, timestamps) in [
+        ("1970-1980", timestamps(0, 315532800000)),
+        ("2020-2030", timestamps(1577836800000, 1893456000000)),
+        ("3990-4000", timestamps(63745056000000, SCOPE_END_MS)),
+        ("1970-4000", timestamps(0, SCOPE_END_MS)),
+    ] {
+        group.bench_function(name, |b| b.iter(|| timestamps.iter().map(|&ts| c.from_unixtime(black_box(ts)).day as u64).sum::<u64>()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_from_unixtime);
+criterion_main!(benches);
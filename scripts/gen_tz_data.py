@@ -0,0 +1,209 @@
+#!/usr/bin/env python3
+"""Generates src/tz_data.rs, the embedded subset of the IANA tz database, from compiled TZif files.
+
+Usage: scripts/gen_tz_data.py [ZONEINFO_DIR] > src/tz_data.rs
+
+For every zone, transitions since 1970 are kept up to the point from which the POSIX TZ footer rule
+reproduces all of the remaining transitions, keeping the tables compact. Zones are grouped by `tz-*`
+cargo features, edit ZONES to change the subset.
+"""
+import datetime
+import re
+import struct
+import sys
+
+ZONES = {
+    "tz-africa":  ["Africa/Cairo", "Africa/Johannesburg", "Africa/Lagos", "Africa/Nairobi"],
+    "tz-america": ["America/Chicago", "America/Denver", "America/Los_Angeles", "America/Mexico_City", "America/New_York",
+                   "America/Phoenix", "America/Sao_Paulo", "America/Toronto"],
+    "tz-asia":    ["Asia/Dubai", "Asia/Hong_Kong", "Asia/Kolkata", "Asia/Shanghai", "Asia/Singapore", "Asia/Tokyo"],
+    "tz-europe":  ["Europe/Amsterdam", "Europe/Berlin", "Europe/London", "Europe/Madrid", "Europe/Moscow", "Europe/Paris",
+                   "Europe/Rome", "Europe/Warsaw", "Europe/Zurich"],
+    "tz-oceania": ["Australia/Melbourne", "Australia/Perth", "Australia/Sydney", "Pacific/Auckland"],
+}
+# transitions are only verified up to the year until which TZif files are generated explicitly
+LAST_VERIFIED_YEAR = 2037
+
+
+def read_tzif(path):
+    """Returns ([(utc_seconds, offset_seconds, is_dst)], footer) of the v2+ section of a TZif file."""
+    data = open(path, "rb").read()
+    assert data[:4] == b"TZif" and data[4] >= ord("2"), f"{path}: TZif v2+ required"
+
+    def header(at):
+        return struct.unpack(">6l", data[at + 20:at + 44])
+
+    isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt = header(0)
+    at = 44 + timecnt * 5 + typecnt * 6 + charcnt + leapcnt * 8 + isstdcnt + isutcnt
+    isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt = header(at)
+    at += 44
+    times = struct.unpack(f">{timecnt}q", data[at:at + timecnt * 8])
+    at += timecnt * 8
+    indices = data[at:at + timecnt]
+    at += timecnt
+    types = [struct.unpack(">lBB", data[at + i * 6:at + i * 6 + 6]) for i in range(typecnt)]
+    at += typecnt * 6 + charcnt + leapcnt * 12 + isstdcnt + isutcnt
+    footer = data[at:].strip(b"\n").decode()
+    # initial type, as per RFC 8536, is the first type
+    transitions = [(None, types[0][0], bool(types[0][1]))]
+    transitions += [(t, types[i][0], bool(types[i][1])) for t, i in zip(times, indices)]
+    return transitions, footer
+
+
+def parse_offset(s):
+    sign = -1 if s.startswith("-") else 1
+    parts = [int(p) for p in s.lstrip("+-").split(":")]
+    parts += [0] * (3 - len(parts))
+    return sign * (parts[0] * 3600 + parts[1] * 60 + parts[2])
+
+
+def parse_footer(footer):
+    """Parses POSIX TZ string with `Mm.w.d[/time]` rules, returns (std_offset_s, dst) with offsets east of UTC."""
+    name = r"(?:[A-Za-z]{3,}|<[^>]+>)"
+    offset = r"([+-]?\d{1,2}(?::\d{2}){0,2})"
+    m = re.fullmatch(rf"{name}{offset}(?:{name}{offset}?,(M[^,]+),(M[^,]+))?", footer)
+    assert m, f"unsupported footer: {footer}"
+    std = -parse_offset(m.group(1))
+    if not m.group(3):
+        return std, None
+    dst = -parse_offset(m.group(2)) if m.group(2) else std + 3600
+
+    def rule(s):
+        date, _, time = s.partition("/")
+        month, week, weekday = (int(x) for x in date[1:].split("."))
+        return (month, week, weekday), parse_offset(time) if time else 7200
+
+    return std, (dst, rule(m.group(3)), rule(m.group(4)))
+
+
+def transition_utc(year, day, time_s, offset_s):
+    (month, week, weekday) = day
+    first = datetime.date(year, month, 1)
+    d = 1 + (weekday - (first.isoweekday() % 7)) % 7 + (week - 1) * 7
+    days_in_month = ((first.replace(day=28) + datetime.timedelta(days=4)).replace(day=1) - datetime.timedelta(days=1)).day
+    while d > days_in_month:
+        d -= 7
+    midnight = datetime.datetime(year, month, d, tzinfo=datetime.timezone.utc).timestamp()
+    return int(midnight) + time_s - offset_s
+
+
+def rule_offset_at(rule, ts):
+    std, dst = rule
+    if dst is None:
+        return std, False
+    dst_offset, (start, start_time), (end, end_time) = dst
+    year = datetime.datetime.fromtimestamp(ts, datetime.timezone.utc).year
+    start_ts = transition_utc(year, start, start_time, std)
+    end_ts = transition_utc(year, end, end_time, dst_offset)
+    is_dst = start_ts <= ts < end_ts if start_ts < end_ts else (ts < end_ts or start_ts <= ts)
+    return (dst_offset, True) if is_dst else (std, False)
+
+
+def rule_breakpoints(rule):
+    std, dst = rule
+    if dst is None:
+        return []
+    dst_offset, (start, start_time), (end, end_time) = dst
+    return [t for y in range(1970, LAST_VERIFIED_YEAR + 1)
+            for t in (transition_utc(y, start, start_time, std), transition_utc(y, end, end_time, dst_offset))]
+
+
+def table_since_epoch(transitions):
+    """Transitions from epoch onwards, with the first one at epoch, dropping those not changing the offset."""
+    at_epoch = [t for t in transitions if t[0] is None or t[0] <= 0][-1]
+    table = [(0, at_epoch[1], at_epoch[2])]
+    for t in transitions:
+        if t[0] is not None and t[0] > 0 and (t[1], t[2]) != table[-1][1:]:
+            table.append(t)
+    return table
+
+
+def table_offset_at(table, ts):
+    return [t for t in table if t[0] <= ts][-1][1:]
+
+
+def trim(table, rule):
+    """Finds the shortest prefix of the table, from the last entry of which the rule reproduces the rest."""
+    end_ts = int(datetime.datetime(LAST_VERIFIED_YEAR + 1, 1, 1, tzinfo=datetime.timezone.utc).timestamp())
+    breakpoints = sorted({t[0] for t in table} | set(rule_breakpoints(rule)))
+    for k in range(len(table)):
+        points = [p + d for p in breakpoints if table[k][0] <= p < end_ts for d in (-1, 0)]
+        points = [p for p in points if p >= table[k][0]]
+        if all(rule_offset_at(rule, p) == table_offset_at(table, p) for p in points):
+            # a rule matching since epoch doesn't need any transitions
+            return [] if k == 0 else table[:k + 1]
+    raise AssertionError("footer rule doesn't match the latest transitions")
+
+
+def minutes(seconds):
+    assert seconds % 60 == 0, f"offset of {seconds}s not representable in minutes"
+    return seconds // 60
+
+
+def const_name(zone):
+    return re.sub(r"[^A-Z0-9]", "_", zone.upper())
+
+
+def rust_rule(rule):
+    std, dst = rule
+    if dst is None:
+        return f"TzRule {{ std_offset_minutes: {minutes(std)}, dst: None }}"
+    dst_offset, (start, start_time), (end, end_time) = dst
+    return (f"TzRule {{ std_offset_minutes: {minutes(std)}, dst: Some(DstRule {{ offset_minutes: {minutes(dst_offset)}, "
+            f"start: mwd{start}, start_time_seconds: {start_time}, end: mwd{end}, end_time_seconds: {end_time} }}) }}")
+
+
+def main():
+    zoneinfo = sys.argv[1] if len(sys.argv) > 1 else "/usr/share/zoneinfo"
+    version = open(f"{zoneinfo}/tzdata.zi").readline().split()[-1]
+    zones = sorted([(zone, feature) for feature, zones in ZONES.items() for zone in zones] + [("UTC", None)])
+
+    out = [
+        f"//! Embedded subset of the IANA tz database {version}, generated by `scripts/gen_tz_data.py` (and subsequently safeguarded by tests_props.rs).",
+        "use super::tz::*;",
+        "",
+        "#[allow(dead_code)]",
+        "const fn t(at_ms: u64, offset_minutes: i16, is_dst: bool) -> Transition {",
+        "    Transition { at_ms, offset: UtcOffset { offset_minutes, is_dst } }",
+        "}",
+        "",
+        "#[allow(dead_code)]",
+        "const fn mwd(month: u8, week: u8, weekday: u8) -> TransitionDay {",
+        "    TransitionDay::MonthWeekday { month, week, weekday }",
+        "}",
+        "",
+        "pub(crate) static ZONES: &[Zone] = &[",
+    ]
+    for zone, feature in zones:
+        if feature:
+            out.append(f"    #[cfg(feature = \"{feature}\")]")
+        out.append(f"    {const_name(zone)},")
+    out.append("];")
+
+    for zone, feature in zones:
+        if zone == "UTC":
+            transitions, rule = [], (0, None)
+        else:
+            all_transitions, footer = read_tzif(f"{zoneinfo}/{zone}")
+            rule = parse_footer(footer)
+            transitions = trim(table_since_epoch(all_transitions), rule)
+        out.append("")
+        if feature:
+            out.append(f"#[cfg(feature = \"{feature}\")]")
+        out.append(f"const {const_name(zone)}: Zone = Zone {{")
+        out.append(f"    name: \"{zone}\",")
+        if transitions:
+            out.append("    transitions: &[")
+            items = [f"t({t * 1000}, {minutes(o)}, {str(d).lower()})" for t, o, d in transitions]
+            for i in range(0, len(items), 6):
+                out.append("        " + ", ".join(items[i:i + 6]) + ",")
+            out.append("    ],")
+        else:
+            out.append("    transitions: &[],")
+        out.append(f"    rule: {rust_rule(rule)},")
+        out.append("};")
+    print("\n".join(out))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,94 @@
+#[cfg(feature = "scale")]
+use codec::{Decode, Encode, MaxEncodedLen};
+#[cfg(feature = "scale")]
+use scale_info::TypeInfo;
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+};
+
+/// Projection of ms from epoch onto block numbers, from `anchor_block` produced at `anchor_ms`, with blocks every `block_time_ms`.
+///
+/// Projections drift as actual block times deviate from `block_time_ms`, to be corrected by `BlockClock::reanchor()` on timestamps of new blocks.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let c = Calendar::create();
+/// let mut clock = BlockClock { anchor_block: 100, anchor_ms: 1650412800000, block_time_ms: 6000 };  // 20/04/2022 00:00:00:000
+/// let schedule = Schedule {
+///     start: DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 },
+///     items: vec![(Frequency::Minute, 1)],
+///     ..Default::default()
+/// };
+/// assert_eq!(c.next_occurrence_block(&clock, 100, BlockRounding::Up, &schedule), Some(110));
+///
+/// // block 105 produced 3s late, projecting subsequent blocks 3s later
+/// assert_eq!(clock.reanchor(105, 1650412833000), 3000);
+/// assert_eq!(clock.block_to_ms(110), Some(1650412863000));
+/// assert_eq!(c.next_occurrence_block(&clock, 105, BlockRounding::Up, &schedule), Some(110));
+/// assert_eq!(c.next_occurrence_block(&clock, 105, BlockRounding::Down, &schedule), Some(109));
+/// ```
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
+pub struct BlockClock {
+    pub anchor_block:  u64,
+    pub anchor_ms:     u64,
+    pub block_time_ms: u64,
+}
+
+/// Rounding of ms to blocks, for ms between timestamps of blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
+pub enum BlockRounding {
+    /// First block at or after, ie. never early
+    Up,
+    /// Last block at or before, ie. never late, other than rounding up occurrences prior to the next block by `Calendar::next_occurrence_block()`
+    Down,
+    /// Closest block, rounding up on ties, and as per `Down`
+    Nearest,
+}
+
+impl BlockClock {
+    /// Projected ms of `block`, returning `None` if before epoch or overflowing.
+    pub fn block_to_ms(&self, block: u64) -> Option<u64> {
+        let ms = self.anchor_ms as i128 + (block as i128 - self.anchor_block as i128) * self.block_time_ms as i128;
+        u64::try_from(ms).ok()
+    }
+
+    /// Projected block of `ms`, as per `rounding`, returning `None` if before block 0, overflowing, or `block_time_ms` is 0.
+    pub fn ms_to_block(&self, ms: u64, rounding: BlockRounding) -> Option<u64> {
+        if self.block_time_ms == 0 {
+            return None;
+        }
+        let (offset, block_time) = (ms as i128 - self.anchor_ms as i128, self.block_time_ms as i128);
+        let blocks = match rounding {
+            BlockRounding::Up      => -(-offset).div_euclid(block_time),
+            BlockRounding::Down    => offset.div_euclid(block_time),
+            BlockRounding::Nearest => (offset + block_time / 2).div_euclid(block_time),
+        };
+        u64::try_from(self.anchor_block as i128 + blocks).ok()
+    }
+
+    /// Re-anchors on actual ms of a new `block`, correcting drift of projections.
+    /// Returns the drift of the prior projection, ie. positive if `block` was late.
+    pub fn reanchor(&mut self, block: u64, ms: u64) -> i64 {
+        let projected = self.anchor_ms as i128 + (block as i128 - self.anchor_block as i128) * self.block_time_ms as i128;
+        self.anchor_block = block;
+        self.anchor_ms = ms;
+        (ms as i128 - projected).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+impl Calendar {
+    /// Finds the block of the next occurrence of `schedule` after `now_block`, as projected by `clock`, with the occurrence rounded to a block as per `rounding`.
+    /// Occurrences rounding to `now_block`, eg. rounding down an occurrence prior to the next block, are rounded up instead, ie. to the next block.
+    pub fn next_occurrence_block(&self, clock: &BlockClock, now_block: u64, rounding: BlockRounding, schedule: &Schedule) -> Option<u64> {
+        let now_ms = clock.block_to_ms(now_block).filter(|&ms| ms < YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1])?;
+        let occurrence_ms = now_ms + self.next_occurrence_ms(&self.from_unixtime(now_ms), schedule)?;
+        match clock.ms_to_block(occurrence_ms, rounding)? {
+            // occurrences are after `now_ms`, hence rounding up is after `now_block`
+            block if block == now_block => clock.ms_to_block(occurrence_ms, BlockRounding::Up),
+            block => Some(block),
+        }
+    }
+}
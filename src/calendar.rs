@@ -16,7 +16,8 @@ use super::{
 /// let schedule = Schedule {
 ///     start: DateTime { year: 2020, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
 ///     items: vec![(Frequency::Year, 1)],
-///     end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 })
+///     end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 }),
+///     ..Default::default()
 /// };
 /// assert!(c.validate_schedule(&schedule).is_ok());
 /// assert_eq!(Some(10*24*60*60*1000), c.next_occurrence_ms(&c.from_unixtime(now_in_ms), &schedule));  // triggers in 10 days
@@ -30,6 +31,8 @@ pub struct Calendar {
     year_ms_offsets:             &'static [u64],
     leap_year_month_offsets:     &'static [u64],
     non_leap_year_month_offsets: &'static [u64],
+    // (catch_up_limit, budget) of `Calendar::cost_bound()` enforced by `Calendar::validate_schedule()`
+    cost_budget:                 Option<(u32, u64)>,
 }
 
 impl Calendar {
@@ -39,9 +42,29 @@ impl Calendar {
             year_ms_offsets: YEAR_MS_OFFSETS,
             leap_year_month_offsets: LEAP_YEAR_MONTH_OFFSETS,
             non_leap_year_month_offsets: NON_LEAP_YEAR_MONTH_OFFSETS,
+            cost_budget: None,
         }
     }
 
+    /// Configures `Calendar::validate_schedule()` to reject schedules with `Calendar::cost_bound()` for `catch_up_limit` exceeding `budget`,
+    /// for callers to catch up on past triggers within the budget via `Calendar::next_occurrence_ms_with_past_triggers_capped()` of the same `catch_up_limit`.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let schedule = Schedule {
+    ///     start: DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
+    ///     items: vec![(Frequency::Month, 1)],
+    ///     days_of_month: (1..=31).collect(),
+    ///     times_of_day: (0..24).map(|hour| TimeOfDay { hour, minute: 0 }).collect(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(Calendar::create().validate_schedule(&schedule).is_ok());
+    /// assert_eq!(Calendar::create().with_cost_budget(10, 100_000).validate_schedule(&schedule), Err(ValidationError::OverBudget));
+    /// ```
+    pub fn with_cost_budget(mut self, catch_up_limit: u32, budget: u64) -> Self {
+        self.cost_budget = Some((catch_up_limit, budget));
+        self
+    }
+
     /// Converts a `&DateTime` to ms from epoch. Note: may panic if invalid `DateTime` specified.
     /// ```rust
     /// # use chrono_light::prelude::*;
@@ -49,20 +72,8 @@ impl Calendar {
     /// assert_eq!(c.to_unixtime(&DateTime {year: 2010, month: 10, day: 10, hour: 10, minute: 10, second: 10, ms: 10}), 1286705410010);
     /// ```
     pub fn to_unixtime(&self, dt: &DateTime) -> u64 {
-        let year = dt.year as usize - EPOCH_YEAR;
-        let year_offset = self.year_ms_offsets[year];
-        let month_offset = if LEAP_YEARS.contains(&(dt.year as u16)) {
-            self.leap_year_month_offsets[dt.month.checked_sub(1).expect("failed to calc month - 1") as usize]
-        } else {
-            self.non_leap_year_month_offsets[dt.month.checked_sub(1).expect("failed to calc month - 1") as usize]
-        };
-        let day_offset = dt.day.checked_sub(1).expect("failed to calc day - 1") as u64 * MS_IN_DAY;
-        let hour_offset = dt.hour as u64 * MS_IN_HOUR;
-        let minute_offset = dt.minute as u64 * MS_IN_MIN;
-        let second_offset = dt.second as u64 * MS_IN_SEC;
-        let ms_offset = dt.ms as u64;
-
-        year_offset + month_offset + day_offset + hour_offset + minute_offset + second_offset + ms_offset
+        let year_offset = self.year_ms_offsets[dt.year as usize - EPOCH_YEAR];
+        year_offset + Self::offset_in_year(dt, self.month_offsets(dt.year))
     }
 
     /// Converts a `&DateTime` to ms from epoch, returning `Some()` if supplied `DateTime` was valid, `None` otherwise.
@@ -78,36 +89,124 @@ impl Calendar {
         Ok(self.to_unixtime(dt))
     }
 
-    /// Converts ms from epoch to `DateTime`.
+    /// Converts ms from epoch to `DateTime`. Note: panics if `ts` is past the scope of `Calendar`, ie. from the start of year 4000.
     pub fn from_unixtime(&self, ts: u64) -> DateTime {
-        // find year
-        let mut year = CURRENT_YEAR - EPOCH_YEAR;
-        if ts > self.year_ms_offsets[year] {
-            while ts > self.year_ms_offsets[year+1] {
-                year += 1;
+        let year = self.year_index(ts);
+        let year_offset = ts - self.year_ms_offsets[year];
+        let month_offsets = self.month_offsets((year + EPOCH_YEAR) as u16);
+
+        let month = Self::month_index(year_offset, month_offsets);
+        Self::datetime_in_month(year, month, year_offset - month_offsets[month-1])
+    }
+
+    /// Converts `datetimes` to ms from epoch into `timestamps`, as per `Calendar::to_unixtime()`,
+    /// reusing the offsets of the prior year for consecutive `DateTime`s of the same year, eg. sorted `DateTime`s.
+    /// Note: may panic if invalid `DateTime` specified, or if lengths of `datetimes` and `timestamps` differ.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let datetimes = [
+    ///     DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 },
+    ///     DateTime { year: 2022, month: 4, day: 21, hour: 0, minute: 0, second: 0, ms: 0 },
+    /// ];
+    /// let mut timestamps = [0; 2];
+    /// c.to_unixtime_batch(&datetimes, &mut timestamps);
+    /// assert_eq!(timestamps, [1650412800000, 1650499200000]);
+    /// ```
+    pub fn to_unixtime_batch(&self, datetimes: &[DateTime], timestamps: &mut [u64]) {
+        assert_eq!(datetimes.len(), timestamps.len(), "failed to match lengths of datetimes and timestamps");
+        // offsets of the prior year, carried forward
+        let mut year = None;
+        let (mut year_offset, mut month_offsets) = (0, self.non_leap_year_month_offsets);
+        for (dt, ts) in datetimes.iter().zip(timestamps.iter_mut()) {
+            if year != Some(dt.year) {
+                year_offset = self.year_ms_offsets[dt.year as usize - EPOCH_YEAR];
+                month_offsets = self.month_offsets(dt.year);
+                year = Some(dt.year);
             }
-        } else {
-            year -= 1;
-            while ts < self.year_ms_offsets[year] {
-                year -= 1;
+            *ts = year_offset + Self::offset_in_year(dt, month_offsets);
+        }
+    }
+
+    /// Converts `timestamps` of ms from epoch into `datetimes`, as per `Calendar::from_unixtime()`,
+    /// carrying the year and month of the prior timestamp forward, skipping their lookups for sorted timestamps.
+    /// Note: may panic if timestamps are past the scope of `Calendar`, or if lengths of `timestamps` and `datetimes` differ.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let mut datetimes = vec![DateTime::default(); 2];
+    /// c.from_unixtime_batch(&[1650412800000, 1650499200000], &mut datetimes);
+    /// assert_eq!(datetimes, [
+    ///     DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 },
+    ///     DateTime { year: 2022, month: 4, day: 21, hour: 0, minute: 0, second: 0, ms: 0 },
+    /// ]);
+    /// ```
+    pub fn from_unixtime_batch(&self, timestamps: &[u64], datetimes: &mut [DateTime]) {
+        assert_eq!(timestamps.len(), datetimes.len(), "failed to match lengths of timestamps and datetimes");
+        // year and month of the prior timestamp, carried forward
+        let (mut year, mut month) = (0_usize, 1_usize);
+        let mut month_offsets = self.month_offsets(EPOCH_YEAR as u16);
+        for (&ts, dt) in timestamps.iter().zip(datetimes.iter_mut()) {
+            if ts < self.year_ms_offsets[year] || ts >= self.year_ms_offsets[year+1] {
+                year = self.year_index(ts);
+                month_offsets = self.month_offsets((year + EPOCH_YEAR) as u16);
+            }
+            let year_offset = ts - self.year_ms_offsets[year];
+            if year_offset < month_offsets[month-1] || year_offset >= month_offsets[month] {
+                month = Self::month_index(year_offset, month_offsets);
             }
+            *dt = Self::datetime_in_month(year, month, year_offset - month_offsets[month-1]);
         }
-        let year_offset = ts - self.year_ms_offsets[year];
-        let month_offsets = if LEAP_YEARS.contains(&(year as u16 + EPOCH_YEAR as u16)) {
-            &self.leap_year_month_offsets
+    }
+
+    /// Index of the year of `ts` in `year_ms_offsets`, in constant time.
+    /// Estimated by days from epoch over the mean Gregorian year, off by at most one year either way within the scope of `Calendar`,
+    /// asserting `ts` is within the scope, as the estimate for the last year may index past `year_ms_offsets` otherwise.
+    fn year_index(&self, ts: u64) -> usize {
+        assert!(ts < self.year_ms_offsets[self.year_ms_offsets.len() - 1], "failed to find year of ts past the scope of Calendar");
+        let year = (ts / MS_IN_DAY * 400 / DAYS_IN_400_YEARS) as usize;
+        if ts < self.year_ms_offsets[year] {
+            year - 1
+        } else if ts >= self.year_ms_offsets[year+1] {
+            year + 1
         } else {
-            &self.non_leap_year_month_offsets
-        };
-        
-        let mut month = 1_usize;
-        if year_offset > 0 {
-            while year_offset > month_offsets[month-1] {
-                month += 1;
-            }
-            month -= 1;
+            year
         }
+    }
 
-        let day_offset = year_offset - month_offsets[month-1];
+    /// Month of `year_offset` ms from the start of the year, of `month_offsets` of the year, in constant time.
+    /// Estimated by days of the year over the longest month, short by at most one month.
+    fn month_index(year_offset: u64, month_offsets: &[u64]) -> usize {
+        let month = (year_offset / MS_IN_DAY / 31) as usize + 1;
+        if year_offset >= month_offsets[month] {
+            month + 1
+        } else {
+            month
+        }
+    }
+
+    fn month_offsets(&self, year: u16) -> &'static [u64] {
+        if is_leap_year(year) {
+            self.leap_year_month_offsets
+        } else {
+            self.non_leap_year_month_offsets
+        }
+    }
+
+    /// Ms of `dt` from the start of its year, of `month_offsets` of the year.
+    fn offset_in_year(dt: &DateTime, month_offsets: &[u64]) -> u64 {
+        let month_offset = month_offsets[dt.month.checked_sub(1).expect("failed to calc month - 1") as usize];
+        let day_offset = dt.day.checked_sub(1).expect("failed to calc day - 1") as u64 * MS_IN_DAY;
+        let hour_offset = dt.hour as u64 * MS_IN_HOUR;
+        let minute_offset = dt.minute as u64 * MS_IN_MIN;
+        let second_offset = dt.second as u64 * MS_IN_SEC;
+        let ms_offset = dt.ms as u64;
+
+        month_offset + day_offset + hour_offset + minute_offset + second_offset + ms_offset
+    }
+
+    /// `DateTime` of `day_offset` ms from the start of `month` of the year at `year` index.
+    fn datetime_in_month(year: usize, month: usize, day_offset: u64) -> DateTime {
         let day = day_offset / MS_IN_DAY + 1;
         let hour = (day_offset % MS_IN_DAY) / MS_IN_HOUR;
         let minute = (day_offset % MS_IN_HOUR) / MS_IN_MIN;
@@ -125,20 +224,87 @@ impl Calendar {
         }
     }
 
+    /// Converts ms from epoch to `OffsetDateTime` in local wall time of `offset_minutes`. Note: may panic if local wall time falls out of scope.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let odt = c.from_unixtime_with_offset(1286705410010, 330);
+    /// assert_eq!(odt.datetime, DateTime {year: 2010, month: 10, day: 10, hour: 15, minute: 40, second: 10, ms: 10});
+    /// assert_eq!(odt.to_string(), "2010-10-10T15:40:10.010+05:30");
+    /// ```
+    pub fn from_unixtime_with_offset(&self, ts: u64, offset_minutes: i16) -> OffsetDateTime {
+        let local_ts = shift_by_offset(ts, offset_minutes).expect("failed to calc local time");
+        OffsetDateTime { datetime: self.from_unixtime(local_ts), offset_minutes }
+    }
+
+    /// Converts a `&OffsetDateTime` to ms from epoch (UTC). Note: may panic if invalid `OffsetDateTime` specified.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let odt = OffsetDateTime { datetime: DateTime {year: 2010, month: 10, day: 10, hour: 15, minute: 40, second: 10, ms: 10}, offset_minutes: 330 };
+    /// assert_eq!(c.to_unixtime_with_offset(&odt), 1286705410010);
+    /// ```
+    pub fn to_unixtime_with_offset(&self, odt: &OffsetDateTime) -> u64 {
+        shift_by_offset(self.to_unixtime(&odt.datetime), -odt.offset_minutes).expect("failed to calc utc time")
+    }
+
+    /// Converts a `&OffsetDateTime` to ms from epoch (UTC), returning `Ok()` if supplied `OffsetDateTime` was valid, `Err()` otherwise.
+    pub fn to_unixtime_with_offset_res(&self, odt: &OffsetDateTime) -> Result<u64, ValidationError> {
+        self.validate_offset_datetime(odt)?;
+        Ok(self.to_unixtime_with_offset(odt))
+    }
+
+    /// Converts an `&OffsetDateTime` to local wall time of another offset, representing the same instant.
+    pub fn to_offset(&self, odt: &OffsetDateTime, offset_minutes: i16) -> OffsetDateTime {
+        self.from_unixtime_with_offset(self.to_unixtime_with_offset(odt), offset_minutes)
+    }
+
+    /// Given a `now` (UTC) `DateTime` and `Schedule` in local wall time of `offset_minutes`, finds ms delta when the next occurrence should trigger.
+    /// If cut of by `Schedule.end`, or `now` falls out of scope in local wall time, returns a `None`.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let schedule = Schedule {
+    ///     start: DateTime { year: 2022, month: 4, day: 30, hour: 9, minute: 0, second: 0, ms: 0 },  // 09:00 in +05:30
+    ///     items: vec![(Frequency::Day, 1)],
+    ///     ..Default::default()
+    /// };
+    /// let now = DateTime { year: 2022, month: 5, day: 1, hour: 0, minute: 0, second: 0, ms: 0 };   // 05:30 in +05:30
+    /// assert_eq!(Some(3*60*60*1000 + 30*60*1000), c.next_occurrence_ms_at_offset(&now, 330, &schedule));
+    /// ```
+    pub fn next_occurrence_ms_at_offset(&self, now: &DateTime, offset_minutes: i16, schedule: &Schedule) -> Option<u64> /* delta_in_ms */ {
+        let local_now_ms = shift_by_offset(self.to_unixtime(now), offset_minutes)
+            .filter(|&ts| ts < self.year_ms_offsets[self.year_ms_offsets.len() - 1])?;
+        self.next_occurrence_ms(&self.from_unixtime(local_now_ms), schedule)
+    }
+
     /// Given a `now` `DateTime` and `Schedule`, finds ms delta when the next occurrence should trigger.
     /// If cut of by `Schedule.end`, returns a `None`.
     pub fn next_occurrence_ms(&self, now: &DateTime, schedule: &Schedule) -> Option<u64> /* delta_in_ms */ {
         let now_in_ms = self.to_unixtime(now);
         let start_in_ms = self.to_unixtime(&schedule.start);
-        let is_expired = || schedule.end.as_ref().map_or(false, |end_dt| now_in_ms > self.to_unixtime(end_dt));
+        let is_expired = || schedule.end.as_ref().is_some_and(|end_dt| now_in_ms > self.to_unixtime(end_dt));
+
+        let is_expanded = !schedule.times_of_day.is_empty() || !schedule.days_of_month.is_empty();
 
-        if now_in_ms < start_in_ms {
+        if now_in_ms < start_in_ms && !is_expanded {
             Some(start_in_ms - now_in_ms)
         } else if is_expired() {
             None
         } else {
-            let next_trigger = schedule.items.iter().map(|(freq, multiplier)| {
+            // expanded triggers need to be at or after start, and after now
+            let after_ms = now_in_ms.max(start_in_ms.saturating_sub(1));
+            let next_trigger = schedule.items.iter().filter_map(|(freq, multiplier)| {
                 match freq {
+                    Frequency::Year | Frequency::Month if is_expanded => {
+                        let months_per_period = if *freq == Frequency::Year { 12 * multiplier } else { *multiplier };
+                        self.next_expanded_month_ms(after_ms, months_per_period, schedule).map(|ts| ts - now_in_ms)
+                    }
+                    Frequency::Week | Frequency::Day if !schedule.times_of_day.is_empty() => {
+                        let days_per_period = if *freq == Frequency::Week { 7 * *multiplier as u64 } else { *multiplier as u64 };
+                        self.next_expanded_day_ms(after_ms, days_per_period, schedule).map(|ts| ts - now_in_ms)
+                    }
+                    _ if now_in_ms < start_in_ms => Some(start_in_ms - now_in_ms),
                     Frequency::Year => {
                         let m_delta = now.month as i64 - schedule.start.month as i64 + i64::from(now.to_day_unixtime() >= schedule.start.to_day_unixtime());
                         let y_delta = now.year as i64 - schedule.start.year as i64 + i64::from(m_delta > 0);
@@ -152,7 +318,7 @@ impl Calendar {
                             second: schedule.start.second,
                             ms: schedule.start.ms,
                         };
-                        self.to_unixtime(&next_occurrence) - self.to_unixtime(now)
+                        Some(self.to_unixtime(&next_occurrence) - self.to_unixtime(now))
                     }
                     Frequency::Month => {
                         let m_delta = now.month as i64 - schedule.start.month as i64 + i64::from(now.to_day_unixtime() >= schedule.start.to_day_unixtime());
@@ -167,15 +333,15 @@ impl Calendar {
                             second: schedule.start.second,
                             ms: schedule.start.ms,
                         };
-                        self.to_unixtime(&next_occurrence) - self.to_unixtime(now)
+                        Some(self.to_unixtime(&next_occurrence) - self.to_unixtime(now))
                     }
                     Frequency::Week | Frequency::Day | Frequency::Hour | Frequency::Minute | Frequency::Second | Frequency::Ms => {
                         let freq_in_ms = freq.to_ms() as u64 * *multiplier as u64;
                         let ms_in_this_period = (now_in_ms - start_in_ms) % freq_in_ms;
                         if ms_in_this_period == 0 {
-                            freq_in_ms
+                            Some(freq_in_ms)
                         } else {
-                            freq_in_ms - ms_in_this_period
+                            Some(freq_in_ms - ms_in_this_period)
                         }
                     },
                 }
@@ -193,12 +359,90 @@ impl Calendar {
         }
     }
 
+    /// Finds the earliest trigger after `after_ms` of `Year`/`Month` periods, expanded by `Schedule.days_of_month` and `Schedule.times_of_day`.
+    fn next_expanded_month_ms(&self, after_ms: u64, months_per_period: u32, schedule: &Schedule) -> Option<u64> /* trigger_in_ms */ {
+        let start = &schedule.start;
+        let from = self.from_unixtime(after_ms.max(self.to_unixtime(start)));
+        let start_month_index = start.year as u32 * 12 + start.month as u32 - 1;
+        let from_month_index = from.year as u32 * 12 + from.month as u32 - 1;
+        let first_period = from_month_index.saturating_sub(start_month_index) / months_per_period;
+        let max_year = (EPOCH_YEAR + self.year_ms_offsets.len() - 1) as u32;
+
+        (first_period..first_period + MAX_EXPANDED_PERIODS)
+            .map(|period| start_month_index + period * months_per_period)
+            .take_while(|month_index| month_index / 12 <= max_year)
+            .find_map(|month_index| {
+                let year = (month_index / 12) as u16;
+                let month = (month_index % 12) as u8 + 1;
+                if schedule.days_of_month.is_empty() {
+                    let date_ms = self.to_unixtime(&DateTime { year, month, day: start.day, hour: 0, minute: 0, second: 0, ms: 0 });
+                    self.next_expanded_time_of_day_ms(after_ms, date_ms, schedule)
+                } else {
+                    schedule.days_of_month.iter()
+                        .filter(|&&day| day <= days_in_month(year, month))
+                        .filter_map(|&day| {
+                            let date_ms = self.to_unixtime(&DateTime { year, month, day, hour: 0, minute: 0, second: 0, ms: 0 });
+                            self.next_expanded_time_of_day_ms(after_ms, date_ms, schedule)
+                        })
+                        .min()
+                }
+            })
+    }
+
+    /// Finds the earliest trigger after `after_ms` of `Week`/`Day` periods, expanded by `Schedule.times_of_day`.
+    fn next_expanded_day_ms(&self, after_ms: u64, days_per_period: u64, schedule: &Schedule) -> Option<u64> /* trigger_in_ms */ {
+        let start_day = self.to_unixtime(&schedule.start) / MS_IN_DAY;
+        let from_day = after_ms / MS_IN_DAY;
+        let first_period = from_day.saturating_sub(start_day) / days_per_period;
+        // every period expands to at least 1 trigger, all of which are after `after_ms` in the period following `first_period`
+        (first_period..first_period + 2).find_map(|period| {
+            let date_ms = (start_day + period * days_per_period) * MS_IN_DAY;
+            self.next_expanded_time_of_day_ms(after_ms, date_ms, schedule)
+        })
+    }
+
+    /// Finds the earliest trigger after `after_ms` on a given date, expanded by `Schedule.times_of_day`, or at `Schedule.start` time if not expanded.
+    fn next_expanded_time_of_day_ms(&self, after_ms: u64, date_ms: u64, schedule: &Schedule) -> Option<u64> /* trigger_in_ms */ {
+        let start = &schedule.start;
+        let sub_minute_ms = start.second as u64 * MS_IN_SEC + start.ms as u64;
+        if schedule.times_of_day.is_empty() {
+            let start_time_of_day_ms = start.hour as u64 * MS_IN_HOUR + start.minute as u64 * MS_IN_MIN + sub_minute_ms;
+            Some(date_ms + start_time_of_day_ms).filter(|&ts| ts > after_ms)
+        } else {
+            schedule.times_of_day.iter()
+                .map(|t| date_ms + t.hour as u64 * MS_IN_HOUR + t.minute as u64 * MS_IN_MIN + sub_minute_ms)
+                .filter(|&ts| ts > after_ms)
+                .min()
+        }
+    }
+
     pub fn next_occurrence_ms_with_past_triggers(&self, last_run: Option<&DateTime>, now: &DateTime, schedule: &Schedule) -> (Vec<u64>, Option<u64>) /* triggers_in_ms, delta_in_ms */ {
+        self.next_occurrence_ms_with_past_triggers_capped(last_run, now, schedule, u32::MAX)
+    }
+
+    /// As per `Calendar::next_occurrence_ms_with_past_triggers()`, catching up on at most `catch_up_limit` triggers, within `Calendar::cost_bound()` of `catch_up_limit`,
+    /// eg. of `Calendar::with_cost_budget()`.
+    /// If more triggers are due, returns the earliest `catch_up_limit` triggers and a delta of 0, the remaining triggers being caught up on by calling again with `last_run` of the last trigger.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let schedule = Schedule {
+    ///     start: DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
+    ///     items: vec![(Frequency::Day, 1)],
+    ///     ..Default::default()
+    /// };
+    /// let now = DateTime { year: 2022, month: 5, day: 10, hour: 12, minute: 0, second: 0, ms: 0 };
+    /// let (triggers, delta) = c.next_occurrence_ms_with_past_triggers_capped(Some(&schedule.start), &now, &schedule, 3);
+    /// assert_eq!((triggers.len(), delta), (3, Some(0)));
+    /// let (triggers, delta) = c.next_occurrence_ms_with_past_triggers_capped(Some(&c.from_unixtime(triggers[2])), &now, &schedule, 10);
+    /// assert_eq!((triggers.len(), delta), (7, Some(12 * 60 * 60 * 1000)));
+    /// ```
+    pub fn next_occurrence_ms_with_past_triggers_capped(&self, last_run: Option<&DateTime>, now: &DateTime, schedule: &Schedule, catch_up_limit: u32) -> (Vec<u64>, Option<u64>) /* triggers_in_ms, delta_in_ms */ {
         let t0 = DateTime { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0, ms: 0 };
-        let now_ms = self.to_unixtime(&now);
+        let now_ms = self.to_unixtime(now);
         let mut triggers = Vec::new();
         let mut next_trigger: Option<u64> = None;
-        let mut last_run = last_run.map(|x| x.clone()).unwrap_or(t0);
+        let mut last_run = last_run.cloned().unwrap_or(t0);
         let mut last_run_ms = self.to_unixtime(&last_run);
         while last_run_ms <= now_ms {
             next_trigger = self.next_occurrence_ms(&last_run, schedule).map(|x| x + last_run_ms);
@@ -206,10 +450,13 @@ impl Calendar {
                 let next_trigger = self.from_unixtime(trigger_ms);
                 last_run = next_trigger;
                 last_run_ms = trigger_ms;
-                if trigger_ms <= now_ms {
-                    triggers.push(trigger_ms);
-                } else {
+                if trigger_ms > now_ms {
                     break;
+                } else if triggers.len() as u32 >= catch_up_limit {
+                    // due, but over the limit
+                    return (triggers, Some(0));
+                } else {
+                    triggers.push(trigger_ms);
                 }
             } else {
                 break;
@@ -219,6 +466,52 @@ impl Calendar {
         (triggers, next_trigger_delay)
     }
 
+    /// Deterministic upper bound of work units, ie. table lookups and loop iterations, of `Calendar::next_occurrence_ms_with_past_triggers_capped()`
+    /// catching up on at most `catch_up_limit` past triggers, dependent only on the contents of `schedule`.
+    /// Bounds of `Calendar::next_occurrence_ms()` correspond to `catch_up_limit` of 0.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let mut schedule = Schedule {
+    ///     start: DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
+    ///     items: vec![(Frequency::Month, 1)],
+    ///     ..Default::default()
+    /// };
+    /// let plain = c.cost_bound(&schedule, 0);
+    /// assert!(c.cost_bound(&schedule, 10) > plain);
+    /// schedule.days_of_month = vec![1, 15];
+    /// assert!(c.cost_bound(&schedule, 0) > plain);
+    /// ```
+    pub fn cost_bound(&self, schedule: &Schedule, catch_up_limit: u32) -> u64 {
+        // every iteration evaluates the schedule and converts the trigger, with 1 more iteration finding the trigger after now
+        let iteration = self.next_occurrence_cost(schedule).saturating_add(FROM_UNIXTIME_COST);
+        (catch_up_limit as u64 + 1).saturating_mul(iteration).saturating_add(2 * TO_UNIXTIME_COST)
+    }
+
+    /// Upper bound of work units of `Calendar::next_occurrence_ms()`, see `Calendar::cost_bound()`.
+    fn next_occurrence_cost(&self, schedule: &Schedule) -> u64 {
+        let is_expanded = !schedule.times_of_day.is_empty() || !schedule.days_of_month.is_empty();
+        let times = schedule.times_of_day.len().max(1) as u64;
+        // conversions of now and start, and end twice
+        let base = 4 * TO_UNIXTIME_COST;
+        schedule.items.iter().fold(base, |cost, (freq, _)| {
+            let item = match freq {
+                Frequency::Year | Frequency::Month if is_expanded => {
+                    let period = if schedule.days_of_month.is_empty() {
+                        TO_UNIXTIME_COST + times
+                    } else {
+                        schedule.days_of_month.len() as u64 * (DAYS_IN_MONTH_COST + TO_UNIXTIME_COST + times)
+                    };
+                    TO_UNIXTIME_COST + FROM_UNIXTIME_COST + MAX_EXPANDED_PERIODS as u64 * period
+                }
+                Frequency::Week | Frequency::Day if !schedule.times_of_day.is_empty() => TO_UNIXTIME_COST + 2 * times,
+                Frequency::Year | Frequency::Month => 2 * TO_UNIXTIME_COST,
+                _ => 1,
+            };
+            cost.saturating_add(item)
+        })
+    }
+
     /// Finds ms delta between 2 `DateTime`s.
     pub fn ms_between(&self, from: &DateTime, to: &DateTime) -> i64 {
         (self.to_unixtime(to) as i64).checked_sub(self.to_unixtime(from) as i64).expect("failed to calc ms_between")
@@ -238,25 +531,50 @@ impl Calendar {
         }
 
         // leap year check
-        let is_leap_year = LEAP_YEARS.contains(&(dt.year as u16));
-        if (is_leap_year && dt.day > MONTH_FOR_LEAP_YEAR[dt.month.checked_sub(1).expect("failed to calc month - 1") as usize]) ||
-            (!is_leap_year && dt.day > MONTH_FOR_NON_LEAP_YEAR[dt.month.checked_sub(1).expect("failed to calc month - 1") as usize]) {
+        if dt.day > days_in_month(dt.year, dt.month) {
             return Err(ValidationError::Invalid);
         }
         Ok(())
     }
 
+    /// Validates `OffsetDateTime` for correctness of fields, offset range, and scope of the UTC representation.
+    pub fn validate_offset_datetime(&self, odt: &OffsetDateTime) -> Result<(), ValidationError> {
+        self.validate_datetime(&odt.datetime)?;
+        if odt.offset_minutes.unsigned_abs() > MAX_OFFSET_MINUTES as u16 {
+            return Err(ValidationError::Invalid);
+        }
+        match shift_by_offset(self.to_unixtime(&odt.datetime), -odt.offset_minutes) {
+            Some(ts) if ts < self.year_ms_offsets[self.year_ms_offsets.len() - 1] => Ok(()),
+            _ => Err(ValidationError::OutOfScope),
+        }
+    }
+
     pub fn validate_schedule(&self, schedule: &Schedule) -> Result<(), ValidationError> {
         self.validate_datetime(&schedule.start)?;
         if let Some(end) = &schedule.end {
-            self.validate_datetime(&end)?;
+            self.validate_datetime(end)?;
         }
 
-        let start_before_end = schedule.end.as_ref().map_or(true, |end| schedule.start <= *end);
+        let start_before_end = schedule.end.as_ref().is_none_or(|end| schedule.start <= *end);
         let all_freqs_non_zero_multiplier = schedule.items.iter().all(|&(_, x)| x > 0);
         if !start_before_end || !all_freqs_non_zero_multiplier {
             return Err(ValidationError::Invalid);
         }
-        Ok(())
+
+        // expansions check, bounded and only applicable to periods of at least a day (times of day) or a month (days of month)
+        let times_of_day_valid = schedule.times_of_day.len() <= MAX_TIMES_OF_DAY
+            && schedule.times_of_day.iter().all(|t| t.hour < 24 && t.minute < 60)
+            && (schedule.times_of_day.is_empty() || schedule.items.iter().all(|(freq, _)| matches!(freq, Frequency::Year | Frequency::Month | Frequency::Week | Frequency::Day)));
+        let days_of_month_valid = schedule.days_of_month.len() <= MAX_DAYS_OF_MONTH
+            && schedule.days_of_month.iter().all(|day| (1..=31).contains(day))
+            && (schedule.days_of_month.is_empty() || schedule.items.iter().all(|(freq, _)| matches!(freq, Frequency::Year | Frequency::Month)));
+        if !times_of_day_valid || !days_of_month_valid {
+            return Err(ValidationError::Invalid);
+        }
+
+        match self.cost_budget {
+            Some((catch_up_limit, budget)) if self.cost_bound(schedule, catch_up_limit) > budget => Err(ValidationError::OverBudget),
+            _ => Ok(()),
+        }
     }
 }
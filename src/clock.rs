@@ -0,0 +1,126 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicU64, Ordering};
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+};
+
+/// Source of the current time, in ms from epoch, eg. system time, or RTC of embedded devices.
+pub trait Clock {
+    fn now_ms(&self) -> u64;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now_ms(&self) -> u64 {
+        (**self).now_ms()
+    }
+}
+
+/// `Clock` of `std::time::SystemTime`, at epoch if set prior to epoch.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64)
+    }
+}
+
+/// `Clock` stopped at ms from epoch.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now_ms(&self) -> u64 {
+        self.0
+    }
+}
+
+/// `Clock` set and advanced explicitly, eg. in tests. Only on targets with 64-bit atomics, ie. not on eg. `thumbv6m`/`riscv32imc`.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let clock = ManualClock::new(1650412800000);  // 20/04/2022 00:00:00:000
+/// clock.advance(60_000);
+/// assert_eq!(clock.now_ms(), 1650412860000);
+/// clock.set(0);
+/// assert_eq!(clock.now_ms(), 0);
+/// ```
+#[cfg(target_has_atomic = "64")]
+#[derive(Default, Debug)]
+pub struct ManualClock(AtomicU64);
+
+#[cfg(target_has_atomic = "64")]
+impl ManualClock {
+    pub fn new(now_ms: u64) -> Self {
+        Self(AtomicU64::new(now_ms))
+    }
+
+    pub fn set(&self, now_ms: u64) {
+        self.0.store(now_ms, Ordering::SeqCst);
+    }
+
+    /// Advances by `ms`, saturating at `u64::MAX`.
+    pub fn advance(&self, ms: u64) {
+        let _ = self.0.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |now_ms| Some(now_ms.saturating_add(ms)));
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl Calendar {
+    /// Current `DateTime` of `clock`. Note: may panic if `clock` is past the scope of `Calendar`.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// assert_eq!(c.now(&FixedClock(1650412800000)), DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 });
+    /// ```
+    pub fn now(&self, clock: &impl Clock) -> DateTime {
+        self.from_unixtime(clock.now_ms())
+    }
+
+    /// Current `DateTime` of `clock`, returning `ValidationError::OutOfScope` if past the scope of `Calendar`.
+    pub fn now_res(&self, clock: &impl Clock) -> Result<DateTime, ValidationError> {
+        let now_ms = clock.now_ms();
+        if now_ms >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return Err(ValidationError::OutOfScope);
+        }
+        Ok(self.from_unixtime(now_ms))
+    }
+
+    /// Finds ms delta of the next occurrence of `schedule` after now of `clock`, as per `Calendar::next_occurrence_ms()`.
+    /// Returns `None` if `clock` is past the scope of `Calendar`.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let schedule = Schedule {
+    ///     start: DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 },
+    ///     items: vec![(Frequency::Hour, 1)],
+    ///     ..Default::default()
+    /// };
+    /// let clock = ManualClock::new(1650412800000);  // 20/04/2022 00:00:00:000
+    /// clock.advance(600_000);
+    /// assert_eq!(c.next_occurrence_from_clock(&schedule, &clock), Some(3_000_000));
+    /// ```
+    pub fn next_occurrence_from_clock(&self, schedule: &Schedule, clock: &impl Clock) -> Option<u64> /* delta_in_ms */ {
+        self.next_occurrence_ms(&self.now_res(clock).ok()?, schedule)
+    }
+
+    /// Finds past triggers since `last_run` up to now of `clock`, and ms delta of the next occurrence, as per `Calendar::next_occurrence_ms_with_past_triggers()`.
+    /// Returns no triggers if `clock` is past the scope of `Calendar`.
+    pub fn past_triggers_from_clock(&self, last_run: Option<&DateTime>, schedule: &Schedule, clock: &impl Clock) -> (Vec<u64>, Option<u64>) /* triggers_in_ms, delta_in_ms */ {
+        match self.now_res(clock) {
+            Ok(now) => self.next_occurrence_ms_with_past_triggers(last_run, &now, schedule),
+            Err(_) => (Vec::new(), None),
+        }
+    }
+}
@@ -1,6 +1,3 @@
-/// Could be altered on yearly basis to speed up the search
-pub(crate) const CURRENT_YEAR: usize = 2022;
-
 pub(crate) const MS_IN_SEC: u64  = 1000;
 pub(crate) const MS_IN_MIN: u64  = 60 * MS_IN_SEC;
 pub(crate) const MS_IN_HOUR: u64 = 60 * MS_IN_MIN;
@@ -11,7 +8,28 @@ pub(crate) const MONTH_FOR_NON_LEAP_YEAR: &[u8] = &[31, 28, 31, 30, 31, 30, 31,
 pub(crate) const MONTH_FOR_LEAP_YEAR: &[u8]     = &[31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
 
 pub(crate) const EPOCH_YEAR: usize = 1970;
+/// Days in a Gregorian cycle of 400 years, estimating years of days from epoch
+pub(crate) const DAYS_IN_400_YEARS: u64 = 146097;
+/// Weekday of 01/01/1970, with 0 = Sunday
+pub(crate) const EPOCH_WEEKDAY: u64 = 4;
+
+// for formatting, weekdays starting with Sunday
+pub(crate) const WEEKDAY_NAMES: &[&str] = &["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+pub(crate) const MONTH_NAMES: &[&str] = &["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+
+/// Max number of periods searched for an expanded trigger, covering all combinations of months and leap years (4 * 12)
+pub(crate) const MAX_EXPANDED_PERIODS: u32 = 48;
+
+// work units of `Calendar::cost_bound()`, as table lookups and loop iterations
+/// `Calendar::to_unixtime()`, checking leap year and looking up year/month offsets
+pub(crate) const TO_UNIXTIME_COST: u64 = 3;
+/// `Calendar::from_unixtime()`, estimating and correcting year and month, and checking leap year
+pub(crate) const FROM_UNIXTIME_COST: u64 = 5;
+/// `days_in_month()`, checking leap year and looking up month length
+pub(crate) const DAYS_IN_MONTH_COST: u64 = 2;
 
+/// Reference table of leap years, validating `is_leap_year()` in tests
+#[cfg(test)]
 pub(crate) const LEAP_YEARS: &[u16] = &[
     1704, 1708, 1712, 1716, 1720, 1724, 1728, 1732, 1736, 1740, 1744, 1748, 1752, 1756, 1760, 1764, 1768, 1772, 1776, 1780, 1784, 1788, 1792, 1796,
     1804, 1808, 1812, 1816, 1820, 1824, 1828, 1832, 1836, 1840, 1844, 1848, 1852, 1856, 1860, 1864, 1868, 1872, 1876, 1880, 1884, 1888, 1892, 1896,
@@ -0,0 +1,197 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+use super::{
+    calendar::Calendar,
+    iso8601::parse_iso_datetime,
+    types::*,
+};
+
+/// Formats as compact DSL, eg. `start=2022-04-30T00:00;every=2w,3d;until=2025-04-30`, see `FromStr`.
+/// Optional fields are omitted, seconds and ms of `DateTime`s only present if set, and `until` without time at midnight.
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "start=")?;
+        write_dsl_datetime(f, &self.start, false)?;
+        if !self.items.is_empty() {
+            write!(f, ";every=")?;
+            for (i, (freq, multiplier)) in self.items.iter().enumerate() {
+                write!(f, "{}{}{}", if i == 0 { "" } else { "," }, multiplier, unit(freq))?;
+            }
+        }
+        if !self.days_of_month.is_empty() {
+            write!(f, ";on=")?;
+            for (i, day) in self.days_of_month.iter().enumerate() {
+                write!(f, "{}{}", if i == 0 { "" } else { "," }, day)?;
+            }
+        }
+        if !self.times_of_day.is_empty() {
+            write!(f, ";at=")?;
+            for (i, time_of_day) in self.times_of_day.iter().enumerate() {
+                write!(f, "{}{}", if i == 0 { "" } else { "," }, time_of_day)?;
+            }
+        }
+        if let Some(end) = &self.end {
+            write!(f, ";until=")?;
+            write_dsl_datetime(f, end, true)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses compact DSL of `;` separated fields, eg. `start=2022-04-30T00:00;every=2w,3d;until=2025-04-30`:
+/// - `start` (required) and `until` (optional) `DateTime`s, as per `%Y-%m-%d`, with optional time as per `T%H:%M`, `T%H:%M:%S` or `T%H:%M:%S.%L`
+/// - `every` comma separated multiplier and unit of `Frequency`, ie. one of `y`, `mo`, `w`, `d`, `h`, `m`, `s`, `ms`
+/// - `on` comma separated `days_of_month`, eg. `1,15`
+/// - `at` comma separated `times_of_day`, eg. `09:00,18:30`
+///
+/// Resulting `Schedule` is validated by `Calendar::validate_schedule()`, with failures reported as `ParseErrorKind::OutOfRange`.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let schedule: Schedule = "start=2022-04-30T00:00;every=2w,3d;until=2025-04-30".parse().unwrap();
+/// assert_eq!(schedule.items, vec![(Frequency::Week, 2), (Frequency::Day, 3)]);
+/// assert_eq!(schedule.to_string(), "start=2022-04-30T00:00;every=2w,3d;until=2025-04-30");
+/// assert_eq!("start=2022-04-30;every=2x".parse::<Schedule>(), Err(ParseError { position: 24, kind: ParseErrorKind::Unexpected }));
+/// ```
+impl FromStr for Schedule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = Calendar::create();
+        let mut schedule = Schedule::default();
+        // positions of field values, and of days and times of day, for validation errors
+        let (mut start, mut every, mut on, mut at, mut until) = (None, None, None, None, None);
+        let (mut day_positions, mut time_positions) = (Vec::new(), Vec::new());
+
+        for (position, field) in split(s, ';', 0) {
+            let (key, value) = field.split_once('=').ok_or(ParseError { position: position + field.len(), kind: too_short_or_unexpected(s, position + field.len()) })?;
+            let value_position = position + key.len() + 1;
+            let seen = match key {
+                "start" => start.replace(value_position),
+                "every" => every.replace(value_position),
+                "on"    => on.replace(value_position),
+                "at"    => at.replace(value_position),
+                "until" => until.replace(value_position),
+                _ => return Err(ParseError { position, kind: ParseErrorKind::Unexpected }),
+            };
+            if seen.is_some() {
+                return Err(ParseError { position, kind: ParseErrorKind::Unexpected });
+            }
+            match key {
+                "start" => schedule.start = parse_iso_datetime(&c, value, value_position)?,
+                "until" => schedule.end = Some(parse_iso_datetime(&c, value, value_position)?),
+                "every" => schedule.items = split(value, ',', value_position).map(|(position, item)| parse_item(item, position)).collect::<Result<Vec<_>, _>>()?,
+                "on" => for (position, day) in split(value, ',', value_position) {
+                    schedule.days_of_month.push(parse_number(day, position, 1, 31)? as u8);
+                    day_positions.push(position);
+                },
+                _ => for (position, time_of_day) in split(value, ',', value_position) {
+                    schedule.times_of_day.push(parse_time_of_day(time_of_day, position)?);
+                    time_positions.push(position);
+                },
+            }
+        }
+
+        let Some(start) = start else {
+            return Err(ParseError { position: s.len(), kind: ParseErrorKind::TooShort });
+        };
+        c.validate_schedule(&schedule).map_err(|_| {
+            // values are validated whilst parsing, remaining are start after end, and bounds and applicability of expansions
+            let position = until.filter(|_| schedule.end.as_ref().is_some_and(|end| *end < schedule.start))
+                .or(invalid_expansion(&day_positions, MAX_DAYS_OF_MONTH, &schedule.items, &[Frequency::Year, Frequency::Month]))
+                .or(invalid_expansion(&time_positions, MAX_TIMES_OF_DAY, &schedule.items, &[Frequency::Year, Frequency::Month, Frequency::Week, Frequency::Day]))
+                .unwrap_or(start);
+            ParseError { position, kind: ParseErrorKind::OutOfRange }
+        })?;
+        Ok(schedule)
+    }
+}
+
+/// Shorthand unit of a `Frequency`.
+fn unit(freq: &Frequency) -> &'static str {
+    match freq {
+        Frequency::Year   => "y",
+        Frequency::Month  => "mo",
+        Frequency::Week   => "w",
+        Frequency::Day    => "d",
+        Frequency::Hour   => "h",
+        Frequency::Minute => "m",
+        Frequency::Second => "s",
+        Frequency::Ms     => "ms",
+    }
+}
+
+/// Writes `DateTime` as eg. `2022-04-30T10:20`, with seconds and ms only if set, optionally omitting midnight time.
+fn write_dsl_datetime(f: &mut fmt::Formatter<'_>, dt: &DateTime, omit_midnight: bool) -> fmt::Result {
+    write!(f, "{:04}-{:02}-{:02}", dt.year, dt.month, dt.day)?;
+    if omit_midnight && dt.hour == 0 && dt.minute == 0 && dt.second == 0 && dt.ms == 0 {
+        return Ok(());
+    }
+    write!(f, "T{:02}:{:02}", dt.hour, dt.minute)?;
+    if dt.second != 0 || dt.ms != 0 {
+        write!(f, ":{:02}", dt.second)?;
+    }
+    if dt.ms != 0 {
+        write!(f, ".{:03}", dt.ms)?;
+    }
+    Ok(())
+}
+
+/// Splits by `separator`, with positions offset by `position`.
+fn split(s: &str, separator: char, position: usize) -> impl Iterator<Item = (usize, &str)> {
+    s.split(separator).scan(position, move |next, part| {
+        let position = *next;
+        *next += part.len() + separator.len_utf8();
+        Some((position, part))
+    })
+}
+
+/// Position of the first item of an expansion beyond `max` items, or of its first item if any of `items` isn't one of `freqs`.
+fn invalid_expansion(positions: &[usize], max: usize, items: &[(Frequency, u32)], freqs: &[Frequency]) -> Option<usize> {
+    let applicable = items.iter().all(|(freq, _)| freqs.contains(freq));
+    positions.get(max).or(positions.first().filter(|_| !applicable)).copied()
+}
+
+pub(crate) fn too_short_or_unexpected(s: &str, position: usize) -> ParseErrorKind {
+    if position < s.len() { ParseErrorKind::Unexpected } else { ParseErrorKind::TooShort }
+}
+
+/// Multiplier with shorthand unit, eg. `2w`.
+fn parse_item(item: &str, position: usize) -> Result<(Frequency, u32), ParseError> {
+    let unit_start = item.find(|c: char| !c.is_ascii_digit()).unwrap_or(item.len());
+    let multiplier = parse_number(&item[..unit_start], position, 1, u32::MAX)?;
+    let freq = match &item[unit_start..] {
+        "y"  => Frequency::Year,
+        "mo" => Frequency::Month,
+        "w"  => Frequency::Week,
+        "d"  => Frequency::Day,
+        "h"  => Frequency::Hour,
+        "m"  => Frequency::Minute,
+        "s"  => Frequency::Second,
+        "ms" => Frequency::Ms,
+        _ => return Err(ParseError { position: position + unit_start, kind: too_short_or_unexpected(item, unit_start) }),
+    };
+    Ok((freq, multiplier))
+}
+
+/// Time of day as `HH:MM`.
+pub(crate) fn parse_time_of_day(time_of_day: &str, position: usize) -> Result<TimeOfDay, ParseError> {
+    let (hour, minute) = time_of_day.split_once(':').ok_or(ParseError { position: position + time_of_day.len(), kind: too_short_or_unexpected(time_of_day, time_of_day.len()) })?;
+    Ok(TimeOfDay {
+        hour:   parse_number(hour, position, 0, 23)? as u8,
+        minute: parse_number(minute, position + hour.len() + 1, 0, 59)? as u8,
+    })
+}
+
+/// Decimal number within [`min`, `max`].
+pub(crate) fn parse_number(s: &str, position: usize, min: u32, max: u32) -> Result<u32, ParseError> {
+    if let Some(i) = s.find(|c: char| !c.is_ascii_digit()) {
+        return Err(ParseError { position: position + i, kind: ParseErrorKind::Unexpected });
+    }
+    if s.is_empty() {
+        return Err(ParseError { position, kind: ParseErrorKind::TooShort });
+    }
+    s.parse::<u32>().ok()
+        .filter(|x| (min..=max).contains(x))
+        .ok_or(ParseError { position, kind: ParseErrorKind::OutOfRange })
+}
@@ -0,0 +1,453 @@
+use core::fmt::Write;
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+    utils::is_leap_year,
+};
+
+impl Calendar {
+    /// Formats a `&DateTime` as per strftime-like `fmt` into `w`, without allocation. Supported specifiers:
+    /// - `%Y` year, eg. `2022`, `%C` century, eg. `20`, `%y` year of the century, eg. `22`
+    /// - `%m` month, eg. `04`, `%B` month name, eg. `April`, `%b`/`%h` abbreviated month name, eg. `Apr`
+    /// - `%d` day of month, eg. `05`, `%e` space padded day of month, eg. ` 5`, `%j` day of the year, eg. `095`
+    /// - `%A` weekday name, eg. `Tuesday`, `%a` abbreviated weekday name, eg. `Tue`, `%u` weekday with Monday = 1, `%w` weekday with Sunday = 0
+    /// - `%H` hour, eg. `13`, `%I` 12-hour clock hour, eg. `01`, `%p` `AM`/`PM`, `%M` minute, `%S` second, `%L` ms, eg. `045`
+    /// - `%s` seconds from epoch
+    /// - `%D` as per `%m/%d/%y`, `%F` as per `%Y-%m-%d`, `%T` as per `%H:%M:%S`, `%R` as per `%H:%M`
+    /// - `%n` new line, `%t` tab, `%%` literal `%`
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let dt = DateTime { year: 2022, month: 4, day: 5, hour: 13, minute: 20, second: 30, ms: 45 };
+    /// let mut s = String::new();
+    /// c.format(&dt, "%a %b %e %Y, %I:%M:%S.%L %p", &mut s).unwrap();
+    /// assert_eq!(s, "Tue Apr  5 2022, 01:20:30.045 PM");
+    /// assert_eq!(c.format(&dt, "%Y-%Q", &mut String::new()), Err(FormatError::InvalidSpecifier { fmt_position: 3 }));
+    /// ```
+    pub fn format(&self, dt: &DateTime, fmt: &str, w: &mut impl Write) -> Result<(), FormatError> {
+        self.validate_datetime(dt).map_err(|_| FormatError::InvalidDateTime)?;
+        let mut rest = fmt;
+        while let Some(i) = rest.find('%') {
+            w.write_str(&rest[..i])?;
+            let fmt_position = fmt.len() - rest.len() + i;
+            let specifier = rest[i + 1..].chars().next().ok_or(FormatError::InvalidSpecifier { fmt_position })?;
+            self.format_specifier(dt, specifier, w)?.ok_or(FormatError::InvalidSpecifier { fmt_position })?;
+            rest = &rest[i + 1 + specifier.len_utf8()..];
+        }
+        w.write_str(rest)?;
+        Ok(())
+    }
+
+    /// Writes a single specifier, `None` if not supported.
+    fn format_specifier(&self, dt: &DateTime, specifier: char, w: &mut impl Write) -> Result<Option<()>, FormatError> {
+        let month_name = MONTH_NAMES[dt.month as usize - 1];
+        let weekday = self.weekday(dt);
+        match specifier {
+            'Y' => write!(w, "{:04}", dt.year)?,
+            'C' => write!(w, "{:02}", dt.year / 100)?,
+            'y' => write!(w, "{:02}", dt.year % 100)?,
+            'm' => write!(w, "{:02}", dt.month)?,
+            'B' => w.write_str(month_name)?,
+            'b' | 'h' => w.write_str(&month_name[..3])?,
+            'd' => write!(w, "{:02}", dt.day)?,
+            'e' => write!(w, "{:2}", dt.day)?,
+            'j' => write!(w, "{:03}", self.day_of_year(dt))?,
+            'A' => w.write_str(WEEKDAY_NAMES[weekday as usize])?,
+            'a' => w.write_str(&WEEKDAY_NAMES[weekday as usize][..3])?,
+            'u' => write!(w, "{}", if weekday == 0 { 7 } else { weekday })?,
+            'w' => write!(w, "{}", weekday)?,
+            'H' => write!(w, "{:02}", dt.hour)?,
+            'I' => write!(w, "{:02}", (dt.hour + 11) % 12 + 1)?,
+            'p' => w.write_str(if dt.hour < 12 { "AM" } else { "PM" })?,
+            'M' => write!(w, "{:02}", dt.minute)?,
+            'S' => write!(w, "{:02}", dt.second)?,
+            'L' => write!(w, "{:03}", dt.ms)?,
+            's' => write!(w, "{}", self.to_unixtime(dt) / MS_IN_SEC)?,
+            'D' => write!(w, "{:02}/{:02}/{:02}", dt.month, dt.day, dt.year % 100)?,
+            'F' => write!(w, "{:04}-{:02}-{:02}", dt.year, dt.month, dt.day)?,
+            'T' => write!(w, "{:02}:{:02}:{:02}", dt.hour, dt.minute, dt.second)?,
+            'R' => write!(w, "{:02}:{:02}", dt.hour, dt.minute)?,
+            'n' => w.write_char('\n')?,
+            't' => w.write_char('\t')?,
+            '%' => w.write_char('%')?,
+            _ => return Ok(None),
+        }
+        Ok(Some(()))
+    }
+
+    /// Weekday of a valid `&DateTime`, with 0 = Sunday.
+    fn weekday(&self, dt: &DateTime) -> u8 {
+        ((self.to_unixtime(dt) / MS_IN_DAY + EPOCH_WEEKDAY) % 7) as u8
+    }
+
+    /// 1 based day of the year of a valid `&DateTime`.
+    fn day_of_year(&self, dt: &DateTime) -> u16 {
+        let start_of_year = DateTime { year: dt.year, month: 1, day: 1, hour: 0, minute: 0, second: 0, ms: 0 };
+        ((self.to_unixtime(dt) - self.to_unixtime(&start_of_year)) / MS_IN_DAY) as u16 + 1
+    }
+}
+
+impl Calendar {
+    /// Parses a `DateTime` as per strptime-like `fmt`, with the same specifiers as `format()`, matching names case insensitively.
+    /// Fields missing from the `fmt` default to zero, ie. 01/01/1970 00:00:00:000, eg. for date only formats.
+    /// Errors point at the byte of the `input`, where parsing reached an unsupported specifier for `ParseErrorKind::InvalidSpecifier`,
+    /// and `DateTime`s failing `validate_datetime()` are reported as `ParseErrorKind::OutOfRange` of the year or day.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// assert_eq!(c.parse_with_format("05/04/2022 13:20", "%d/%m/%Y %H:%M"), Ok(DateTime { year: 2022, month: 4, day: 5, hour: 13, minute: 20, second: 0, ms: 0 }));
+    /// assert_eq!(c.parse_with_format("Tue Apr  5 2022", "%a %b %e %Y"), Ok(DateTime { year: 2022, month: 4, day: 5, hour: 0, minute: 0, second: 0, ms: 0 }));
+    /// assert_eq!(c.parse_with_format("2022-02-30", "%F"), Err(ParseError { position: 8, kind: ParseErrorKind::OutOfRange }));
+    /// ```
+    pub fn parse_with_format(&self, input: &str, fmt: &str) -> Result<DateTime, ParseError> {
+        let mut parser = FormatParser { input: input.as_bytes(), position: 0, fields: Fields::default() };
+        parser.format(fmt)?;
+        parser.end()?;
+        self.resolve_fields(&parser.fields)
+    }
+
+    /// Combines parsed fields into a valid `DateTime`.
+    fn resolve_fields(&self, fields: &Fields) -> Result<DateTime, ParseError> {
+        let out_of_range = |position| ParseError { position, kind: ParseErrorKind::OutOfRange };
+        let value = |field: Option<(u16, usize)>| field.map(|(value, _)| value);
+
+        let mut dt = if let Some((ts, position)) = fields.timestamp {
+            let ts_ms = ts.checked_mul(MS_IN_SEC).filter(|&ts_ms| ts_ms < YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1]).ok_or(out_of_range(position))?;
+            self.from_unixtime(ts_ms)
+        } else {
+            let year = match (fields.year, fields.century, fields.year_of_century) {
+                (Some((year, _)), _, _) => year,
+                (None, None, None) => EPOCH_YEAR as u16,
+                // as per POSIX, years of century without century are within [1969, 2068]
+                (None, century, year_of_century) => {
+                    let year_of_century = value(year_of_century).unwrap_or(0);
+                    value(century).unwrap_or(if year_of_century < 69 { 20 } else { 19 }) * 100 + year_of_century
+                }
+            };
+            let (month, day) = match (fields.month, fields.day, fields.day_of_year) {
+                (None, None, Some((day_of_year, position))) => {
+                    let days_in_months = if is_leap_year(year) { MONTH_FOR_LEAP_YEAR } else { MONTH_FOR_NON_LEAP_YEAR };
+                    let mut day = day_of_year;
+                    let mut month = 0;
+                    while month < 12 && day > days_in_months[month] as u16 {
+                        day -= days_in_months[month] as u16;
+                        month += 1;
+                    }
+                    if month == 12 {
+                        return Err(out_of_range(position));
+                    }
+                    (month as u8 + 1, day as u8)
+                }
+                (month, day, _) => (value(month).unwrap_or(1) as u8, value(day).unwrap_or(1) as u8),
+            };
+            let hour = match (fields.hour, fields.hour12, fields.pm) {
+                (Some((hour, _)), _, _) => hour as u8,
+                (None, hour12, pm) => (value(hour12).unwrap_or(12) % 12 + if value(pm) == Some(1) { 12 } else { 0 }) as u8 % 24,
+            };
+            DateTime {
+                year, month, day, hour,
+                minute: value(fields.minute).unwrap_or(0) as u8,
+                second: value(fields.second).unwrap_or(0) as u8,
+                ms:     0,
+            }
+        };
+        if let Some(ms) = value(fields.ms) {
+            dt.ms = ms;
+        }
+
+        match self.validate_datetime(&dt) {
+            Ok(()) => (),
+            Err(ValidationError::OutOfScope) => return Err(out_of_range(fields.year.or(fields.century).or(fields.year_of_century).map_or(0, |(_, position)| position))),
+            Err(_) => return Err(out_of_range(fields.day.or(fields.day_of_year).map_or(0, |(_, position)| position))),
+        }
+        if let Some((weekday, position)) = fields.weekday {
+            if weekday as u8 != self.weekday(&dt) {
+                return Err(out_of_range(position));
+            }
+        }
+        Ok(dt)
+    }
+}
+
+/// Format of HTTP IMF-fixdate, as per RFC 7231, eg. `Sun, 06 Nov 1994 08:49:37 GMT`.
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %T GMT";
+/// Obsolete zone names of RFC 2822, with their offsets.
+const RFC2822_ZONE_NAMES: &[&str] = &["UT", "GMT", "EST", "EDT", "CST", "CDT", "MST", "MDT", "PST", "PDT"];
+const RFC2822_ZONE_OFFSETS: &[i16] = &[0, 0, -300, -240, -360, -300, -420, -360, -480, -420];
+
+impl Calendar {
+    /// Formats a `&OffsetDateTime` as per RFC 2822, eg. `Tue, 05 Apr 2022 13:07:08 +0530`, dropping ms.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let odt = OffsetDateTime { datetime: DateTime { year: 2022, month: 4, day: 5, hour: 13, minute: 7, second: 8, ms: 9 }, offset_minutes: 330 };
+    /// let mut s = String::new();
+    /// c.format_rfc2822(&odt, &mut s).unwrap();
+    /// assert_eq!(s, "Tue, 05 Apr 2022 13:07:08 +0530");
+    /// ```
+    pub fn format_rfc2822(&self, odt: &OffsetDateTime, w: &mut impl Write) -> Result<(), FormatError> {
+        self.validate_offset_datetime(odt).map_err(|_| FormatError::InvalidDateTime)?;
+        self.format(&odt.datetime, "%a, %d %b %Y %T ", w)?;
+        let sign = if odt.offset_minutes < 0 { '-' } else { '+' };
+        let offset = odt.offset_minutes.unsigned_abs();
+        write!(w, "{}{:02}{:02}", sign, offset / 60, offset % 60)?;
+        Ok(())
+    }
+
+    /// Parses an `OffsetDateTime` as per RFC 2822, eg. `Tue, 5 Apr 2022 13:07:08 +0530`, with optional weekday and seconds,
+    /// and obsolete 2 digit years and zone names, eg. `5 Apr 22 13:07 GMT`.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let odt = OffsetDateTime { datetime: DateTime { year: 2022, month: 4, day: 5, hour: 13, minute: 7, second: 8, ms: 0 }, offset_minutes: 330 };
+    /// assert_eq!(c.parse_rfc2822("Tue, 05 Apr 2022 13:07:08 +0530"), Ok(odt));
+    /// assert_eq!(c.parse_rfc2822("Mon, 05 Apr 2022 13:07:08 +0530"), Err(ParseError { position: 0, kind: ParseErrorKind::OutOfRange }));
+    /// ```
+    pub fn parse_rfc2822(&self, input: &str) -> Result<OffsetDateTime, ParseError> {
+        let mut parser = FormatParser { input: input.as_bytes(), position: 0, fields: Fields::default() };
+        parser.whitespace();
+        if parser.input.get(parser.position).is_some_and(|c| c.is_ascii_alphabetic()) {
+            parser.format("%a,")?;
+            parser.whitespace();
+        }
+        parser.format("%e")?;
+        parser.required_whitespace()?;
+        parser.format("%b")?;
+        parser.required_whitespace()?;
+        // obsolete years of 2 digits are within [1950, 2049], of 3 digits offset by 1900
+        let year_position = parser.position;
+        let year = parser.number(4, 0, 9999)?;
+        let year = match parser.position - year_position {
+            2 if year < 50 => year + 2000,
+            2 | 3 => year + 1900,
+            _ => year,
+        };
+        parser.fields.year = Some((year, year_position));
+        parser.required_whitespace()?;
+        parser.format("%R")?;
+        if parser.input.get(parser.position) == Some(&b':') {
+            parser.format(":%S")?;
+        }
+        parser.required_whitespace()?;
+        let zone_position = parser.position;
+        let offset_minutes = match parser.input.get(parser.position) {
+            Some(&sign @ (b'+' | b'-')) => {
+                parser.position += 1;
+                let hours = parser.number(2, 0, 99)? as i16;
+                let minutes = parser.number(2, 0, 59)? as i16;
+                if parser.position - zone_position != 5 {
+                    return Err(parser.unexpected());
+                }
+                let offset_minutes = hours * 60 + minutes;
+                if offset_minutes > MAX_OFFSET_MINUTES {
+                    return Err(ParseError { position: zone_position, kind: ParseErrorKind::OutOfRange });
+                }
+                if sign == b'-' { -offset_minutes } else { offset_minutes }
+            }
+            _ => RFC2822_ZONE_OFFSETS[parser.name(RFC2822_ZONE_NAMES)? as usize],
+        };
+        parser.whitespace();
+        parser.end()?;
+
+        let odt = OffsetDateTime { datetime: self.resolve_fields(&parser.fields)?, offset_minutes };
+        match self.validate_offset_datetime(&odt) {
+            Ok(()) => Ok(odt),
+            Err(_) => Err(ParseError { position: zone_position, kind: ParseErrorKind::OutOfRange }),
+        }
+    }
+
+    /// Formats ms from epoch as HTTP IMF-fixdate, as per RFC 7231, eg. `Sun, 06 Nov 1994 08:49:37 GMT`, dropping ms.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let mut s = String::new();
+    /// c.format_http_date(784111777000, &mut s).unwrap();
+    /// assert_eq!(s, "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// ```
+    pub fn format_http_date(&self, ts: u64, w: &mut impl Write) -> Result<(), FormatError> {
+        if ts >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return Err(FormatError::InvalidDateTime);
+        }
+        self.format(&self.from_unixtime(ts), HTTP_DATE_FORMAT, w)
+    }
+
+    /// Parses HTTP IMF-fixdate, as per RFC 7231, eg. `Sun, 06 Nov 1994 08:49:37 GMT`, to ms from epoch.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// assert_eq!(c.parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Ok(784111777000));
+    /// ```
+    pub fn parse_http_date(&self, input: &str) -> Result<u64, ParseError> {
+        self.parse_with_format(input, HTTP_DATE_FORMAT).map(|dt| self.to_unixtime(&dt))
+    }
+}
+
+/// Fields parsed by `FormatParser`, with positions of the input for error reporting.
+#[derive(Default)]
+struct Fields {
+    year:            Option<(u16, usize)>,
+    century:         Option<(u16, usize)>,
+    year_of_century: Option<(u16, usize)>,
+    month:           Option<(u16, usize)>,
+    day:             Option<(u16, usize)>,
+    day_of_year:     Option<(u16, usize)>,
+    weekday:         Option<(u16, usize)>,  // 0 = Sunday
+    hour:            Option<(u16, usize)>,
+    hour12:          Option<(u16, usize)>,
+    pm:              Option<(u16, usize)>,  // 0 = AM, 1 = PM
+    minute:          Option<(u16, usize)>,
+    second:          Option<(u16, usize)>,
+    ms:              Option<(u16, usize)>,
+    timestamp:       Option<(u64, usize)>,
+}
+
+struct FormatParser<'a> {
+    input:    &'a [u8],
+    position: usize,
+    fields:   Fields,
+}
+
+impl FormatParser<'_> {
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError { position: self.position, kind }
+    }
+
+    /// `TooShort` at the end of the input, `Unexpected` otherwise.
+    fn unexpected(&self) -> ParseError {
+        self.error(if self.position < self.input.len() { ParseErrorKind::Unexpected } else { ParseErrorKind::TooShort })
+    }
+
+    fn end(&self) -> Result<(), ParseError> {
+        match self.position < self.input.len() {
+            true => Err(self.error(ParseErrorKind::Unexpected)),
+            false => Ok(()),
+        }
+    }
+
+    /// Matches the input against the format string, literal characters exactly.
+    fn format(&mut self, fmt: &str) -> Result<(), ParseError> {
+        let mut rest = fmt;
+        while let Some(i) = rest.find('%') {
+            self.literal(&rest[..i])?;
+            let specifier = rest[i + 1..].chars().next().ok_or(self.error(ParseErrorKind::InvalidSpecifier))?;
+            if !self.specifier(specifier)? {
+                return Err(self.error(ParseErrorKind::InvalidSpecifier));
+            }
+            rest = &rest[i + 1 + specifier.len_utf8()..];
+        }
+        self.literal(rest)
+    }
+
+    /// Parses a single specifier, `false` if not supported.
+    fn specifier(&mut self, specifier: char) -> Result<bool, ParseError> {
+        let position = self.position;
+        match specifier {
+            'Y' => self.fields.year = Some((self.number(4, 0, 9999)?, position)),
+            'C' => self.fields.century = Some((self.number(2, 0, 99)?, position)),
+            'y' => self.fields.year_of_century = Some((self.number(2, 0, 99)?, position)),
+            'm' => self.fields.month = Some((self.number(2, 1, 12)?, position)),
+            'B' | 'b' | 'h' => self.fields.month = Some((self.name(MONTH_NAMES)? + 1, position)),
+            'd' => self.fields.day = Some((self.number(2, 1, 31)?, position)),
+            'e' => {
+                if self.input.get(self.position) == Some(&b' ') {
+                    self.position += 1;
+                }
+                self.fields.day = Some((self.number(2, 1, 31)?, position));
+            }
+            'j' => self.fields.day_of_year = Some((self.number(3, 1, 366)?, position)),
+            'A' | 'a' => self.fields.weekday = Some((self.name(WEEKDAY_NAMES)?, position)),
+            'u' => self.fields.weekday = Some((self.number(1, 1, 7)? % 7, position)),
+            'w' => self.fields.weekday = Some((self.number(1, 0, 6)?, position)),
+            'H' => self.fields.hour = Some((self.number(2, 0, 23)?, position)),
+            'I' => self.fields.hour12 = Some((self.number(2, 1, 12)?, position)),
+            'p' => self.fields.pm = Some((self.name(&["AM", "PM"])?, position)),
+            'M' => self.fields.minute = Some((self.number(2, 0, 59)?, position)),
+            'S' => self.fields.second = Some((self.number(2, 0, 59)?, position)),
+            'L' => {
+                let ms = self.number(3, 0, 999)?;
+                if self.position - position < 3 {
+                    return Err(self.unexpected());
+                }
+                self.fields.ms = Some((ms, position));
+            }
+            's' => self.fields.timestamp = Some((self.long_number(12)?, position)),
+            'D' => self.format("%m/%d/%y")?,
+            'F' => self.format("%Y-%m-%d")?,
+            'T' => self.format("%H:%M:%S")?,
+            'R' => self.format("%H:%M")?,
+            'n' | 't' => self.whitespace(),
+            '%' => self.literal("%")?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Skips any whitespace.
+    fn whitespace(&mut self) {
+        while self.input.get(self.position).is_some_and(|c| c.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    /// Skips whitespace, requiring at least one character of it.
+    fn required_whitespace(&mut self) -> Result<(), ParseError> {
+        let start = self.position;
+        self.whitespace();
+        if self.position == start {
+            return Err(self.unexpected());
+        }
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        for &c in literal.as_bytes() {
+            if self.input.get(self.position) != Some(&c) {
+                return Err(self.unexpected());
+            }
+            self.position += 1;
+        }
+        Ok(())
+    }
+
+    /// Decimal number of up to `max_digits`, within [`min`, `max`].
+    fn number(&mut self, max_digits: usize, min: u16, max: u16) -> Result<u16, ParseError> {
+        let start = self.position;
+        let value = self.long_number(max_digits)?;
+        if value < min as u64 || value > max as u64 {
+            return Err(ParseError { position: start, kind: ParseErrorKind::OutOfRange });
+        }
+        Ok(value as u16)
+    }
+
+    /// Decimal number of up to `max_digits`, with at least one digit.
+    fn long_number(&mut self, max_digits: usize) -> Result<u64, ParseError> {
+        let start = self.position;
+        let mut value = 0_u64;
+        while let Some(c @ b'0'..=b'9') = self.input.get(self.position).copied() {
+            if self.position - start == max_digits {
+                break;
+            }
+            value = value * 10 + (c - b'0') as u64;
+            self.position += 1;
+        }
+        if self.position == start {
+            return Err(self.unexpected());
+        }
+        Ok(value)
+    }
+
+    /// Index of a case insensitive match of either full, or abbreviated to 3 characters, name.
+    fn name(&mut self, names: &[&str]) -> Result<u16, ParseError> {
+        let rest = &self.input[self.position..];
+        for (i, name) in names.iter().enumerate() {
+            let matched = [name.len(), 3.min(name.len())].into_iter()
+                .find(|&len| rest.len() >= len && rest[..len].eq_ignore_ascii_case(&name.as_bytes()[..len]));
+            if let Some(len) = matched {
+                self.position += len;
+                return Ok(i as u16);
+            }
+        }
+        Err(self.unexpected())
+    }
+}
@@ -0,0 +1,394 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+use core::{fmt, str::FromStr};
+use super::{
+    calendar::Calendar,
+    constants::*,
+    dsl::{parse_number, too_short_or_unexpected},
+    types::*,
+    utils::*,
+};
+
+/// Formats as ISO 8601, eg. `P1Y2M3DT4H`, omitting zero components, or `PT0S` if all are zero. `ms` are written as fraction of seconds, eg. `PT1.5S`.
+impl fmt::Display for IsoDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == IsoDuration::default() {
+            return write!(f, "PT0S");
+        }
+        write!(f, "P")?;
+        for (value, designator) in [(self.years, 'Y'), (self.months, 'M'), (self.weeks, 'W'), (self.days, 'D')] {
+            if value != 0 {
+                write!(f, "{}{}", value, designator)?;
+            }
+        }
+        let seconds_ms = self.seconds as u64 * MS_IN_SEC + self.ms as u64;
+        if self.hours != 0 || self.minutes != 0 || seconds_ms != 0 {
+            write!(f, "T")?;
+            for (value, designator) in [(self.hours, 'H'), (self.minutes, 'M')] {
+                if value != 0 {
+                    write!(f, "{}{}", value, designator)?;
+                }
+            }
+            if seconds_ms != 0 {
+                write!(f, "{}", seconds_ms / MS_IN_SEC)?;
+                // fraction without trailing zeros
+                match seconds_ms % MS_IN_SEC {
+                    0 => (),
+                    ms if ms % 100 == 0 => write!(f, ".{}", ms / 100)?,
+                    ms if ms % 10 == 0 => write!(f, ".{:02}", ms / 10)?,
+                    ms => write!(f, ".{:03}", ms)?,
+                }
+                write!(f, "S")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses ISO 8601 duration, eg. `P1Y2M3DT4H5M6.789S` or `P2W`, with components in order of `Y`, `M`, `W`, `D`, and time components `H`, `M`, `S` following `T`.
+/// Only seconds may have a fraction, of up to 3 digits.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let duration: IsoDuration = "P1Y2M3DT4H".parse().unwrap();
+/// assert_eq!(duration, IsoDuration { years: 1, months: 2, days: 3, hours: 4, ..Default::default() });
+/// assert_eq!("PT0.5S".parse::<IsoDuration>().unwrap().to_string(), "PT0.5S");
+/// assert_eq!("P1H".parse::<IsoDuration>(), Err(ParseError { position: 2, kind: ParseErrorKind::Unexpected }));
+/// ```
+impl FromStr for IsoDuration {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_duration(s, 0)
+    }
+}
+
+/// Formats as ISO 8601, eg. `R5/2022-01-01T00:00:00Z/P1M`, or `R/2022-01-01T09:00:00Z/PT15M` if repeating indefinitely.
+impl fmt::Display for RepeatingInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "R")?;
+        if let Some(repetitions) = self.repetitions {
+            write!(f, "{}", repetitions)?;
+        }
+        let start = &self.start;
+        write!(f, "/{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", start.year, start.month, start.day, start.hour, start.minute, start.second)?;
+        if start.ms != 0 {
+            write!(f, ".{:03}", start.ms)?;
+        }
+        write!(f, "Z/{}", self.duration)
+    }
+}
+
+/// Parses ISO 8601 repeating interval of start and duration, eg. `R5/2022-01-01T00:00:00Z/P1M` or `R/2022-01-01T09:00+01:00/PT15M`:
+/// - start as per `%Y-%m-%d`, with optional time as per `T%H:%M`, `T%H:%M:%S` or `T%H:%M:%S.%L`, and UTC offset of `Z`, `±HH:MM`, `±HHMM` or `±HH`, converted to UTC.
+///   Start without offset is considered UTC
+/// - duration as per `IsoDuration`
+///
+/// Forms with end, eg. `R5/2022-01-01T00:00:00Z/2022-02-01T00:00:00Z`, are `ParseErrorKind::Unsupported`.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let ri: RepeatingInterval = "R/2022-01-01T09:00+01:00/PT15M".parse().unwrap();
+/// assert_eq!(ri.repetitions, None);
+/// assert_eq!(ri.start, DateTime { year: 2022, month: 1, day: 1, hour: 8, minute: 0, second: 0, ms: 0 });
+/// assert_eq!(ri.to_string(), "R/2022-01-01T08:00:00Z/PT15M");
+/// ```
+impl FromStr for RepeatingInterval {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = Calendar::create();
+        let mut parts = s.splitn(3, '/');
+        let repetitions = parts.next().unwrap_or_default();
+        let (start, duration) = match (parts.next(), parts.next()) {
+            (Some(start), Some(duration)) => (start, duration),
+            _ => return Err(ParseError { position: s.len(), kind: ParseErrorKind::TooShort }),
+        };
+        let start_position = repetitions.len() + 1;
+        let duration_position = start_position + start.len() + 1;
+
+        let repetitions = match repetitions.strip_prefix('R') {
+            Some("") => None,
+            Some(repetitions) => Some(parse_number(repetitions, 1, 0, u32::MAX)?),
+            None => return Err(ParseError { position: 0, kind: too_short_or_unexpected(s, 0) }),
+        };
+        // duration/end and start/end forms
+        if start.starts_with('P') {
+            return Err(ParseError { position: start_position, kind: ParseErrorKind::Unsupported });
+        }
+        if !duration.is_empty() && !duration.starts_with('P') {
+            return Err(ParseError { position: duration_position, kind: ParseErrorKind::Unsupported });
+        }
+        Ok(RepeatingInterval {
+            repetitions,
+            start:    parse_iso_utc_datetime(&c, start, start_position)?,
+            duration: parse_duration(duration, duration_position)?,
+        })
+    }
+}
+
+impl Calendar {
+    /// Adds `IsoDuration` to a `DateTime`, returning `None` if either is out of scope.
+    /// Years and months are added first, with day clamped to the last day of the resulting month, followed by the fixed components.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let dt = DateTime { year: 2022, month: 1, day: 31, hour: 10, minute: 0, second: 0, ms: 0 };
+    /// let duration = IsoDuration { months: 1, days: 1, hours: 14, ..Default::default() };
+    /// assert_eq!(c.add_duration(&dt, &duration), Some(DateTime { year: 2022, month: 3, day: 2, hour: 0, minute: 0, second: 0, ms: 0 }));
+    /// ```
+    pub fn add_duration(&self, dt: &DateTime, duration: &IsoDuration) -> Option<DateTime> {
+        self.add_duration_times(dt, duration, 1)
+    }
+
+    /// Adds `IsoDuration` multiplied by `times`, with a single clamping of the day.
+    fn add_duration_times(&self, dt: &DateTime, duration: &IsoDuration, times: u32) -> Option<DateTime> {
+        self.validate_datetime(dt).ok()?;
+        let months = (duration.years as u64 * 12 + duration.months as u64).checked_mul(times as u64)?;
+        let month_index = dt.year as u64 * 12 + dt.month as u64 - 1 + months;
+        let year = u16::try_from(month_index / 12).ok()?;
+        let month = (month_index % 12) as u8 + 1;
+        if year as usize >= EPOCH_YEAR + YEAR_MS_OFFSETS.len() - 1 {
+            return None;
+        }
+        let date = DateTime { year, month, day: dt.day.min(days_in_month(year, month)), ..dt.clone() };
+        let ts = self.to_unixtime(&date).checked_add(fixed_ms(duration).checked_mul(times as u64)?)?;
+        if ts >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return None;
+        }
+        Some(self.from_unixtime(ts))
+    }
+
+    /// Converts `RepeatingInterval` to `Schedule` of a single item, with `end` at the last repetition, as per `Calendar::add_duration()`.
+    /// Durations of years and months map to `Frequency::Year`/`Frequency::Month`, and of fixed components to the largest `Frequency` dividing them, eg. `P1DT12H` to 36 hours.
+    ///
+    /// Durations mixing both, eg. `P1M1D`, zero durations and zero repetitions are not representable, and classified as `ValidationError::Invalid`.
+    /// So are durations of years and months starting after the 28th, as repetitions clamp the day to the end of shorter months, whereas schedules roll over into the following month.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let schedule = c.schedule_from_repeating_interval(&"R5/2022-01-28T00:00:00Z/P1M".parse().unwrap()).unwrap();
+    /// assert_eq!(schedule.items, vec![(Frequency::Month, 1)]);
+    /// assert_eq!(schedule.end, Some(DateTime { year: 2022, month: 5, day: 28, hour: 0, minute: 0, second: 0, ms: 0 }));
+    /// assert_eq!(c.schedule_from_repeating_interval(&"R5/2022-01-31T00:00:00Z/P1M".parse().unwrap()), Err(ValidationError::Invalid));
+    /// ```
+    pub fn schedule_from_repeating_interval(&self, ri: &RepeatingInterval) -> Result<Schedule, ValidationError> {
+        self.validate_datetime(&ri.start)?;
+        let item = frequency_of_duration(&ri.duration).ok_or(ValidationError::Invalid)?;
+        if !is_day_of_every_month(item.0, &ri.start) {
+            return Err(ValidationError::Invalid);
+        }
+        let end = match ri.repetitions {
+            None => None,
+            Some(0) => return Err(ValidationError::Invalid),
+            Some(repetitions) => Some(self.add_duration_times(&ri.start, &ri.duration, repetitions - 1).ok_or(ValidationError::OutOfScope)?),
+        };
+        let schedule = Schedule { start: ri.start.clone(), items: vec![item], end, ..Default::default() };
+        self.validate_schedule(&schedule)?;
+        Ok(schedule)
+    }
+
+    /// Converts valid `Schedule` of a single item without expansions to `RepeatingInterval`, with `repetitions` counting the repetitions until `end`.
+    /// Returns `None` if not representable, including `Frequency::Year`/`Frequency::Month` schedules starting after the 28th.
+    pub fn repeating_interval_from_schedule(&self, schedule: &Schedule) -> Option<RepeatingInterval> {
+        self.validate_schedule(schedule).ok()?;
+        let [(freq, multiplier)] = schedule.items.as_slice() else {
+            return None;
+        };
+        if !schedule.times_of_day.is_empty() || !schedule.days_of_month.is_empty() || !is_day_of_every_month(*freq, &schedule.start) {
+            return None;
+        }
+        let duration = duration_of_frequency(*freq, *multiplier);
+        let repetitions = match &schedule.end {
+            None => None,
+            Some(end) => {
+                let start = &schedule.start;
+                let periods = match freq {
+                    Frequency::Year | Frequency::Month => {
+                        let months = (end.year as u64 * 12 + end.month as u64) - (start.year as u64 * 12 + start.month as u64);
+                        let periods = u32::try_from(months / (duration.years as u64 * 12 + duration.months as u64)).ok()?;
+                        // last period might end after `end`, due to day and time
+                        if self.add_duration_times(start, &duration, periods).is_none_or(|last| last > *end) { periods - 1 } else { periods }
+                    }
+                    _ => u32::try_from((self.to_unixtime(end) - self.to_unixtime(start)) / fixed_ms(&duration)).ok()?,
+                };
+                Some(periods.checked_add(1)?)
+            }
+        };
+        Some(RepeatingInterval { repetitions, start: schedule.start.clone(), duration })
+    }
+}
+
+/// Whether repetitions of `Frequency::Year`/`Frequency::Month` fall on the day of `start`, agreeing between schedules and repeating intervals.
+fn is_day_of_every_month(freq: Frequency, start: &DateTime) -> bool {
+    !matches!(freq, Frequency::Year | Frequency::Month) || start.day <= 28
+}
+
+/// Fixed components of `IsoDuration` in ms.
+fn fixed_ms(duration: &IsoDuration) -> u64 {
+    duration.weeks as u64 * 7 * MS_IN_DAY
+        + duration.days as u64 * MS_IN_DAY
+        + duration.hours as u64 * MS_IN_HOUR
+        + duration.minutes as u64 * MS_IN_MIN
+        + duration.seconds as u64 * MS_IN_SEC
+        + duration.ms as u64
+}
+
+/// `Frequency` with multiplier equivalent to `IsoDuration`, if not mixing years/months with fixed components.
+fn frequency_of_duration(duration: &IsoDuration) -> Option<(Frequency, u32)> {
+    let months = duration.years as u64 * 12 + duration.months as u64;
+    let fixed_ms = fixed_ms(duration);
+    match (months, fixed_ms) {
+        (0, 0) => None,
+        (0, _) => {
+            let freq = [Frequency::Week, Frequency::Day, Frequency::Hour, Frequency::Minute, Frequency::Second, Frequency::Ms].into_iter()
+                .find(|freq| fixed_ms % freq.to_ms() as u64 == 0)?;
+            Some((freq, u32::try_from(fixed_ms / freq.to_ms() as u64).ok()?))
+        }
+        (_, 0) if duration.months == 0 => Some((Frequency::Year, duration.years)),
+        (_, 0) => Some((Frequency::Month, u32::try_from(months).ok()?)),
+        _ => None,
+    }
+}
+
+fn duration_of_frequency(freq: Frequency, multiplier: u32) -> IsoDuration {
+    match freq {
+        Frequency::Year   => IsoDuration { years: multiplier, ..Default::default() },
+        Frequency::Month  => IsoDuration { months: multiplier, ..Default::default() },
+        Frequency::Week   => IsoDuration { weeks: multiplier, ..Default::default() },
+        Frequency::Day    => IsoDuration { days: multiplier, ..Default::default() },
+        Frequency::Hour   => IsoDuration { hours: multiplier, ..Default::default() },
+        Frequency::Minute => IsoDuration { minutes: multiplier, ..Default::default() },
+        Frequency::Second => IsoDuration { seconds: multiplier, ..Default::default() },
+        Frequency::Ms     => IsoDuration { ms: multiplier, ..Default::default() },
+    }
+}
+
+/// Parses `IsoDuration`, with positions offset by `position`.
+fn parse_duration(s: &str, position: usize) -> Result<IsoDuration, ParseError> {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&b'P') {
+        return Err(ParseError { position, kind: too_short_or_unexpected(s, 0) });
+    }
+    let mut duration = IsoDuration::default();
+    let mut i = 1;
+    let mut in_time = false;
+    // index of the next allowed designator, and whether any components were present in the current part
+    let (mut next_designator, mut has_components) = (0, false);
+    while i < bytes.len() {
+        if bytes[i] == b'T' && !in_time {
+            (in_time, next_designator, has_components) = (true, 0, false);
+            i += 1;
+            continue;
+        }
+
+        let value_start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == value_start {
+            return Err(ParseError { position: position + i, kind: ParseErrorKind::Unexpected });
+        }
+        let value = parse_number(&s[value_start..i], position + value_start, 0, u32::MAX)?;
+        let mut fraction = None;
+        if i < bytes.len() && (bytes[i] == b'.' || bytes[i] == b',') {
+            let fraction_start = i + 1;
+            i = fraction_start;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            let digits = i - fraction_start;
+            if digits == 0 {
+                return Err(ParseError { position: position + i, kind: too_short_or_unexpected(s, i) });
+            }
+            if digits > 3 {
+                return Err(ParseError { position: position + fraction_start + 3, kind: ParseErrorKind::Unsupported });
+            }
+            // scale to ms, eg. `.5` to 500
+            let ms = parse_number(&s[fraction_start..i], position + fraction_start, 0, 999)? * [0, 100, 10, 1][digits];
+            fraction = Some((fraction_start - 1, ms));
+        }
+
+        let designators: &[u8] = if in_time { b"HMS" } else { b"YMWD" };
+        let designator = match bytes.get(i) {
+            Some(designator) => designators.iter().position(|d| d == designator).filter(|&d| d >= next_designator),
+            None => return Err(ParseError { position: position + i, kind: ParseErrorKind::TooShort }),
+        }.ok_or(ParseError { position: position + i, kind: ParseErrorKind::Unexpected })?;
+        let field = match (in_time, designator) {
+            (false, 0) => &mut duration.years,
+            (false, 1) => &mut duration.months,
+            (false, 2) => &mut duration.weeks,
+            (false, _) => &mut duration.days,
+            (true, 0)  => &mut duration.hours,
+            (true, 1)  => &mut duration.minutes,
+            (true, _)  => &mut duration.seconds,
+        };
+        *field = value;
+        match fraction {
+            Some((_, ms)) if in_time && designator == 2 => duration.ms = ms,
+            Some((fraction_position, _)) => return Err(ParseError { position: position + fraction_position, kind: ParseErrorKind::Unsupported }),
+            None => (),
+        }
+        (next_designator, has_components) = (designator + 1, true);
+        i += 1;
+    }
+    // `P` and `T` require at least one component
+    if !has_components {
+        return Err(ParseError { position: position + s.len(), kind: ParseErrorKind::TooShort });
+    }
+    Ok(duration)
+}
+
+/// Parses `DateTime` as per `%Y-%m-%d`, with optional time as per `T%H:%M`, `T%H:%M:%S` or `T%H:%M:%S.%L`, with positions offset by `position`.
+pub(crate) fn parse_iso_datetime(c: &Calendar, value: &str, position: usize) -> Result<DateTime, ParseError> {
+    let fmt = match value.split_once('T').map(|(_, time)| (time.matches(':').count(), time.contains('.'))) {
+        None => "%F",
+        Some((1, _)) => "%FT%R",
+        Some((_, false)) => "%FT%T",
+        Some((_, true)) => "%FT%T.%L",
+    };
+    c.parse_with_format(value, fmt).map_err(|e| ParseError { position: position + e.position, ..e })
+}
+
+/// Parses `DateTime` as per `parse_iso_datetime()`, with optional UTC offset, converted to UTC.
+fn parse_iso_utc_datetime(c: &Calendar, value: &str, position: usize) -> Result<DateTime, ParseError> {
+    let offset_start = value.find('T')
+        .and_then(|time_start| value[time_start..].find(['Z', '+', '-']).map(|i| time_start + i))
+        .unwrap_or(value.len());
+    let datetime = parse_iso_datetime(c, &value[..offset_start], position)?;
+    let offset_position = position + offset_start;
+    let odt = OffsetDateTime { datetime, offset_minutes: parse_offset(&value[offset_start..], offset_position)? };
+    c.validate_offset_datetime(&odt).map_err(|_| ParseError { position: offset_position, kind: ParseErrorKind::OutOfRange })?;
+    Ok(c.from_unixtime(c.to_unixtime_with_offset(&odt)))
+}
+
+/// Parses UTC offset of `Z`, `±HH:MM`, `±HHMM` or `±HH` in minutes, empty being UTC.
+fn parse_offset(offset: &str, position: usize) -> Result<i16, ParseError> {
+    let sign = match offset.as_bytes() {
+        [] | [b'Z'] => return Ok(0),
+        [b'+', ..] => 1,
+        [b'-', ..] => -1,
+        _ => return Err(ParseError { position: position + 1, kind: ParseErrorKind::Unexpected }),
+    };
+    let digits = &offset[1..];
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, Some((minutes, 1 + hours.len() + 1))),
+        None if digits.len() > 2 && digits.is_char_boundary(2) => (&digits[..2], Some((&digits[2..], 3))),
+        None => (digits, None),
+    };
+    let hours = parse_two_digits(hours, position + 1, 18)?;
+    let minutes = match minutes {
+        Some((minutes, minutes_start)) => parse_two_digits(minutes, position + minutes_start, 59)?,
+        None => 0,
+    };
+    Ok(sign * (hours * 60 + minutes))
+}
+
+fn parse_two_digits(s: &str, position: usize, max: u32) -> Result<i16, ParseError> {
+    if s.len() > 2 {
+        return Err(ParseError { position: position + 2, kind: ParseErrorKind::Unexpected });
+    }
+    let value = parse_number(s, position, 0, max)?;
+    if s.len() < 2 {
+        return Err(ParseError { position: position + s.len(), kind: ParseErrorKind::TooShort });
+    }
+    Ok(value as i16)
+}
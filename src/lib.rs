@@ -2,14 +2,47 @@
 
 #[cfg(not(feature = "std"))]
 extern crate alloc;
+mod block_clock;
 mod calendar;
+mod clock;
 mod constants;
+mod dsl;
+mod format;
+mod iso8601;
+#[cfg(feature = "std")]
+mod runner;
+#[cfg(feature = "scale")]
+mod scale;
+mod scheduler_queue;
+mod scheduler_state;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod timing_wheel;
 mod types;
+mod tz;
+#[cfg(feature = "tz")]
+mod tz_data;
+mod tz_posix;
+#[cfg(feature = "std")]
+mod tzif;
 mod utils;
 
 pub mod prelude {
+    pub use super::block_clock::*;
     pub use super::calendar::*;
+    pub use super::clock::*;
+    pub use super::scheduler_queue::*;
+    pub use super::scheduler_state::*;
+    pub use super::timing_wheel::*;
     pub use super::types::*;
+    pub use super::utils::{days_in_month, days_in_year, is_leap_year};
+    #[cfg(feature = "std")]
+    pub use super::runner::*;
+    #[cfg(feature = "scale")]
+    pub use super::scale::*;
+    pub use super::tz::*;
+    #[cfg(feature = "std")]
+    pub use super::tzif::*;
 }
 
 #[cfg(test)]
@@ -0,0 +1,200 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use super::{
+    clock::*,
+    scheduler_queue::SchedulerQueue,
+    types::*,
+};
+
+type Job = Box<dyn FnMut(u64) + Send>;
+
+struct State {
+    queue:    SchedulerQueue<u64>,
+    // `None` while running, entries removed on cancellation
+    jobs:     BTreeMap<u64, Option<Job>>,
+    // jobs dropped after their closure panicked
+    panicked: BTreeSet<u64>,
+    next_id:  u64,
+    shutdown: bool,
+}
+
+struct Shared {
+    state:   Mutex<State>,
+    condvar: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().expect("failed to lock runner state")
+    }
+}
+
+/// Runner of closures registered against `Schedule`s, on a thread sleeping until the next trigger as per `Clock`.
+///
+/// Closures are called with trigger ms, in order of triggers, catching up on triggers missed while busy.
+/// Closures panicking are dropped, without affecting other closures, see `JobHandle::has_panicked()`.
+/// Dropping the runner shuts it down, after the running closure returns.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// # use std::sync::mpsc;
+/// let runner = ScheduleRunner::with_clock(ManualClock::new(1650412800000));  // 20/04/2022 00:00:00:000
+/// let schedule = Schedule {
+///     start: DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 },
+///     items: vec![(Frequency::Minute, 1)],
+///     ..Default::default()
+/// };
+/// let (sender, receiver) = mpsc::channel();
+/// let handle = runner.register(schedule, move |trigger_ms| sender.send(trigger_ms).unwrap()).unwrap();
+///
+/// runner.clock().advance(120_000);
+/// runner.notify();
+/// assert_eq!(receiver.recv(), Ok(1650412860000));
+/// assert_eq!(receiver.recv(), Ok(1650412920000));
+/// assert!(handle.cancel());
+/// runner.shutdown();
+/// ```
+pub struct ScheduleRunner<C: Clock + Send + Sync + 'static> {
+    clock:  Arc<C>,
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Handle of a closure registered with `ScheduleRunner`, for cancellation.
+#[derive(Clone)]
+pub struct JobHandle {
+    id:     u64,
+    shared: Arc<Shared>,
+}
+
+impl ScheduleRunner<SystemClock> {
+    /// Constructor of a runner of `SystemClock`.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl Default for ScheduleRunner<SystemClock> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clock + Send + Sync + 'static> ScheduleRunner<C> {
+    /// Constructor of a runner of `clock`, spawning its thread.
+    pub fn with_clock(clock: C) -> Self {
+        let clock = Arc::new(clock);
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State { queue: SchedulerQueue::new(), jobs: BTreeMap::new(), panicked: BTreeSet::new(), next_id: 0, shutdown: false }),
+            condvar: Condvar::new(),
+        });
+        let thread = {
+            let (clock, shared) = (clock.clone(), shared.clone());
+            thread::spawn(move || run(&*clock, &shared))
+        };
+        Self { clock, shared, thread: Some(thread) }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Registers `job` to be called at every trigger of validated `schedule` after now, inactive if without triggers after now.
+    pub fn register<F: FnMut(u64) + Send + 'static>(&self, schedule: Schedule, job: F) -> Result<JobHandle, ValidationError> {
+        let mut state = self.shared.lock();
+        let id = state.next_id;
+        state.queue.insert(id, schedule, self.clock.now_ms())?;
+        if state.queue.next_trigger(&id).is_some() {
+            state.jobs.insert(id, Some(Box::new(job)));
+        }
+        state.next_id += 1;
+        self.shared.condvar.notify_all();
+        Ok(JobHandle { id, shared: self.shared.clone() })
+    }
+
+    /// Wakes the runner to re-evaluate triggers, eg. after the `Clock` was changed.
+    pub fn notify(&self) {
+        let _state = self.shared.lock();
+        self.shared.condvar.notify_all();
+    }
+
+    /// Shuts down gracefully, waiting for the running closure to return, without calling further closures.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.condvar.notify_all();
+        if let Some(thread) = self.thread.take() {
+            // closures' panics are caught on the thread, leaving panics of `Clock`, with nothing left to clean up
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<C: Clock + Send + Sync + 'static> Drop for ScheduleRunner<C> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl JobHandle {
+    /// Cancels further calls of the closure, returning `false` if already cancelled or without further triggers.
+    /// A running closure isn't interrupted.
+    pub fn cancel(&self) -> bool {
+        let mut state = self.shared.lock();
+        state.jobs.remove(&self.id);
+        state.queue.remove(&self.id).is_some()
+    }
+
+    /// Whether the closure has further triggers.
+    pub fn is_active(&self) -> bool {
+        self.shared.lock().queue.next_trigger(&self.id).is_some()
+    }
+
+    /// Whether the closure panicked, and was dropped without further calls.
+    pub fn has_panicked(&self) -> bool {
+        self.shared.lock().panicked.contains(&self.id)
+    }
+}
+
+fn run(clock: &impl Clock, shared: &Shared) {
+    let mut state = shared.lock();
+    while !state.shutdown {
+        let now_ms = clock.now_ms();
+        let due = state.queue.pop_due(now_ms);
+        if due.is_empty() {
+            state = match state.queue.peek() {
+                Some((trigger_ms, _)) => shared.condvar.wait_timeout(state, Duration::from_millis(trigger_ms - now_ms)).expect("failed to wait for runner state").0,
+                None => shared.condvar.wait(state).expect("failed to wait for runner state"),
+            };
+            continue;
+        }
+        for (trigger_ms, id) in due {
+            if state.shutdown {
+                break;
+            }
+            if let Some(mut job) = state.jobs.get_mut(&id).and_then(Option::take) {
+                drop(state);
+                let result = panic::catch_unwind(AssertUnwindSafe(|| job(trigger_ms)));
+                state = shared.lock();
+                if result.is_err() {
+                    state.jobs.remove(&id);
+                    state.queue.remove(&id);
+                    state.panicked.insert(id);
+                } else if let Some(slot) = state.jobs.get_mut(&id) {
+                    // re-registered unless cancelled while running
+                    *slot = Some(job);
+                }
+            }
+        }
+        // drop jobs without further triggers
+        let State { queue, jobs, .. } = &mut *state;
+        jobs.retain(|id, _| queue.next_trigger(id).is_some());
+    }
+}
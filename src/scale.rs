@@ -0,0 +1,354 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::ops::Deref;
+use codec::{Compact, CompactLen, Decode, Encode, Input, MaxEncodedLen, Output};
+use scale_info::{build::Fields, meta_type, Path, Type, TypeInfo, TypeParameter};
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+};
+
+/// Valid `DateTime`, encoded as compact ms from epoch, eg. 7 bytes rather than 9 bytes of `DateTime` fields for current dates.
+/// Constructed via `TryFrom<DateTime>` of `DateTime`s prior to year 4000, with decoding failing for ms beyond.
+#[derive(Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CompactDateTime(DateTime);
+
+impl CompactDateTime {
+    pub fn datetime(&self) -> &DateTime {
+        &self.0
+    }
+}
+
+impl TryFrom<DateTime> for CompactDateTime {
+    type Error = ValidationError;
+
+    fn try_from(dt: DateTime) -> Result<Self, Self::Error> {
+        let c = Calendar::create();
+        c.validate_datetime(&dt)?;
+        // as supported by `Calendar::from_unixtime()` on decoding
+        if c.to_unixtime(&dt) >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return Err(ValidationError::OutOfScope);
+        }
+        Ok(CompactDateTime(dt))
+    }
+}
+
+impl From<CompactDateTime> for DateTime {
+    fn from(dt: CompactDateTime) -> Self {
+        dt.0
+    }
+}
+
+impl Encode for CompactDateTime {
+    fn size_hint(&self) -> usize {
+        Compact::<u64>::compact_len(&Calendar::create().to_unixtime(&self.0))
+    }
+
+    fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+        Compact(Calendar::create().to_unixtime(&self.0)).encode_to(dest)
+    }
+}
+
+impl Decode for CompactDateTime {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let ts = Compact::<u64>::decode(input)?.0;
+        if ts >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return Err("CompactDateTime out of scope".into());
+        }
+        Ok(CompactDateTime(Calendar::create().from_unixtime(ts)))
+    }
+}
+
+impl MaxEncodedLen for CompactDateTime {
+    fn max_encoded_len() -> usize {
+        Compact::<u64>::compact_len(&(YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] - 1))
+    }
+}
+
+impl TypeInfo for CompactDateTime {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("CompactDateTime", module_path!()))
+            .composite(Fields::unnamed().field(|f| f.compact::<u64>().type_name("u64")))
+    }
+}
+
+/// `Vec` of at most `MAX` elements, encoded as `Vec`, with decoding failing for more elements.
+/// Constructed via `TryFrom<Vec<T>>`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BoundedList<T, const MAX: u32>(Vec<T>);
+
+impl<T, const MAX: u32> BoundedList<T, MAX> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const MAX: u32> Default for BoundedList<T, MAX> {
+    fn default() -> Self {
+        BoundedList(Vec::new())
+    }
+}
+
+impl<T, const MAX: u32> Deref for BoundedList<T, MAX> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const MAX: u32> TryFrom<Vec<T>> for BoundedList<T, MAX> {
+    type Error = ValidationError;
+
+    fn try_from(items: Vec<T>) -> Result<Self, Self::Error> {
+        if items.len() > MAX as usize {
+            return Err(ValidationError::Invalid);
+        }
+        Ok(BoundedList(items))
+    }
+}
+
+impl<T: Encode, const MAX: u32> Encode for BoundedList<T, MAX> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<T: Decode, const MAX: u32> Decode for BoundedList<T, MAX> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        // length checked prior to decoding of elements
+        let len = Compact::<u32>::decode(input)?.0;
+        if len > MAX {
+            return Err("BoundedList exceeds its bound".into());
+        }
+        (0..len).map(|_| T::decode(input)).collect::<Result<Vec<_>, _>>().map(BoundedList)
+    }
+}
+
+impl<T: MaxEncodedLen, const MAX: u32> MaxEncodedLen for BoundedList<T, MAX> {
+    fn max_encoded_len() -> usize {
+        Compact::<u32>::compact_len(&MAX).saturating_add(T::max_encoded_len().saturating_mul(MAX as usize))
+    }
+}
+
+impl<T: TypeInfo + 'static, const MAX: u32> TypeInfo for BoundedList<T, MAX> {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("BoundedList", module_path!()))
+            .type_params(vec![TypeParameter::new("T", Some(meta_type::<T>()))])
+            .composite(Fields::unnamed().field(|f| f.ty::<Vec<T>>().type_name("Vec<T>")))
+    }
+}
+
+/// `Schedule` with bounded encoding, as required for `MaxEncodedLen`, of at most `MAX_ITEMS` items, and `DateTime`s as `CompactDateTime`.
+///
+/// Schedules stored by 0.1.x are in `ScheduleV1` layout, and are migrated by decoding as `ScheduleV1` and converting with `TryFrom<ScheduleV1>`,
+/// ie. `ScheduleV1::decode()` -> `Schedule::from()` -> `CompactSchedule::try_from()`, eg. via `translate()` of a storage item.
+/// Schedules stored as `VersionedSchedule` convert with `TryFrom<VersionedSchedule>`, and ones stored by 0.2.x with `TryFrom<Schedule>`.
+/// `Schedule`s exceeding `MAX_ITEMS` or otherwise invalid fail the conversion.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// # use codec::{Decode, Encode, MaxEncodedLen};
+/// let legacy = ScheduleV1 {
+///     start: DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
+///     items: vec![(Frequency::Week, 2), (Frequency::Day, 3)],
+///     end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 }),
+/// }.encode();
+/// let compact = CompactSchedule::<4>::try_from(ScheduleV1::decode(&mut &legacy[..]).unwrap()).unwrap();
+/// assert!(compact.encode().len() < legacy.len());
+/// assert!(compact.encode().len() <= CompactSchedule::<4>::max_encoded_len());
+/// assert_eq!(Schedule::from(compact).items, vec![(Frequency::Week, 2), (Frequency::Day, 3)]);
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Eq, Encode, TypeInfo, MaxEncodedLen)]
+pub struct CompactSchedule<const MAX_ITEMS: u32> {
+    pub start:         CompactDateTime,
+    pub items:         BoundedList<(Frequency, u32), MAX_ITEMS>,
+    pub end:           Option<CompactDateTime>,
+    pub times_of_day:  BoundedList<TimeOfDay, { MAX_TIMES_OF_DAY as u32 }>,
+    pub days_of_month: BoundedList<u8, { MAX_DAYS_OF_MONTH as u32 }>,
+}
+
+/// Validates `Schedule` by `Calendar::validate_schedule()`, failing with `ValidationError::Invalid` for more than `MAX_ITEMS` items.
+impl<const MAX_ITEMS: u32> TryFrom<Schedule> for CompactSchedule<MAX_ITEMS> {
+    type Error = ValidationError;
+
+    fn try_from(schedule: Schedule) -> Result<Self, Self::Error> {
+        Calendar::create().validate_schedule(&schedule)?;
+        Ok(CompactSchedule {
+            start:         schedule.start.try_into()?,
+            items:         schedule.items.try_into()?,
+            end:           schedule.end.map(TryInto::try_into).transpose()?,
+            times_of_day:  schedule.times_of_day.try_into()?,
+            days_of_month: schedule.days_of_month.try_into()?,
+        })
+    }
+}
+
+/// Decodes fields as encoded, failing for schedules failing `Calendar::validate_schedule()`, as per `TryFrom<Schedule>`.
+impl<const MAX_ITEMS: u32> Decode for CompactSchedule<MAX_ITEMS> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let schedule = CompactSchedule {
+            start:         Decode::decode(input)?,
+            items:         Decode::decode(input)?,
+            end:           Decode::decode(input)?,
+            times_of_day:  Decode::decode(input)?,
+            days_of_month: Decode::decode(input)?,
+        };
+        if Calendar::create().validate_schedule(&schedule.clone().into()).is_err() {
+            return Err("CompactSchedule invalid".into());
+        }
+        Ok(schedule)
+    }
+}
+
+/// Migrates `Schedule`s stored by 0.1.x, see `TryFrom<Schedule>`.
+impl<const MAX_ITEMS: u32> TryFrom<ScheduleV1> for CompactSchedule<MAX_ITEMS> {
+    type Error = ValidationError;
+
+    fn try_from(schedule: ScheduleV1) -> Result<Self, Self::Error> {
+        Schedule::from(schedule).try_into()
+    }
+}
+
+/// Upgrades to `VersionedSchedule::LATEST_VERSION`, see `TryFrom<Schedule>`.
+impl<const MAX_ITEMS: u32> TryFrom<VersionedSchedule> for CompactSchedule<MAX_ITEMS> {
+    type Error = ValidationError;
+
+    fn try_from(versioned: VersionedSchedule) -> Result<Self, Self::Error> {
+        Schedule::from(versioned).try_into()
+    }
+}
+
+impl<const MAX_ITEMS: u32> From<CompactSchedule<MAX_ITEMS>> for Schedule {
+    fn from(schedule: CompactSchedule<MAX_ITEMS>) -> Self {
+        Schedule {
+            start:         schedule.start.into(),
+            items:         schedule.items.into_inner(),
+            end:           schedule.end.map(Into::into),
+            times_of_day:  schedule.times_of_day.into_inner(),
+            days_of_month: schedule.days_of_month.into_inner(),
+        }
+    }
+}
+
+/// Layout of `Schedule` as released in 0.1.x.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct ScheduleV1 {
+    pub start: DateTime,
+    pub items: Vec<(Frequency, u32)>,
+    pub end:   Option<DateTime>,
+}
+
+/// No expansions, as prior to `times_of_day` and `days_of_month`.
+impl From<ScheduleV1> for Schedule {
+    fn from(schedule: ScheduleV1) -> Self {
+        Schedule { start: schedule.start, items: schedule.items, end: schedule.end, ..Default::default() }
+    }
+}
+
+/// `Schedule` encoding with explicit version tag, for storage to remain decodable as `Schedule` gains fields.
+/// Every version is a frozen layout of `Schedule`, with lossless upgrades to the subsequent version.
+///
+/// Encoded as version tag byte followed by the layout, see `fixtures/scale`. Stored unversioned `Schedule`s of 0.1.x are migrated by decoding as `ScheduleV1`.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// # use codec::{Decode, Encode};
+/// let legacy = ScheduleV1 {
+///     start: DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
+///     items: vec![(Frequency::Week, 2)],
+///     end: None,
+/// }.encode();
+/// let versioned = VersionedSchedule::V1(ScheduleV1::decode(&mut &legacy[..]).unwrap());
+/// assert_eq!(versioned.version(), 1);
+/// let schedule = Schedule::from(versioned);
+/// assert_eq!(schedule.items, vec![(Frequency::Week, 2)]);
+/// assert_eq!(VersionedSchedule::from(schedule).version(), VersionedSchedule::LATEST_VERSION);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum VersionedSchedule {
+    #[codec(index = 1)]
+    V1(ScheduleV1),
+    #[codec(index = 2)]
+    V2(ScheduleV2),
+}
+
+/// Layout of `Schedule` with `times_of_day` and `days_of_month` expansions.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct ScheduleV2 {
+    pub start:         DateTime,
+    pub items:         Vec<(Frequency, u32)>,
+    pub end:           Option<DateTime>,
+    pub times_of_day:  Vec<TimeOfDay>,
+    pub days_of_month: Vec<u8>,
+}
+
+impl VersionedSchedule {
+    /// Version of `From<Schedule>`.
+    pub const LATEST_VERSION: u8 = 2;
+
+    pub fn version(&self) -> u8 {
+        match self {
+            VersionedSchedule::V1(_) => 1,
+            VersionedSchedule::V2(_) => 2,
+        }
+    }
+
+    /// Upgrades to `LATEST_VERSION`, without loss of the schedule.
+    pub fn upgrade(self) -> Self {
+        match self {
+            VersionedSchedule::V1(schedule) => VersionedSchedule::V2(schedule.into()),
+            latest @ VersionedSchedule::V2(_) => latest,
+        }
+    }
+}
+
+/// No expansions, as prior to `times_of_day` and `days_of_month`.
+impl From<ScheduleV1> for ScheduleV2 {
+    fn from(schedule: ScheduleV1) -> Self {
+        ScheduleV2 { start: schedule.start, items: schedule.items, end: schedule.end, times_of_day: Vec::new(), days_of_month: Vec::new() }
+    }
+}
+
+impl From<Schedule> for VersionedSchedule {
+    fn from(schedule: Schedule) -> Self {
+        VersionedSchedule::V2(ScheduleV2 {
+            start:         schedule.start,
+            items:         schedule.items,
+            end:           schedule.end,
+            times_of_day:  schedule.times_of_day,
+            days_of_month: schedule.days_of_month,
+        })
+    }
+}
+
+impl From<ScheduleV2> for Schedule {
+    fn from(schedule: ScheduleV2) -> Self {
+        Schedule {
+            start:         schedule.start,
+            items:         schedule.items,
+            end:           schedule.end,
+            times_of_day:  schedule.times_of_day,
+            days_of_month: schedule.days_of_month,
+        }
+    }
+}
+
+/// Upgrades to `VersionedSchedule::LATEST_VERSION`.
+impl From<VersionedSchedule> for Schedule {
+    fn from(versioned: VersionedSchedule) -> Self {
+        match versioned {
+            VersionedSchedule::V1(schedule) => ScheduleV2::from(schedule).into(),
+            VersionedSchedule::V2(schedule) => schedule.into(),
+        }
+    }
+}
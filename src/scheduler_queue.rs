@@ -0,0 +1,121 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::{BTreeMap, BTreeSet}, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+};
+
+/// Priority queue of `Schedule`s keyed by job `Id`, ordered by next trigger in ms from epoch.
+///
+/// Jobs triggering in the same ms are ordered by `Id`. Jobs without further triggers, eg. past `Schedule.end`, are dropped.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let mut queue = SchedulerQueue::new();
+/// let now_ms: u64 = 1650412800000;  // 20/04/2022 00:00:00:000
+/// let start = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+/// queue.insert("minutely", Schedule { start: start.clone(), items: vec![(Frequency::Minute, 1)], ..Default::default() }, now_ms).unwrap();
+/// queue.insert("hourly", Schedule { start, items: vec![(Frequency::Hour, 1)], ..Default::default() }, now_ms).unwrap();
+/// assert_eq!(queue.peek(), Some((now_ms + 60_000, &"minutely")));
+///
+/// let due = queue.pop_due(now_ms + 60 * 60_000);
+/// assert_eq!(due.len(), 61);
+/// assert_eq!(due[58..], [(now_ms + 59 * 60_000, "minutely"), (now_ms + 60 * 60_000, "hourly"), (now_ms + 60 * 60_000, "minutely")]);
+/// assert_eq!(queue.next_trigger(&"hourly"), Some(now_ms + 2 * 60 * 60_000));
+/// ```
+pub struct SchedulerQueue<Id> {
+    calendar: Calendar,
+    jobs:     BTreeMap<Id, (Schedule, u64)>,  // schedule, next trigger
+    queue:    BTreeSet<(u64, Id)>,
+}
+
+impl<Id: Ord + Clone> Default for SchedulerQueue<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: Ord + Clone> SchedulerQueue<Id> {
+    /// Constructor of an empty queue.
+    pub fn new() -> Self {
+        Self::with_calendar(Calendar::create())
+    }
+
+    /// Constructor of an empty queue, validating `Schedule`s by `calendar`, eg. configured by `Calendar::with_cost_budget()`.
+    pub fn with_calendar(calendar: Calendar) -> Self {
+        Self { calendar, jobs: BTreeMap::new(), queue: BTreeSet::new() }
+    }
+
+    /// Number of queued jobs.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Next trigger of job `id`, in ms from epoch.
+    pub fn next_trigger(&self, id: &Id) -> Option<u64> {
+        self.jobs.get(id).map(|(_, trigger_ms)| *trigger_ms)
+    }
+
+    /// Earliest trigger, with its job `Id`.
+    pub fn peek(&self) -> Option<(u64, &Id)> {
+        self.queue.iter().next().map(|(trigger_ms, id)| (*trigger_ms, id))
+    }
+
+    /// Queues job `id` at the next occurrence of validated `schedule` after `now_ms`, replacing any prior `Schedule` of `id`, which is returned.
+    /// Fails with `ValidationError::OutOfScope` if `now_ms` is past the scope of `Calendar`.
+    pub fn insert(&mut self, id: Id, schedule: Schedule, now_ms: u64) -> Result<Option<Schedule>, ValidationError> {
+        self.calendar.validate_schedule(&schedule)?;
+        if now_ms >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return Err(ValidationError::OutOfScope);
+        }
+        let prior = self.remove(&id);
+        if let Some(trigger_ms) = self.next_trigger_after(&schedule, now_ms) {
+            self.queue.insert((trigger_ms, id.clone()));
+            self.jobs.insert(id, (schedule, trigger_ms));
+        }
+        Ok(prior)
+    }
+
+    /// Dequeues job `id`, returning its `Schedule`.
+    pub fn remove(&mut self, id: &Id) -> Option<Schedule> {
+        let (schedule, trigger_ms) = self.jobs.remove(id)?;
+        self.queue.remove(&(trigger_ms, id.clone()));
+        Some(schedule)
+    }
+
+    /// Replaces `Schedule` of a queued job `id`, as per `SchedulerQueue::insert()`, returning `false` without queueing if `id` isn't queued.
+    pub fn reschedule(&mut self, id: &Id, schedule: Schedule, now_ms: u64) -> Result<bool, ValidationError> {
+        if !self.jobs.contains_key(id) {
+            return Ok(false);
+        }
+        self.insert(id.clone(), schedule, now_ms).map(|_| true)
+    }
+
+    /// Dequeues all triggers up to and including `now_ms`, ordered by trigger and `Id`, requeueing jobs at their next occurrence.
+    /// Jobs triggering multiple times up to `now_ms` are returned for every trigger.
+    pub fn pop_due(&mut self, now_ms: u64) -> Vec<(u64, Id)> /* triggers_in_ms */ {
+        let mut due = Vec::new();
+        while let Some((trigger_ms, id)) = self.queue.first().filter(|(trigger_ms, _)| *trigger_ms <= now_ms).cloned() {
+            self.queue.remove(&(trigger_ms, id.clone()));
+            let (schedule, _) = self.jobs.remove(&id).expect("queued job without schedule");
+            if let Some(next_ms) = self.next_trigger_after(&schedule, trigger_ms) {
+                self.queue.insert((next_ms, id.clone()));
+                self.jobs.insert(id.clone(), (schedule, next_ms));
+            }
+            due.push((trigger_ms, id));
+        }
+        due
+    }
+
+    /// Next occurrence of `schedule` after `after_ms`, if within scope of `Calendar`.
+    fn next_trigger_after(&self, schedule: &Schedule, after_ms: u64) -> Option<u64> {
+        let delta = self.calendar.next_occurrence_ms(&self.calendar.from_unixtime(after_ms), schedule)?;
+        Some(after_ms + delta).filter(|&ts| ts < YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1])
+    }
+}
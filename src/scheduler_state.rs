@@ -0,0 +1,256 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "scale")]
+use codec::{Decode, Encode};
+#[cfg(feature = "scale")]
+use scale_info::TypeInfo;
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+};
+
+/// Version of the binary format of `SchedulerState::to_bytes()`.
+pub const SCHEDULER_STATE_VERSION: u8 = 1;
+
+/// Persisted state of a job of `SchedulerState`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+pub struct JobState {
+    pub schedule:   Schedule,
+    /// Last recorded trigger, or time of insertion prior to the first trigger
+    pub last_run:   DateTime,
+    /// Number of recorded triggers, ie. sequence number of the last trigger
+    pub fire_count: u64,
+    pub paused:     bool,
+}
+
+/// Snapshot of jobs keyed by id, for schedulers to resume after a restart without losing or duplicating triggers.
+///
+/// Triggers are returned by `SchedulerState::due()` until recorded by `SchedulerState::record_fire()`.
+/// A trigger fired but not recorded in the saved snapshot, eg. on a crash prior to the save, is returned again after restoring,
+/// with the same trigger ms and sequence number, for effects to be deduplicated by these.
+/// A trigger recorded in the saved snapshot is never returned again.
+///
+/// Snapshots are encoded by `SchedulerState::to_bytes()` in a compact, versioned binary format, or by SCALE with `scale` feature.
+/// Unlike `SchedulerState::from_bytes()`, SCALE decoding doesn't validate `Schedule`s, see `Calendar::validate_schedule()`.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let now = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+/// let mut state = SchedulerState::default();
+/// state.insert(1, Schedule { start: now.clone(), items: vec![(Frequency::Hour, 1)], ..Default::default() }, now.clone()).unwrap();
+///
+/// let later = DateTime { hour: 2, ..now.clone() };
+/// let due = state.due(&later);
+/// assert_eq!(due, vec![(1650416400000, 1, 1), (1650420000000, 1, 2)]);  // trigger ms, id, sequence number
+/// assert!(state.record_fire(1, 1650416400000));
+/// let saved = state.to_bytes();  // crash prior to recording the 2nd trigger
+///
+/// let restored = SchedulerState::from_bytes(&saved).unwrap();
+/// assert_eq!(restored.due(&later), vec![(1650420000000, 1, 2)]);
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+pub struct SchedulerState {
+    jobs: BTreeMap<u64, JobState>,
+}
+
+impl SchedulerState {
+    pub fn jobs(&self) -> &BTreeMap<u64, JobState> {
+        &self.jobs
+    }
+
+    /// Inserts job `id` of validated `schedule`, triggering after `now`, replacing any prior job of `id`, which is returned.
+    pub fn insert(&mut self, id: u64, schedule: Schedule, now: DateTime) -> Result<Option<JobState>, ValidationError> {
+        let c = Calendar::create();
+        c.validate_schedule(&schedule)?;
+        c.validate_datetime(&now)?;
+        Ok(self.jobs.insert(id, JobState { schedule, last_run: now, fire_count: 0, paused: false }))
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<JobState> {
+        self.jobs.remove(&id)
+    }
+
+    /// Pauses job `id`, returning `false` if not found.
+    pub fn pause(&mut self, id: u64) -> bool {
+        self.jobs.get_mut(&id).map(|job| job.paused = true).is_some()
+    }
+
+    /// Resumes job `id` triggering after `now`, skipping triggers while paused, returning `false` if not found.
+    pub fn resume(&mut self, id: u64, now: DateTime) -> bool {
+        match self.jobs.get_mut(&id) {
+            Some(job) => {
+                if job.paused {
+                    job.paused = false;
+                    job.last_run = job.last_run.clone().max(now);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Triggers of unpaused jobs after their `last_run` up to and including `now`, as (trigger ms, id, sequence number), ordered by trigger and id.
+    pub fn due(&self, now: &DateTime) -> Vec<(u64, u64, u64)> /* triggers_in_ms */ {
+        let c = Calendar::create();
+        let mut due: Vec<_> = self.jobs.iter()
+            .filter(|(_, job)| !job.paused && job.last_run < *now)
+            .flat_map(|(&id, job)| {
+                let (triggers, _) = c.next_occurrence_ms_with_past_triggers(Some(&job.last_run), now, &job.schedule);
+                triggers.into_iter().zip(job.fire_count + 1..).map(move |(trigger_ms, seq)| (trigger_ms, id, seq))
+            })
+            .collect();
+        due.sort_unstable();
+        due
+    }
+
+    /// Records trigger of job `id` at `trigger_ms`, as returned by `SchedulerState::due()`, in order of triggers.
+    /// Returns `false` for triggers not after `last_run`, ie. already recorded, or if not found.
+    pub fn record_fire(&mut self, id: u64, trigger_ms: u64) -> bool {
+        let c = Calendar::create();
+        match self.jobs.get_mut(&id) {
+            Some(job) if trigger_ms > c.to_unixtime(&job.last_run) && trigger_ms < YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] => {
+                job.last_run = c.from_unixtime(trigger_ms);
+                job.fire_count += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Encodes in binary format of `SCHEDULER_STATE_VERSION`, of LEB128 integers, with `DateTime`s as ms from epoch.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let c = Calendar::create();
+        let mut bytes = vec![SCHEDULER_STATE_VERSION];
+        write_varint(&mut bytes, self.jobs.len() as u64);
+        for (&id, job) in &self.jobs {
+            let schedule = &job.schedule;
+            write_varint(&mut bytes, id);
+            write_varint(&mut bytes, c.to_unixtime(&schedule.start));
+            write_varint(&mut bytes, schedule.items.len() as u64);
+            for &(freq, multiplier) in &schedule.items {
+                bytes.push(freq as u8);
+                write_varint(&mut bytes, multiplier as u64);
+            }
+            match &schedule.end {
+                Some(end) => {
+                    bytes.push(1);
+                    write_varint(&mut bytes, c.to_unixtime(end));
+                }
+                None => bytes.push(0),
+            }
+            bytes.push(schedule.times_of_day.len() as u8);
+            bytes.extend(schedule.times_of_day.iter().flat_map(|t| [t.hour, t.minute]));
+            bytes.push(schedule.days_of_month.len() as u8);
+            bytes.extend(&schedule.days_of_month);
+            write_varint(&mut bytes, c.to_unixtime(&job.last_run));
+            write_varint(&mut bytes, job.fire_count);
+            bytes.push(job.paused as u8);
+        }
+        bytes
+    }
+
+    /// Decodes binary format of `SchedulerState::to_bytes()`, validating `Schedule`s, with `ParseError` at byte `position` of `bytes`.
+    /// Jobs are expected in ascending order of id, as encoded.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader { bytes, position: 0 };
+        if reader.byte()? != SCHEDULER_STATE_VERSION {
+            return Err(ParseError { position: 0, kind: ParseErrorKind::Unsupported });
+        }
+        let c = Calendar::create();
+        let mut jobs = BTreeMap::new();
+        for _ in 0..reader.varint()? {
+            let position = reader.position;
+            let id = reader.varint()?;
+            if jobs.keys().next_back().is_some_and(|&prior| prior >= id) {
+                return Err(ParseError { position, kind: ParseErrorKind::Unexpected });
+            }
+            let schedule_position = reader.position;
+            let start = reader.datetime(&c)?;
+            // every item takes at least 2 bytes, bounding allocation
+            let items_len = reader.varint_max((bytes.len() - reader.position) as u64 / 2)?;
+            let items = (0..items_len).map(|_| {
+                let freq = reader.frequency()?;
+                let multiplier = reader.varint_max(u32::MAX as u64)? as u32;
+                Ok((freq, multiplier))
+            }).collect::<Result<Vec<_>, _>>()?;
+            let end = if reader.flag()? { Some(reader.datetime(&c)?) } else { None };
+            let times_of_day = (0..reader.byte()?).map(|_| Ok(TimeOfDay { hour: reader.byte()?, minute: reader.byte()? })).collect::<Result<Vec<_>, _>>()?;
+            let days_of_month = (0..reader.byte()?).map(|_| reader.byte()).collect::<Result<Vec<_>, _>>()?;
+            let schedule = Schedule { start, items, end, times_of_day, days_of_month };
+            c.validate_schedule(&schedule).map_err(|_| ParseError { position: schedule_position, kind: ParseErrorKind::OutOfRange })?;
+            let last_run = reader.datetime(&c)?;
+            let fire_count = reader.varint()?;
+            let paused = reader.flag()?;
+            jobs.insert(id, JobState { schedule, last_run, fire_count, paused });
+        }
+        if reader.position < bytes.len() {
+            return Err(ParseError { position: reader.position, kind: ParseErrorKind::Unexpected });
+        }
+        Ok(Self { jobs })
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes:    &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, ParseError> {
+        let byte = *self.bytes.get(self.position).ok_or(ParseError { position: self.bytes.len(), kind: ParseErrorKind::TooShort })?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn flag(&mut self) -> Result<bool, ParseError> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ParseError { position: self.position - 1, kind: ParseErrorKind::Unexpected }),
+        }
+    }
+
+    fn varint(&mut self) -> Result<u64, ParseError> {
+        let position = self.position;
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as u64;
+            if bits << shift >> shift != bits {
+                return Err(ParseError { position, kind: ParseErrorKind::OutOfRange });
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ParseError { position, kind: ParseErrorKind::OutOfRange })
+    }
+
+    fn varint_max(&mut self, max: u64) -> Result<u64, ParseError> {
+        let position = self.position;
+        self.varint().and_then(|value| if value <= max { Ok(value) } else { Err(ParseError { position, kind: ParseErrorKind::OutOfRange }) })
+    }
+
+    fn datetime(&mut self, c: &Calendar) -> Result<DateTime, ParseError> {
+        self.varint_max(YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] - 1).map(|ts| c.from_unixtime(ts))
+    }
+
+    fn frequency(&mut self) -> Result<Frequency, ParseError> {
+        const FREQUENCIES: [Frequency; 8] = [Frequency::Year, Frequency::Month, Frequency::Week, Frequency::Day, Frequency::Hour, Frequency::Minute, Frequency::Second, Frequency::Ms];
+        let tag = self.byte()?;
+        FREQUENCIES.get(tag as usize).copied().ok_or(ParseError { position: self.position - 1, kind: ParseErrorKind::Unexpected })
+    }
+}
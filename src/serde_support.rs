@@ -0,0 +1,124 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use super::{
+    calendar::Calendar,
+    constants::*,
+    dsl::parse_time_of_day,
+    iso8601::parse_iso_datetime,
+    types::*,
+};
+
+/// Serializes as ISO 8601 string in human readable formats, eg. `2022-04-30T10:20:30.400`, and as ms from epoch otherwise.
+impl Serialize for DateTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let c = Calendar::create();
+        let ts = c.to_unixtime_res(self).map_err(|e| serde::ser::Error::custom(format_args!("invalid datetime: {:?}", e)))?;
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(ts)
+        }
+    }
+}
+
+/// Deserializes from ISO 8601 string in human readable formats, as per `%Y-%m-%d` with optional `T%H:%M`, `T%H:%M:%S` or `T%H:%M:%S.%L`,
+/// and from ms from epoch otherwise.
+impl<'de> Deserialize<'de> for DateTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DateTimeVisitor)
+        } else {
+            deserializer.deserialize_u64(DateTimeVisitor)
+        }
+    }
+}
+
+struct DateTimeVisitor;
+
+impl de::Visitor<'_> for DateTimeVisitor {
+    type Value = DateTime;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ISO 8601 datetime string or ms from epoch")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<DateTime, E> {
+        parse_iso_datetime(&Calendar::create(), s, 0).map_err(|e| E::custom(format_args!("invalid datetime: {}", e)))
+    }
+
+    fn visit_u64<E: de::Error>(self, ts: u64) -> Result<DateTime, E> {
+        if ts >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(ts), &self));
+        }
+        Ok(Calendar::create().from_unixtime(ts))
+    }
+}
+
+/// Serializes as `HH:MM` string in human readable formats, eg. `09:30`, and as (`hour`, `minute`) tuple otherwise.
+impl Serialize for TimeOfDay {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.hour, self.minute).serialize(serializer)
+        }
+    }
+}
+
+/// Deserializes from `HH:MM` string in human readable formats, and from (`hour`, `minute`) tuple otherwise, rejecting times out of range.
+impl<'de> Deserialize<'de> for TimeOfDay {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(TimeOfDayVisitor)
+        } else {
+            let (hour, minute) = <(u8, u8)>::deserialize(deserializer)?;
+            if hour > 23 {
+                return Err(de::Error::invalid_value(de::Unexpected::Unsigned(hour as u64), &"hour within 0..=23"));
+            }
+            if minute > 59 {
+                return Err(de::Error::invalid_value(de::Unexpected::Unsigned(minute as u64), &"minute within 0..=59"));
+            }
+            Ok(TimeOfDay { hour, minute })
+        }
+    }
+}
+
+struct TimeOfDayVisitor;
+
+impl de::Visitor<'_> for TimeOfDayVisitor {
+    type Value = TimeOfDay;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "time of day string as HH:MM")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<TimeOfDay, E> {
+        parse_time_of_day(s, 0).map_err(|e| E::custom(format_args!("invalid time of day: {}", e)))
+    }
+}
+
+/// Fields of `Schedule` prior to validation.
+#[derive(Deserialize)]
+#[serde(rename = "Schedule", deny_unknown_fields)]
+struct ScheduleFields {
+    start:         DateTime,
+    items:         Vec<(Frequency, u32)>,
+    #[serde(default)]
+    end:           Option<DateTime>,
+    #[serde(default)]
+    times_of_day:  Vec<TimeOfDay>,
+    #[serde(default)]
+    days_of_month: Vec<u8>,
+}
+
+/// Deserializes fields of `Schedule`, validated by `Calendar::validate_schedule()`.
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ScheduleFields { start, items, end, times_of_day, days_of_month } = ScheduleFields::deserialize(deserializer)?;
+        let schedule = Schedule { start, items, end, times_of_day, days_of_month };
+        Calendar::create().validate_schedule(&schedule).map_err(|e| de::Error::custom(format_args!("invalid schedule: {:?}", e)))?;
+        Ok(schedule)
+    }
+}
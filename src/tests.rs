@@ -1,6 +1,6 @@
 use super::prelude::*;
 #[cfg(not(feature = "std"))]
-use alloc::vec;
+use alloc::{string::{String, ToString}, vec, vec::Vec};
 
 #[cfg(feature = "std")]
 use std::vec;
@@ -13,7 +13,7 @@ fn test_roundtrip() {
         (1648515689162, DateTime { year: 2022, month:  3, day: 29, hour:  1, minute:  1, second: 29, ms: 162 }),
         (1646092675123, DateTime { year: 2022, month:  2, day: 28, hour: 23, minute: 57, second: 55, ms: 123 }),
         (1583020675456, DateTime { year: 2020, month:  2, day: 29, hour: 23, minute: 57, second: 55, ms: 456 }),
-        (1731665410010, DateTime { year: 2024, month: 11, day: 15, hour: 10, minute: 10, second: 10, ms: 010 }),
+        (1731665410010, DateTime { year: 2024, month: 11, day: 15, hour: 10, minute: 10, second: 10, ms:  10 }),
         (1648515689162, DateTime { year: 2022, month:  3, day: 29, hour:  1, minute:  1, second: 29, ms: 162 }),
         (1650863010000, DateTime { year: 2022, month:  4, day: 25, hour:  5, minute:  3, second: 30, ms:   0 })
     ];
@@ -26,6 +26,71 @@ fn test_roundtrip() {
     }
 }
 
+#[test]
+fn test_roundtrip_at_month_and_year_starts() {
+    let c = Calendar::create();
+    for (year, month) in [(1970, 1), (1970, 2), (2021, 1), (2022, 1), (2022, 3), (2023, 1), (2024, 3), (2031, 1), (2031, 2), (2100, 1), (2100, 3), (2400, 3), (2401, 1), (3600, 1), (3999, 12)] {
+        let dt = DateTime { year, month, day: 1, hour: 0, minute: 0, second: 0, ms: 0 };
+        assert_eq!(dt, c.from_unixtime(c.to_unixtime(&dt)));
+    }
+
+    // last ms before the starts
+    for dt in [
+        DateTime { year: 1970, month:  1, day: 31, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 2021, month: 12, day: 31, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 2022, month:  2, day: 28, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 2024, month:  2, day: 29, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 2030, month: 12, day: 31, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 2099, month: 12, day: 31, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 2100, month:  2, day: 28, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 2400, month: 12, day: 31, hour: 23, minute: 59, second: 59, ms: 999 },
+        DateTime { year: 3999, month: 12, day: 31, hour: 23, minute: 59, second: 59, ms: 999 },
+    ] {
+        assert_eq!(dt, c.from_unixtime(c.to_unixtime(&dt)));
+    }
+}
+
+#[test]
+fn test_batch_conversions() {
+    let c = Calendar::create();
+    let end_of_scope = YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] - 1;
+    // sorted across month and year starts, then unsorted, back and forth
+    let timestamps = [
+        0, 2678399999, 2678400000, 31535999999, 31536000000, 1583020799999, 1583020800000, 1609459199999, 1609459200000, 1640995200000,
+        1672531200000, 1650412800000, 1650412800000, 1640995199999, 86_400_000, end_of_scope, 1650412800000, end_of_scope - 1,
+    ];
+    let mut datetimes = vec![DateTime::default(); timestamps.len()];
+    c.from_unixtime_batch(&timestamps, &mut datetimes);
+    assert_eq!(timestamps.iter().map(|&ts| c.from_unixtime(ts)).collect::<Vec<_>>(), datetimes);
+
+    let mut roundtrip = [0; 18];
+    c.to_unixtime_batch(&datetimes, &mut roundtrip);
+    assert_eq!(timestamps, roundtrip);
+
+    // empty
+    c.from_unixtime_batch(&[], &mut []);
+    c.to_unixtime_batch(&[], &mut []);
+}
+
+#[test]
+#[should_panic]
+fn test_batch_conversions_length_mismatch() {
+    Calendar::create().from_unixtime_batch(&[0, 1], &mut [DateTime::default()]);
+}
+
+#[test]
+#[should_panic(expected = "past the scope of Calendar")]
+fn test_from_unixtime_past_scope() {
+    // estimated year index of mid January 4000 being past the last year of the offsets
+    Calendar::create().from_unixtime(YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] + 20 * MS_IN_DAY);
+}
+
+#[test]
+#[should_panic(expected = "past the scope of Calendar")]
+fn test_from_unixtime_batch_past_scope() {
+    Calendar::create().from_unixtime_batch(&[0, YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1]], &mut [DateTime::default(), DateTime::default()]);
+}
+
 #[test]
 fn test_invalid_dates() {
     let c = Calendar::create();
@@ -46,7 +111,8 @@ fn test_next_occurrence_day_to_ms() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Minute, 2)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some(4*60*60*1000), next_occurrence);
 
@@ -55,7 +121,8 @@ fn test_next_occurrence_day_to_ms() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Minute, 5)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some(3*60*1000-1), next_occurrence);
 
@@ -64,7 +131,8 @@ fn test_next_occurrence_day_to_ms() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Hour, 3)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some(2*60*60*1000+58*60*1000), next_occurrence);
 
@@ -72,7 +140,8 @@ fn test_next_occurrence_day_to_ms() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Day, 2)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((24+24-1)*60*60*1000), next_occurrence);
 
@@ -80,7 +149,8 @@ fn test_next_occurrence_day_to_ms() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Second, 10)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some(9000), next_occurrence);
 
@@ -88,7 +158,8 @@ fn test_next_occurrence_day_to_ms() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Ms, 100)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some(90), next_occurrence);
 }
@@ -104,7 +175,8 @@ fn test_with_schedule_end() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Minute, 2)],
-        end: Some(end.clone())
+        end: Some(end.clone()),
+        ..Default::default()
     });
     assert_eq!(None, next_occurrence);
 }
@@ -118,28 +190,32 @@ fn test_next_occurrence_months() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Month, 1)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((1+25)*24*60*60*1000), next_occurrence);
 
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Month, 2)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((1+28+25)*24*60*60*1000), next_occurrence);
 
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Month, 3)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((1+28+31+25)*24*60*60*1000), next_occurrence);
 
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Month, 36)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((365+366+365-5)*24*60*60*1000), next_occurrence);
 }
@@ -153,21 +229,24 @@ fn test_next_occurrence_years() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Year, 1)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((365-5)*24*60*60*1000), next_occurrence);
 
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Year, 2)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((365+365-5)*24*60*60*1000), next_occurrence);
 
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Year, 3)],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some((365+365+366-5)*24*60*60*1000), next_occurrence);
 }
@@ -235,14 +314,16 @@ fn test_no_schedules_for_now() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Second, 3)],
-        end: Some(start_plus_2s.clone())
+        end: Some(start_plus_2s.clone()),
+        ..Default::default()
     });
     assert_eq!(None, next_occurrence);
 
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start.clone(),
         items: vec![(Frequency::Second, 3)],
-        end: Some(start_plus_3s)
+        end: Some(start_plus_3s),
+        ..Default::default()
     });
     assert_eq!(Some(3000), next_occurrence);
 
@@ -250,7 +331,8 @@ fn test_no_schedules_for_now() {
     let next_occurrence = c.next_occurrence_ms(&now, &Schedule {
         start: start_plus_1s.clone(),
         items: vec![(Frequency::Second, 3)],
-        end: Some(start_plus_2s)
+        end: Some(start_plus_2s),
+        ..Default::default()
     });
     assert_eq!(Some(1000), next_occurrence);
 }
@@ -260,11 +342,11 @@ fn test_schedule_valid() {
     let c = Calendar::create();
     let t1 = DateTime { year: 2022, month: 1, day: 25, hour: 5, minute: 3, second: 30, ms: 0 };
     let t2 = DateTime { second: t1.second + 1, ..t1 };
-    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: t1.clone(), items: vec![], end: None}));
-    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: t1.clone(), items: vec![], end: Some(t2.clone())}));
-    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: t2.clone(), items: vec![], end: Some(t1.clone())}));
-    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: t1.clone(), items: vec![(Frequency::Hour, 1)], end: None}));
-    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: t1.clone(), items: vec![(Frequency::Hour, 0)], end: None}));
+    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: t1.clone(), items: vec![], end: None, ..Default::default() }));
+    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: t1.clone(), items: vec![], end: Some(t2.clone()), ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: t2.clone(), items: vec![], end: Some(t1.clone()), ..Default::default() }));
+    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: t1.clone(), items: vec![(Frequency::Hour, 1)], end: None, ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: t1.clone(), items: vec![(Frequency::Hour, 0)], end: None, ..Default::default() }));
 }
 
 #[test]
@@ -276,7 +358,8 @@ fn test_next_occurrence_ms_with_past_triggers() {
     let schedule = Schedule {
         start: start.clone(),
         items: vec![(Frequency::Hour, 3)],
-        end: Some(end.clone())
+        end: Some(end.clone()),
+        ..Default::default()
     };
 
     let (triggers, next_occurrence) = c.next_occurrence_ms_with_past_triggers(None, &now.clone(), &schedule);
@@ -322,11 +405,837 @@ fn test_earliest_schedule_selected() {
             (Frequency::Minute, 3),
             (Frequency::Ms, 5000),
         ],
-        end: None
+        end: None,
+        ..Default::default()
     });
     assert_eq!(Some(2000), next_occurrence);
 }
 
+#[test]
+fn test_next_occurrence_times_of_day() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 1, day: 25, hour: 0, minute: 0, second: 0, ms: 0 };
+    let schedule = Schedule {
+        start: start.clone(),
+        items: vec![(Frequency::Day, 1)],
+        end: None,
+        times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }, TimeOfDay { hour: 18, minute: 0 }, TimeOfDay { hour: 13, minute: 30 }],
+        ..Default::default()
+    };
+
+    // before start, first expansion of the start day
+    let now = DateTime { day: 24, hour: 12, ..start };
+    assert_eq!(Some(21*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+
+    // within the day
+    let now = DateTime { hour: 9, ..start };
+    assert_eq!(Some(4*60*60*1000+30*60*1000), c.next_occurrence_ms(&now, &schedule));
+    let now = DateTime { hour: 13, minute: 30, ..start };
+    assert_eq!(Some(4*60*60*1000+30*60*1000), c.next_occurrence_ms(&now, &schedule));
+
+    // after last expansion of the day, rolls over to the next period
+    let now = DateTime { hour: 18, ..start };
+    assert_eq!(Some(15*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+
+    // every 2 days, skipping the day in between
+    let schedule = Schedule { items: vec![(Frequency::Day, 2)], ..schedule };
+    assert_eq!(Some((24+15)*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+
+    // expansions before the start time of the start day are ignored
+    let schedule = Schedule { start: DateTime { hour: 12, ..start }, items: vec![(Frequency::Week, 1)], ..schedule };
+    let now = DateTime { hour: 8, ..start };
+    assert_eq!(Some((5*60+30)*60*1000), c.next_occurrence_ms(&now, &schedule));
+    let now = DateTime { hour: 19, ..start };
+    assert_eq!(Some((6*24+14)*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+}
+
+#[test]
+fn test_next_occurrence_days_of_month() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 1, day: 1, hour: 8, minute: 0, second: 0, ms: 0 };
+    let schedule = Schedule {
+        start: start.clone(),
+        items: vec![(Frequency::Month, 1)],
+        end: None,
+        days_of_month: vec![15, 30],
+        ..Default::default()
+    };
+
+    let now = DateTime { day: 15, ..start };
+    assert_eq!(Some(15*24*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+
+    // no 30th in February
+    let now = DateTime { day: 30, ..start };
+    assert_eq!(Some(16*24*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+    let now = DateTime { month: 2, day: 15, ..start };
+    assert_eq!(Some(28*24*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+
+    // combined with times of day
+    let schedule = Schedule { times_of_day: vec![TimeOfDay { hour: 6, minute: 0 }, TimeOfDay { hour: 20, minute: 0 }], ..schedule };
+    let now = DateTime { day: 15, hour: 6, ..start };
+    assert_eq!(Some(14*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+    let now = DateTime { day: 15, hour: 20, ..start };
+    assert_eq!(Some((14*24+10)*60*60*1000), c.next_occurrence_ms(&now, &schedule));
+
+    // leap day only, every year
+    let schedule = Schedule { items: vec![(Frequency::Year, 1)], start: DateTime { month: 2, ..start }, days_of_month: vec![29], times_of_day: vec![], ..schedule };
+    let now = DateTime { year: 2022, month: 3, ..start };
+    assert_eq!(c.to_unixtime(&DateTime { year: 2024, month: 2, day: 29, ..start }) - c.to_unixtime(&now), c.next_occurrence_ms(&now, &schedule).unwrap());
+
+    // end cuts off the expansion
+    let schedule = Schedule { end: Some(DateTime { year: 2023, ..start }), ..schedule };
+    assert_eq!(None, c.next_occurrence_ms(&now, &schedule));
+}
+
+#[test]
+fn test_schedule_expansions_valid() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 1, day: 25, hour: 5, minute: 3, second: 30, ms: 0 };
+    let at_9 = TimeOfDay { hour: 9, minute: 0 };
+    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Day, 1)], times_of_day: vec![at_9], ..Default::default() }));
+    assert_eq!(Ok(()),                        c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Month, 1)], times_of_day: vec![at_9], days_of_month: vec![1, 31], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Hour, 1)], times_of_day: vec![at_9], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Week, 1)], days_of_month: vec![1], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Day, 1)], times_of_day: vec![TimeOfDay { hour: 24, minute: 0 }], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Day, 1)], times_of_day: vec![TimeOfDay { hour: 0, minute: 60 }], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Month, 1)], days_of_month: vec![0], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Month, 1)], days_of_month: vec![32], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Day, 1)], times_of_day: vec![at_9; MAX_TIMES_OF_DAY + 1], ..Default::default() }));
+    assert_eq!(Err(ValidationError::Invalid), c.validate_schedule(&Schedule { start: start.clone(), items: vec![(Frequency::Month, 1)], days_of_month: vec![1; MAX_DAYS_OF_MONTH + 1], ..Default::default() }));
+}
+
+#[test]
+fn test_schedule_describe() {
+    let start = DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 };
+    let end = DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 };
+
+    assert_eq!("every 2 weeks and every 3 days starting 2022-04-30 00:00:00, until 2025-04-30",
+        Schedule { start: start.clone(), items: vec![(Frequency::Week, 2), (Frequency::Day, 3)], end: Some(end), ..Default::default() }.describe().to_string());
+    assert_eq!("every year, every 6 months and every 1000 milliseconds starting 2022-04-30 10:20:30.040, until 2025-04-30 10:20:00",
+        Schedule {
+            start: DateTime { hour: 10, minute: 20, second: 30, ms: 40, ..start.clone() },
+            items: vec![(Frequency::Year, 1), (Frequency::Month, 6), (Frequency::Ms, 1000)],
+            end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 10, minute: 20, second: 0, ms: 0 }),
+            ..Default::default()
+        }.describe().to_string());
+    assert_eq!("every day at 09:00 starting 2022-04-30 00:00:00",
+        Schedule { start: start.clone(), items: vec![(Frequency::Day, 1)], times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }], ..Default::default() }.describe().to_string());
+    assert_eq!("every 3 months on day 31 starting 2022-04-30 00:00:00",
+        Schedule { start: start.clone(), items: vec![(Frequency::Month, 3)], days_of_month: vec![31], ..Default::default() }.describe().to_string());
+    assert_eq!("once at 2022-04-30 00:00:00", Schedule { start, ..Default::default() }.describe().to_string());
+}
+
+#[test]
+fn test_schedule_dsl() {
+    let start = DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 };
+    let schedule = Schedule {
+        start: start.clone(),
+        items: vec![(Frequency::Week, 2), (Frequency::Day, 3)],
+        end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 }),
+        ..Default::default()
+    };
+    assert_eq!(Ok(schedule.clone()), "start=2022-04-30T00:00;every=2w,3d;until=2025-04-30".parse());
+    assert_eq!(Ok(schedule.clone()), "until=2025-04-30T00:00:00.000;every=2w,3d;start=2022-04-30".parse());
+    assert_eq!("start=2022-04-30T00:00;every=2w,3d;until=2025-04-30", schedule.to_string());
+
+    let schedule = Schedule {
+        start: DateTime { hour: 10, minute: 20, second: 30, ms: 40, ..start.clone() },
+        items: vec![(Frequency::Year, 1), (Frequency::Month, 6)],
+        end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 10, minute: 20, second: 30, ms: 0 }),
+        times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }, TimeOfDay { hour: 18, minute: 30 }],
+        days_of_month: vec![1, 15],
+    };
+    let dsl = "start=2022-04-30T10:20:30.040;every=1y,6mo;on=1,15;at=09:00,18:30;until=2025-04-30T10:20:30";
+    assert_eq!(Ok(schedule.clone()), dsl.parse());
+    assert_eq!(dsl, schedule.to_string());
+
+    let units = "start=2022-04-30T00:00;every=1y,2mo,3w,4d,5h,6m,7s,8ms";
+    assert_eq!(units, units.parse::<Schedule>().unwrap().to_string());
+    assert_eq!(Ok(Schedule { start, ..Default::default() }), "start=2022-04-30".parse());
+
+    let error = |position, kind| Err::<Schedule, _>(ParseError { position, kind });
+    assert_eq!(error(0, ParseErrorKind::TooShort),    "".parse());
+    assert_eq!(error(5, ParseErrorKind::TooShort),    "start".parse());
+    assert_eq!(error(12, ParseErrorKind::TooShort),   "every=2w,3d;".parse());
+    assert_eq!(error(11, ParseErrorKind::TooShort),   "every=2w,3d".parse());  // start required
+    assert_eq!(error(17, ParseErrorKind::Unexpected), "start=2022-04-30;evry=2w".parse());
+    assert_eq!(error(17, ParseErrorKind::Unexpected), "start=2022-04-30;start=2022-04-30".parse());
+    assert_eq!(error(10, ParseErrorKind::Unexpected), "start=2022/04/30".parse());
+    assert_eq!(error(11, ParseErrorKind::OutOfRange), "start=2022-13-30".parse());
+    assert_eq!(error(14, ParseErrorKind::OutOfRange), "start=2022-02-30".parse());
+    assert_eq!(error(24, ParseErrorKind::Unexpected), "start=2022-04-30;every=2x".parse());
+    assert_eq!(error(24, ParseErrorKind::TooShort),   "start=2022-04-30;every=2".parse());
+    assert_eq!(error(23, ParseErrorKind::OutOfRange), "start=2022-04-30;every=0d".parse());
+    assert_eq!(error(23, ParseErrorKind::OutOfRange), "start=2022-04-30;every=99999999999d".parse());
+    assert_eq!(error(26, ParseErrorKind::TooShort),   "start=2022-04-30;every=2d,".parse());
+    assert_eq!(error(20, ParseErrorKind::OutOfRange), "start=2022-04-30;on=32".parse());
+    assert_eq!(error(23, ParseErrorKind::OutOfRange), "start=2022-04-30;at=09:60".parse());
+    assert_eq!(error(22, ParseErrorKind::TooShort),   "start=2022-04-30;at=09".parse());
+    assert_eq!(error(23, ParseErrorKind::OutOfRange), "start=2022-04-30;until=2021-04-30".parse());
+    assert_eq!(error(29, ParseErrorKind::OutOfRange), "start=2022-04-30;every=1h;at=09:00".parse());
+    assert_eq!(error(29, ParseErrorKind::OutOfRange), "start=2022-04-30;every=1d;on=1".parse());
+    assert_eq!(error(20, ParseErrorKind::OutOfRange), "start=2022-04-30;on=1,2;at=09:00;every=1d".parse());
+    let days: Vec<_> = (1..=31).chain([1]).map(|day| day.to_string()).collect();
+    assert_eq!(error(104, ParseErrorKind::OutOfRange), (String::from("start=2022-04-30;on=") + &days.join(",")).parse());
+    let times: Vec<_> = (0..=24).map(|hour| TimeOfDay { hour: hour % 24, minute: 0 }.to_string()).collect();
+    assert_eq!(error(164, ParseErrorKind::OutOfRange), (String::from("start=2022-04-30;at=") + &times.join(",")).parse());
+    assert_eq!("unexpected input at position 24", "start=2022-04-30;every=2x".parse::<Schedule>().unwrap_err().to_string());
+}
+
+#[test]
+fn test_iso8601_duration() {
+    let duration = IsoDuration { years: 1, months: 2, weeks: 3, days: 4, hours: 5, minutes: 6, seconds: 7, ms: 890 };
+    assert_eq!(Ok(duration), "P1Y2M3W4DT5H6M7.89S".parse());
+    assert_eq!(Ok(duration), "P1Y2M3W4DT5H6M7,890S".parse());
+    assert_eq!("P1Y2M3W4DT5H6M7.89S", duration.to_string());
+    for iso in ["PT0S", "P1Y", "P1M", "P2W", "P1DT12H", "PT15M", "PT0.5S", "PT1.05S", "PT0.001S", "P4294967295D"] {
+        assert_eq!(iso, iso.parse::<IsoDuration>().unwrap().to_string());
+    }
+    assert_eq!("PT1M1.5S", IsoDuration { minutes: 1, ms: 1500, ..Default::default() }.to_string());  // ms carried over to seconds
+
+    let error = |position, kind| Err::<IsoDuration, _>(ParseError { position, kind });
+    assert_eq!(error(0, ParseErrorKind::TooShort),    "".parse());
+    assert_eq!(error(0, ParseErrorKind::Unexpected),  "1D".parse());
+    assert_eq!(error(1, ParseErrorKind::TooShort),    "P".parse());
+    assert_eq!(error(2, ParseErrorKind::TooShort),    "PT".parse());
+    assert_eq!(error(4, ParseErrorKind::TooShort),    "P1DT".parse());
+    assert_eq!(error(2, ParseErrorKind::TooShort),    "P1".parse());
+    assert_eq!(error(2, ParseErrorKind::Unexpected),  "P1H".parse());
+    assert_eq!(error(4, ParseErrorKind::Unexpected),  "P1D1Y".parse());  // out of order
+    assert_eq!(error(4, ParseErrorKind::Unexpected),  "P1D1D".parse());
+    assert_eq!(error(4, ParseErrorKind::Unexpected),  "PT1HT1M".parse());
+    assert_eq!(error(1, ParseErrorKind::Unexpected),  "PxD".parse());
+    assert_eq!(error(1, ParseErrorKind::OutOfRange),  "P4294967296D".parse());
+    assert_eq!(error(2, ParseErrorKind::Unsupported), "P1.5D".parse());
+    assert_eq!(error(7, ParseErrorKind::Unsupported), "PT1.0001S".parse());
+    assert_eq!(error(4, ParseErrorKind::Unexpected),  "PT1.S".parse());
+}
+
+#[test]
+fn test_add_duration() {
+    let c = Calendar::create();
+    let dt = |year, month, day, hour| DateTime { year, month, day, hour, minute: 0, second: 0, ms: 0 };
+    for (from, iso, expected) in [
+        (dt(2022, 1, 31, 10),  "P1M",       Some(dt(2022, 2, 28, 10))),  // clamped to last day of month
+        (dt(2024, 1, 31, 10),  "P1M",       Some(dt(2024, 2, 29, 10))),
+        (dt(2024, 2, 29, 10),  "P1Y",       Some(dt(2025, 2, 28, 10))),
+        (dt(2022, 1, 31, 10),  "P1M1D",     Some(dt(2022, 3, 1, 10))),   // months first, then days
+        (dt(2022, 11, 30, 10), "P1Y2M",     Some(dt(2024, 1, 30, 10))),
+        (dt(2022, 12, 31, 10), "P1W",       Some(dt(2023, 1, 7, 10))),
+        (dt(2022, 12, 31, 10), "PT14H",     Some(dt(2023, 1, 1, 0))),
+        (dt(2022, 12, 31, 10), "PT0S",      Some(dt(2022, 12, 31, 10))),
+        (dt(3999, 12, 31, 10), "PT14H",     None),  // out of scope
+        (dt(3999, 12, 31, 10), "P1M",       None),
+        (dt(2022, 1, 1, 0),    "P4294967295Y", None),
+        (dt(2022, 2, 29, 0),   "P1D",       None),  // invalid
+    ] {
+        assert_eq!(expected, c.add_duration(&from, &iso.parse().unwrap()), "{} + {}", from, iso);
+    }
+}
+
+#[test]
+fn test_repeating_interval() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 1, day: 1, hour: 0, minute: 0, second: 0, ms: 0 };
+    let ri = RepeatingInterval { repetitions: Some(5), start: start.clone(), duration: IsoDuration { months: 1, ..Default::default() } };
+    assert_eq!(Ok(ri.clone()), "R5/2022-01-01T00:00:00Z/P1M".parse());
+    assert_eq!(Ok(ri.clone()), "R5/2022-01-01/P1M".parse());
+    assert_eq!(Ok(ri.clone()), "R5/2022-01-01T05:30+05:30/P1M".parse());
+    assert_eq!(Ok(ri.clone()), "R5/2021-12-31T19:00-0500/P1M".parse());
+    assert_eq!(Ok(ri.clone()), "R5/2022-01-01T01:00:00.000+01/P1M".parse());
+    assert_eq!("R5/2022-01-01T00:00:00Z/P1M", ri.to_string());
+    let ri = RepeatingInterval { repetitions: None, start: DateTime { hour: 9, ms: 5, ..start.clone() }, duration: IsoDuration { minutes: 15, ..Default::default() } };
+    assert_eq!(Ok(ri.clone()), "R/2022-01-01T09:00:00.005Z/PT15M".parse());
+    assert_eq!("R/2022-01-01T09:00:00.005Z/PT15M", ri.to_string());
+
+    let error = |position, kind| Err::<RepeatingInterval, _>(ParseError { position, kind });
+    assert_eq!(error(0, ParseErrorKind::TooShort),    "".parse());
+    assert_eq!(error(2, ParseErrorKind::TooShort),    "R5".parse());
+    assert_eq!(error(13, ParseErrorKind::TooShort),   "R5/2022-01-01".parse());
+    assert_eq!(error(0, ParseErrorKind::Unexpected),  "5/2022-01-01/P1M".parse());
+    assert_eq!(error(1, ParseErrorKind::Unexpected),  "Rx/2022-01-01/P1M".parse());
+    assert_eq!(error(3, ParseErrorKind::Unsupported), "R5/P1M/2022-01-01".parse());
+    assert_eq!(error(14, ParseErrorKind::Unsupported), "R5/2022-01-01/2022-02-01".parse());
+    assert_eq!(error(14, ParseErrorKind::TooShort),   "R5/2022-01-01/".parse());
+    assert_eq!(error(15, ParseErrorKind::Unexpected), "R5/2022-01-01/PxM".parse());
+    assert_eq!(error(11, ParseErrorKind::OutOfRange), "R5/2022-02-30/P1M".parse());
+    assert_eq!(error(19, ParseErrorKind::Unexpected), "R5/2022-01-01T00:00X/P1M".parse());
+    assert_eq!(error(20, ParseErrorKind::OutOfRange), "R5/2022-01-01T00:00+19/P1M".parse());
+    assert_eq!(error(23, ParseErrorKind::OutOfRange), "R5/2022-01-01T00:00+01:60/P1M".parse());
+    assert_eq!(error(21, ParseErrorKind::TooShort),   "R5/2022-01-01T00:00+1/P1M".parse());
+    assert_eq!(error(19, ParseErrorKind::OutOfRange), "R5/1970-01-01T00:00+01/P1M".parse());  // before epoch in UTC
+
+    // to schedule, with end at the last repetition
+    let schedule = |items, end| Schedule { start: start.clone(), items, end, ..Default::default() };
+    for (iso, expected) in [
+        ("R5/2022-01-01/P1M",      Ok(schedule(vec![(Frequency::Month, 1)], Some(DateTime { month: 5, ..start.clone() })))),
+        ("R/2022-01-01/P1Y2M",     Ok(schedule(vec![(Frequency::Month, 14)], None))),
+        ("R3/2022-01-01/P2Y",      Ok(schedule(vec![(Frequency::Year, 2)], Some(DateTime { year: 2026, ..start.clone() })))),
+        ("R1/2022-01-01/P2W",      Ok(schedule(vec![(Frequency::Week, 2)], Some(start.clone())))),
+        ("R3/2022-01-01/P1DT12H",  Ok(schedule(vec![(Frequency::Hour, 36)], Some(DateTime { day: 4, ..start.clone() })))),
+        ("R/2022-01-01/PT1.5S",    Ok(schedule(vec![(Frequency::Ms, 1500)], None))),
+        ("R/2022-01-01/P7D",       Ok(schedule(vec![(Frequency::Week, 1)], None))),
+        ("R3/2022-01-28/P1M",      Ok(Schedule { start: DateTime { day: 28, ..start.clone() }, ..schedule(vec![(Frequency::Month, 1)], Some(DateTime { month: 3, day: 28, ..start.clone() })) })),
+        ("R3/2022-01-31/P1M",      Err(ValidationError::Invalid)),  // repetitions clamped to 28/02, schedule rolling over to 03/03
+        ("R3/2020-02-29/P1Y",      Err(ValidationError::Invalid)),
+        ("R/2022-01-01/P1M1D",     Err(ValidationError::Invalid)),  // mixing calendar and fixed components
+        ("R/2022-01-01/PT0S",      Err(ValidationError::Invalid)),
+        ("R0/2022-01-01/P1D",      Err(ValidationError::Invalid)),
+        ("R/2022-01-01/PT4294967.5S", Err(ValidationError::Invalid)),  // 4294967500 ms exceed multiplier
+        ("R10000/2022-01-01/P1Y",  Err(ValidationError::OutOfScope)),
+    ] {
+        assert_eq!(expected, c.schedule_from_repeating_interval(&iso.parse().unwrap()), "{}", iso);
+    }
+
+    // from schedule, with repetitions until end
+    let month_end = DateTime { day: 31, ..start.clone() };
+    for (schedule, expected) in [
+        (schedule(vec![(Frequency::Month, 1)], Some(DateTime { month: 5, ..start.clone() })), Some("R5/2022-01-01T00:00:00Z/P1M")),
+        (schedule(vec![(Frequency::Month, 1)], Some(DateTime { month: 5, day: 31, ..start.clone() })), Some("R5/2022-01-01T00:00:00Z/P1M")),
+        (Schedule { start: DateTime { day: 28, ..start.clone() }, items: vec![(Frequency::Month, 1)], end: Some(DateTime { month: 2, day: 28, ..start.clone() }), ..Default::default() }, Some("R2/2022-01-28T00:00:00Z/P1M")),
+        (Schedule { start: DateTime { day: 28, ..start.clone() }, items: vec![(Frequency::Month, 1)], end: Some(DateTime { month: 2, day: 27, ..start.clone() }), ..Default::default() }, Some("R1/2022-01-28T00:00:00Z/P1M")),
+        (Schedule { start: month_end.clone(), items: vec![(Frequency::Month, 1)], end: None, ..Default::default() }, None),  // rolling over, unlike repetitions
+        (Schedule { start: month_end.clone(), items: vec![(Frequency::Day, 1)], end: None, ..Default::default() }, Some("R/2022-01-31T00:00:00Z/P1D")),
+        (schedule(vec![(Frequency::Year, 2)], Some(DateTime { year: 2025, month: 12, ..start.clone() })), Some("R2/2022-01-01T00:00:00Z/P2Y")),
+        (schedule(vec![(Frequency::Hour, 36)], Some(DateTime { day: 4, ..start.clone() })), Some("R3/2022-01-01T00:00:00Z/PT36H")),
+        (schedule(vec![(Frequency::Ms, 1)], None), Some("R/2022-01-01T00:00:00Z/PT0.001S")),
+        (schedule(vec![(Frequency::Ms, 1)], Some(DateTime { year: 2023, ..start.clone() })), None),  // too many repetitions
+        (schedule(vec![(Frequency::Day, 1), (Frequency::Hour, 1)], None), None),
+        (schedule(vec![], None), None),
+        (Schedule { times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }], ..schedule(vec![(Frequency::Day, 1)], None) }, None),
+    ] {
+        assert_eq!(expected.map(|iso| iso.parse().unwrap()), c.repeating_interval_from_schedule(&schedule), "{:?}", schedule);
+    }
+}
+
+#[test]
+fn test_block_clock() {
+    let clock = BlockClock { anchor_block: 100, anchor_ms: 60_000, block_time_ms: 6_000 };
+    for (ms, up, down, nearest) in [
+        (60_000, 100, 100, 100),
+        (60_001, 101, 100, 100),
+        (62_999, 101, 100, 100),
+        (63_000, 101, 100, 101),  // ties round up
+        (65_999, 101, 100, 101),
+        (59_999, 100, 99, 100),   // prior to anchor
+        (53_000, 99, 98, 99),
+        (0, 90, 90, 90),
+    ] {
+        assert_eq!((Some(up), Some(down), Some(nearest)), (clock.ms_to_block(ms, BlockRounding::Up), clock.ms_to_block(ms, BlockRounding::Down), clock.ms_to_block(ms, BlockRounding::Nearest)), "ms: {}", ms);
+    }
+    assert_eq!(Some(60_000), clock.block_to_ms(100));
+    assert_eq!(Some(66_000), clock.block_to_ms(101));
+    assert_eq!(Some(0), clock.block_to_ms(90));
+    assert_eq!(None, clock.block_to_ms(89));  // before epoch
+    assert_eq!(None, BlockClock { anchor_block: 1, ..clock }.ms_to_block(0, BlockRounding::Down));  // before block 0
+    assert_eq!(None, BlockClock { block_time_ms: 0, ..clock }.ms_to_block(0, BlockRounding::Down));
+
+    // drift corrections, with early and late blocks
+    let mut clock = clock;
+    assert_eq!(-1_000, clock.reanchor(110, 119_000));
+    assert_eq!(BlockClock { anchor_block: 110, anchor_ms: 119_000, block_time_ms: 6_000 }, clock);
+    assert_eq!(2_500, clock.reanchor(111, 127_500));
+    assert_eq!(Some(133_500), clock.block_to_ms(112));
+    assert_eq!(Some(111), clock.ms_to_block(127_500, BlockRounding::Up));
+}
+
+#[test]
+fn test_next_occurrence_block() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+    let start_ms = c.to_unixtime(&start);
+    let clock = BlockClock { anchor_block: 1_000, anchor_ms: start_ms, block_time_ms: 12_000 };
+    let schedule = Schedule { start: start.clone(), items: vec![(Frequency::Second, 50)], end: Some(DateTime { minute: 10, ..start.clone() }), ..Default::default() };
+
+    // occurrences at 50s, 100s, 150s, ..., ie. 4.17, 8.33, 12.5, ... blocks
+    for (now_block, up, down, nearest) in [
+        (1_000, 1_005, 1_004, 1_004),
+        (1_004, 1_005, 1_005, 1_005),  // rounding to now rounds up instead
+        (1_005, 1_009, 1_008, 1_008),
+        (1_009, 1_013, 1_012, 1_013),
+    ] {
+        assert_eq!(
+            (Some(up), Some(down), Some(nearest)),
+            (c.next_occurrence_block(&clock, now_block, BlockRounding::Up, &schedule), c.next_occurrence_block(&clock, now_block, BlockRounding::Down, &schedule), c.next_occurrence_block(&clock, now_block, BlockRounding::Nearest, &schedule)),
+            "now_block: {}", now_block);
+    }
+    // prior to start, and after end
+    assert_eq!(Some(1_000), c.next_occurrence_block(&clock, 990, BlockRounding::Up, &schedule));
+    assert_eq!(Some(1_050), c.next_occurrence_block(&clock, 1_049, BlockRounding::Up, &schedule));
+    assert_eq!(None, c.next_occurrence_block(&clock, 1_050, BlockRounding::Up, &schedule));
+    assert_eq!(None, c.next_occurrence_block(&BlockClock { anchor_ms: u64::MAX, ..clock }, 1_000, BlockRounding::Up, &schedule));
+}
+
+#[test]
+fn test_cost_bound() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 };
+    let fixed = Schedule { start: start.clone(), items: vec![(Frequency::Day, 1)], ..Default::default() };
+    let next_occurrence = 4 * TO_UNIXTIME_COST + 1;
+    assert_eq!(next_occurrence + FROM_UNIXTIME_COST + 2 * TO_UNIXTIME_COST, c.cost_bound(&fixed, 0));
+    assert_eq!(11 * (next_occurrence + FROM_UNIXTIME_COST) + 2 * TO_UNIXTIME_COST, c.cost_bound(&fixed, 10));
+    assert_eq!(c.cost_bound(&fixed, 10), c.cost_bound(&Schedule { items: vec![(Frequency::Day, 7)], ..fixed.clone() }, 10));  // multipliers don't matter
+
+    // expansions grow with their sizes
+    let times_of_day = vec![TimeOfDay { hour: 9, minute: 0 }, TimeOfDay { hour: 18, minute: 30 }];
+    let daily = Schedule { times_of_day: times_of_day.clone(), ..fixed.clone() };
+    assert_eq!(next_occurrence - 1 + TO_UNIXTIME_COST + 4 + FROM_UNIXTIME_COST + 2 * TO_UNIXTIME_COST, c.cost_bound(&daily, 0));
+    let monthly = Schedule { items: vec![(Frequency::Month, 1)], days_of_month: vec![1, 15], times_of_day, ..fixed.clone() };
+    let period = 2 * (DAYS_IN_MONTH_COST + TO_UNIXTIME_COST + 2);
+    assert_eq!(next_occurrence - 1 + TO_UNIXTIME_COST + FROM_UNIXTIME_COST + MAX_EXPANDED_PERIODS as u64 * period + FROM_UNIXTIME_COST + 2 * TO_UNIXTIME_COST, c.cost_bound(&monthly, 0));
+    let worst = Schedule {
+        days_of_month: (1..=31).collect(),
+        times_of_day: (0..24).map(|hour| TimeOfDay { hour, minute: 0 }).collect(),
+        ..monthly.clone()
+    };
+    assert!(c.cost_bound(&worst, 0) > c.cost_bound(&monthly, 0));
+    assert_eq!(u64::MAX, c.cost_bound(&Schedule { items: vec![(Frequency::Month, 1); 1_000_000], ..worst.clone() }, u32::MAX));  // saturating
+
+    // budget, inclusive
+    let budget = c.cost_bound(&monthly, 5);
+    assert_eq!(Ok(()), c.validate_schedule(&worst));
+    assert_eq!(Ok(()), Calendar::create().with_cost_budget(5, budget).validate_schedule(&monthly));
+    assert_eq!(Err(ValidationError::OverBudget), Calendar::create().with_cost_budget(6, budget).validate_schedule(&monthly));
+    assert_eq!(Err(ValidationError::OverBudget), Calendar::create().with_cost_budget(5, budget).validate_schedule(&worst));
+    assert_eq!(Err(ValidationError::Invalid), Calendar::create().with_cost_budget(5, budget).validate_schedule(&Schedule { items: vec![(Frequency::Day, 0)], ..fixed.clone() }));
+
+    // catch up capped at the limit of the budget, remaining triggers being due, with uncapped catch up regardless of the budget
+    let now = DateTime { day: 10, month: 5, hour: 12, ..start.clone() };
+    let (all, next_occurrence) = c.next_occurrence_ms_with_past_triggers(Some(&start), &now, &fixed);
+    assert_eq!((10, Some(12 * MS_IN_HOUR)), (all.len(), next_occurrence));
+    let budgeted = Calendar::create().with_cost_budget(4, c.cost_bound(&fixed, 4));
+    assert_eq!((all.clone(), next_occurrence), budgeted.next_occurrence_ms_with_past_triggers(Some(&start), &now, &fixed));
+    assert_eq!((all[..4].to_vec(), Some(0)), budgeted.next_occurrence_ms_with_past_triggers_capped(Some(&start), &now, &fixed, 4));
+    assert_eq!((all[4..8].to_vec(), Some(0)), c.next_occurrence_ms_with_past_triggers_capped(Some(&c.from_unixtime(all[3])), &now, &fixed, 4));
+    assert_eq!((all[8..].to_vec(), next_occurrence), c.next_occurrence_ms_with_past_triggers_capped(Some(&c.from_unixtime(all[7])), &now, &fixed, 4));
+    assert_eq!((all.clone(), next_occurrence), c.next_occurrence_ms_with_past_triggers_capped(Some(&start), &now, &fixed, 10));
+    assert_eq!((vec![], Some(0)), c.next_occurrence_ms_with_past_triggers_capped(Some(&start), &now, &fixed, 0));
+}
+
+#[test]
+fn test_clock() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+    let now_ms = c.to_unixtime(&start);
+    let schedule = Schedule { start: start.clone(), items: vec![(Frequency::Minute, 15)], ..Default::default() };
+    let clock = ManualClock::new(now_ms);
+    assert_eq!(start, c.now(&clock));
+    assert_eq!(Some(900_000), c.next_occurrence_from_clock(&schedule, &clock));
+    clock.advance(1_000_000);
+    assert_eq!(DateTime { minute: 16, second: 40, ..start.clone() }, c.now(&&clock));
+    assert_eq!(Some(800_000), c.next_occurrence_from_clock(&schedule, &clock));
+    assert_eq!((vec![now_ms + 900_000], Some(800_000)), c.past_triggers_from_clock(Some(&start), &schedule, &clock));
+    assert_eq!(Ok(start.clone()), c.now_res(&FixedClock(now_ms)));
+
+    // past the scope
+    let end_of_scope = FixedClock(c.to_unixtime(&DateTime { year: 4000, month: 12, day: 31, hour: 23, minute: 59, second: 59, ms: 999 }) + 1);
+    assert_eq!(Err(ValidationError::OutOfScope), c.now_res(&end_of_scope));
+    assert_eq!(None, c.next_occurrence_from_clock(&schedule, &end_of_scope));
+    assert_eq!((vec![], None), c.past_triggers_from_clock(Some(&start), &schedule, &end_of_scope));
+    clock.set(u64::MAX - 1);
+    clock.advance(2);
+    assert_eq!(u64::MAX, clock.now_ms());
+
+    #[cfg(feature = "std")]
+    assert!(c.now_res(&SystemClock).is_ok_and(|now| now.year >= 2022));
+}
+
+#[test]
+fn test_scheduler_queue() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+    let now_ms = c.to_unixtime(&start);
+    let hourly = Schedule { start: start.clone(), items: vec![(Frequency::Hour, 1)], ..Default::default() };
+    let every_90_min = Schedule { start: start.clone(), items: vec![(Frequency::Minute, 90)], ..Default::default() };
+    let mut queue = SchedulerQueue::new();
+    assert!(queue.is_empty());
+    assert_eq!(None, queue.peek());
+    assert_eq!(Ok(None), queue.insert(2, hourly.clone(), now_ms));
+    assert_eq!(Ok(None), queue.insert(1, every_90_min.clone(), now_ms));
+    assert_eq!(Ok(None), queue.insert(3, hourly.clone(), now_ms));
+    assert_eq!((3, Some((now_ms + 3_600_000, &2))), (queue.len(), queue.peek()));
+
+    // same ms ordered by id, catching up on multiple triggers
+    assert!(queue.pop_due(now_ms + 3_599_999).is_empty());
+    assert_eq!(
+        vec![(now_ms + 3_600_000, 2), (now_ms + 3_600_000, 3), (now_ms + 5_400_000, 1), (now_ms + 7_200_000, 2), (now_ms + 7_200_000, 3)],
+        queue.pop_due(now_ms + 7_200_000));
+    assert_eq!((Some(now_ms + 10_800_000), Some(now_ms + 10_800_000)), (queue.next_trigger(&1), queue.next_trigger(&2)));
+    assert_eq!(vec![(now_ms + 10_800_000, 1), (now_ms + 10_800_000, 2), (now_ms + 10_800_000, 3)], queue.pop_due(now_ms + 10_800_000));
+
+    // remove, reschedule and replace
+    assert_eq!(Some(hourly.clone()), queue.remove(&3));
+    assert_eq!(None, queue.remove(&3));
+    assert_eq!(Ok(false), queue.reschedule(&3, hourly.clone(), now_ms));
+    assert_eq!(None, queue.next_trigger(&3));
+    assert_eq!(Ok(true), queue.reschedule(&1, hourly.clone(), now_ms + 10_800_000));
+    assert_eq!(Ok(Some(hourly.clone())), queue.insert(2, every_90_min, now_ms + 10_800_000));
+    assert_eq!(vec![(now_ms + 14_400_000, 1), (now_ms + 16_200_000, 2)], queue.pop_due(now_ms + 16_200_000));
+
+    // jobs without further triggers are dropped
+    let ending = Schedule { end: Some(DateTime { hour: 2, ..start.clone() }), ..hourly.clone() };
+    assert_eq!(Ok(None), queue.insert(4, ending.clone(), now_ms));
+    assert_eq!(vec![(now_ms + 3_600_000, 4), (now_ms + 7_200_000, 4)], queue.pop_due(now_ms + 7_200_000));
+    assert_eq!((None, 2), (queue.next_trigger(&4), queue.len()));
+    assert_eq!(Ok(None), queue.insert(4, ending, now_ms + 7_200_000));
+    assert_eq!((None, 2), (queue.next_trigger(&4), queue.len()));
+
+    // invalid schedules, and out of scope
+    assert_eq!(Err(ValidationError::Invalid), queue.insert(5, Schedule { items: vec![(Frequency::Hour, 0)], ..hourly.clone() }, now_ms));
+    assert_eq!(Err(ValidationError::OutOfScope), queue.insert(5, hourly.clone(), u64::MAX));
+    let mut budgeted = SchedulerQueue::with_calendar(Calendar::create().with_cost_budget(0, 0));
+    assert_eq!(Err(ValidationError::OverBudget), budgeted.insert(1, hourly, now_ms));
+    assert!(budgeted.is_empty());
+}
+
+#[test]
+fn test_timing_wheel() {
+    let c = Calendar::create();
+    let start = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+    let now_ms = c.to_unixtime(&start);
+    let mut wheel = TimingWheel::new(now_ms);
+    wheel.reserve(3);
+    assert!(wheel.is_empty());
+    assert!(wheel.advance(now_ms + 1_000).is_empty());
+    let every_ms = wheel.insert(1, Schedule { start: start.clone(), items: vec![(Frequency::Ms, 1)], end: Some(start.clone()), ..Default::default() }).unwrap();
+    let yearly = wheel.insert(2, Schedule { start: start.clone(), items: vec![(Frequency::Year, 1)], ..Default::default() }).unwrap();
+    let monthly = wheel.insert(3, Schedule { start: start.clone(), items: vec![(Frequency::Month, 1)], days_of_month: vec![31], ..Default::default() }).unwrap();
+    assert_eq!((2, None), (wheel.len(), wheel.next_trigger(every_ms)));  // past end
+    assert_eq!(Some(&3), wheel.get(monthly));
+    assert_eq!(Some(c.to_unixtime(&DateTime { month: 5, day: 31, ..start.clone() })), wheel.next_trigger(monthly));
+
+    // triggers cascading through levels, skipping months without 31st
+    let due = wheel.advance(c.to_unixtime(&DateTime { year: 2023, month: 4, day: 20, ..start.clone() }));
+    let triggers: Vec<_> = due.iter().map(|&(trigger_ms, key)| (c.from_unixtime(trigger_ms), *wheel.get(key).unwrap())).collect();
+    assert_eq!(
+        vec![5, 7, 8, 10, 12, 1, 3].into_iter().map(|month| (DateTime { year: 2022 + (month < 5) as u16, month, day: 31, ..start.clone() }, 3))
+            .chain([(DateTime { year: 2023, ..start.clone() }, 2)])
+            .collect::<Vec<_>>(),
+        triggers);
+    assert_eq!(c.to_unixtime(&DateTime { year: 2023, ..start.clone() }), wheel.now_ms());
+    assert_eq!(Some(c.to_unixtime(&DateTime { year: 2024, ..start.clone() })), wheel.next_trigger(yearly));
+
+    // removal, with keys of removed jobs not found
+    assert_eq!(Some((2, Schedule { start: start.clone(), items: vec![(Frequency::Year, 1)], ..Default::default() })), wheel.remove(yearly));
+    assert_eq!(None, wheel.remove(yearly));
+    let every_second = wheel.insert(4, Schedule { start: start.clone(), items: vec![(Frequency::Second, 1)], ..Default::default() }).unwrap();
+    assert_eq!((None, Some(&4)), (wheel.get(yearly), wheel.get(every_second)));
+    let now_ms = wheel.now_ms();
+    assert_eq!(vec![(now_ms + 1_000, every_second), (now_ms + 2_000, every_second)], wheel.advance(now_ms + 2_500));
+    assert_eq!(vec![(now_ms + 3_000, every_second)], wheel.advance(now_ms + 3_000));
+    assert_eq!(2, wheel.len());
+
+    // invalid schedules, and out of scope
+    assert_eq!(Err(ValidationError::Invalid), wheel.insert(5, Schedule { start: start.clone(), items: vec![(Frequency::Hour, 0)], ..Default::default() }));
+    let mut wheel = TimingWheel::new(u64::MAX);
+    assert_eq!(Err(ValidationError::OutOfScope), wheel.insert(5, Schedule { start, items: vec![(Frequency::Hour, 1)], ..Default::default() }));
+}
+
+#[test]
+fn test_scheduler_state() {
+    let c = Calendar::create();
+    let now = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+    let now_ms = c.to_unixtime(&now);
+    let hourly = Schedule { start: now.clone(), items: vec![(Frequency::Hour, 1)], ..Default::default() };
+    let expanded = Schedule {
+        start: now.clone(),
+        items: vec![(Frequency::Day, 3)],
+        end: Some(DateTime { year: 2023, ..now.clone() }),
+        times_of_day: vec![TimeOfDay { hour: 9, minute: 30 }],
+        ..Default::default()
+    };
+    let mut state = SchedulerState::default();
+    assert_eq!(Ok(None), state.insert(7, hourly.clone(), now.clone()));
+    assert_eq!(Ok(None), state.insert(3, expanded.clone(), now.clone()));
+    assert_eq!(Err(ValidationError::Invalid), state.insert(1, Schedule { items: vec![(Frequency::Hour, 0)], ..hourly.clone() }, now.clone()));
+    assert_eq!(Err(ValidationError::Invalid), state.insert(1, hourly.clone(), DateTime { month: 13, ..now.clone() }));
+
+    // triggers until recorded, never recorded twice
+    let later = DateTime { hour: 10, ..now.clone() };
+    let due = state.due(&later);
+    assert_eq!(11, due.len());
+    assert_eq!((now_ms + 3_600_000, 7, 1), due[0]);
+    assert_eq!(vec![(now_ms + 9 * 3_600_000, 7, 9), (now_ms + 9 * 3_600_000 + 1_800_000, 3, 1), (now_ms + 10 * 3_600_000, 7, 10)], due[8..]);
+    assert!(state.record_fire(7, now_ms + 3_600_000));
+    assert!(!state.record_fire(7, now_ms + 3_600_000));
+    assert!(!state.record_fire(1, now_ms + 3_600_000));
+    assert_eq!(due[1..], state.due(&later));
+
+    // pausing skips triggers until resumed
+    assert!(state.pause(7));
+    assert!(!state.pause(1));
+    assert_eq!(vec![(now_ms + 9 * 3_600_000 + 1_800_000, 3, 1)], state.due(&later));
+    assert!(state.resume(7, DateTime { hour: 8, ..now.clone() }));
+    assert!(!state.resume(1, now.clone()));
+    assert_eq!(vec![(now_ms + 9 * 3_600_000, 7, 2), (now_ms + 9 * 3_600_000 + 1_800_000, 3, 1), (now_ms + 10 * 3_600_000, 7, 3)], state.due(&later));
+    assert!(state.pause(3));
+
+    // binary format roundtrip
+    let bytes = state.to_bytes();
+    assert_eq!(&[SCHEDULER_STATE_VERSION, 2, 3], &bytes[..3]);
+    assert_eq!(Ok(state.clone()), SchedulerState::from_bytes(&bytes));
+    assert_eq!(Ok(SchedulerState::default()), SchedulerState::from_bytes(&SchedulerState::default().to_bytes()));
+    #[cfg(feature = "scale")]
+    assert_eq!(Some(state.clone()), <SchedulerState as codec::Decode>::decode(&mut &codec::Encode::encode(&state)[..]).ok());
+    assert_eq!(JobState { schedule: hourly, last_run: DateTime { hour: 8, ..now.clone() }, fire_count: 1, paused: false }, state.jobs()[&7]);
+    assert_eq!(Some(expanded), state.remove(3).map(|job| job.schedule));
+    assert_eq!(None, state.remove(3));
+
+    // malformed input
+    let error = |position, kind| Err(ParseError { position, kind });
+    assert_eq!(error(0, ParseErrorKind::TooShort), SchedulerState::from_bytes(&[]));
+    assert_eq!(error(0, ParseErrorKind::Unsupported), SchedulerState::from_bytes(&[2, 0]));
+    assert_eq!(error(2, ParseErrorKind::Unexpected), SchedulerState::from_bytes(&[1, 0, 0]));
+    assert_eq!(error(bytes.len() - 1, ParseErrorKind::TooShort), SchedulerState::from_bytes(&bytes[..bytes.len() - 1]));
+    assert_eq!(error(1, ParseErrorKind::OutOfRange), SchedulerState::from_bytes(&[1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]));
+    let mut invalid = bytes.clone();
+    *invalid.last_mut().unwrap() = 2;  // paused flag of job 7
+    assert_eq!(error(bytes.len() - 1, ParseErrorKind::Unexpected), SchedulerState::from_bytes(&invalid));
+    let job_7 = &state.to_bytes()[2..];
+    assert_eq!(error(2 + job_7.len(), ParseErrorKind::Unexpected), SchedulerState::from_bytes(&[&[SCHEDULER_STATE_VERSION, 2], job_7, job_7].concat()));
+    let mut invalid = state.to_bytes();
+    invalid[3 + 6 + 2] = 0;  // multiplier of the 1st item, after id and start
+    assert_eq!(error(3, ParseErrorKind::OutOfRange), SchedulerState::from_bytes(&invalid));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_schedule_runner() {
+    use std::sync::mpsc;
+    let start = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+    let now_ms = Calendar::create().to_unixtime(&start);
+    let runner = ScheduleRunner::with_clock(ManualClock::new(now_ms));
+    let (sender, receiver) = mpsc::channel();
+    let register = |id: u8, schedule: Schedule| {
+        let sender = sender.clone();
+        runner.register(schedule, move |trigger_ms| sender.send((trigger_ms, id)).unwrap())
+    };
+    let hourly = register(1, Schedule { start: start.clone(), items: vec![(Frequency::Hour, 1)], ..Default::default() }).unwrap();
+    let every_90_min = register(2, Schedule { start: start.clone(), items: vec![(Frequency::Minute, 90)], ..Default::default() }).unwrap();
+    let ending = register(3, Schedule { start: start.clone(), items: vec![(Frequency::Hour, 1)], end: Some(DateTime { hour: 1, ..start.clone() }), ..Default::default() }).unwrap();
+    assert_eq!(Err(ValidationError::Invalid), register(4, Schedule { start: start.clone(), items: vec![(Frequency::Hour, 0)], ..Default::default() }).map(|_| ()));
+    assert!(!register(4, Schedule { start: start.clone(), items: vec![(Frequency::Hour, 1)], end: Some(start.clone()), ..Default::default() }).unwrap().is_active());
+
+    // catching up in order of triggers, after the clock is advanced
+    runner.clock().advance(3 * 3_600_000);
+    runner.notify();
+    let triggers: Vec<_> = receiver.iter().take(6).collect();
+    assert_eq!(vec![(now_ms + 3_600_000, 1), (now_ms + 3_600_000, 3), (now_ms + 5_400_000, 2), (now_ms + 7_200_000, 1), (now_ms + 10_800_000, 1), (now_ms + 10_800_000, 2)], triggers);
+    assert_eq!((true, true, false), (hourly.is_active(), every_90_min.is_active(), ending.is_active()));
+
+    // cancellation
+    assert!(hourly.cancel());
+    assert!(!hourly.clone().cancel());
+    assert!(!ending.cancel());
+    runner.clock().advance(5_400_000);
+    runner.notify();
+    assert_eq!(Ok((now_ms + 16_200_000, 2)), receiver.recv());
+
+    // panicking closures are dropped, leaving the runner and other closures alive
+    let panicking = runner.register(Schedule { start: start.clone(), items: vec![(Frequency::Minute, 30)], ..Default::default() }, |_| panic!("faulty job")).unwrap();
+    runner.clock().advance(5_400_000);
+    runner.notify();
+    assert_eq!(Ok((now_ms + 21_600_000, 2)), receiver.recv());
+    assert_eq!((false, true), (panicking.is_active(), panicking.has_panicked()));
+    assert!(!every_90_min.has_panicked());
+    assert!(!panicking.cancel());
+
+    // graceful shutdown, waiting for the running closure
+    let (started_sender, started_receiver) = mpsc::channel();
+    let (finish_sender, finish_receiver) = mpsc::channel::<()>();
+    let blocking = runner.register(Schedule { start: start.clone(), items: vec![(Frequency::Minute, 1)], ..Default::default() }, move |trigger_ms| {
+        started_sender.send(trigger_ms).unwrap();
+        finish_receiver.recv().unwrap();
+        started_sender.send(0).unwrap();
+    }).unwrap();
+    runner.clock().advance(60_000);
+    runner.notify();
+    assert_eq!(Ok(now_ms + 21_600_000 + 60_000), started_receiver.recv());
+    finish_sender.send(()).unwrap();
+    runner.shutdown();
+    assert_eq!(Ok(0), started_receiver.try_recv());
+    assert!(blocking.is_active());
+    assert_eq!(None, receiver.try_iter().next());
+}
+
+#[test]
+fn test_offset_roundtrip() {
+    let c = Calendar::create();
+    let ts = 1650863010000;  // 25/04/2022 05:03:30:000 UTC
+    for (offset_minutes, local) in [
+        (0,    DateTime { year: 2022, month: 4, day: 25, hour:  5, minute:  3, second: 30, ms: 0 }),
+        (330,  DateTime { year: 2022, month: 4, day: 25, hour: 10, minute: 33, second: 30, ms: 0 }),
+        (-360, DateTime { year: 2022, month: 4, day: 24, hour: 23, minute:  3, second: 30, ms: 0 }),
+        (MAX_OFFSET_MINUTES, DateTime { year: 2022, month: 4, day: 25, hour: 23, minute: 3, second: 30, ms: 0 }),
+    ] {
+        let odt = c.from_unixtime_with_offset(ts, offset_minutes);
+        assert_eq!(OffsetDateTime { datetime: local, offset_minutes }, odt);
+        assert_eq!(Ok(ts), c.to_unixtime_with_offset_res(&odt));
+        assert_eq!(c.from_unixtime_with_offset(ts, 60), c.to_offset(&odt, 60));
+    }
+}
+
+#[test]
+fn test_offset_display() {
+    let datetime = DateTime { year: 2022, month: 4, day: 5, hour: 6, minute: 7, second: 8, ms: 9 };
+    assert_eq!("2022-04-05T06:07:08.009", datetime.to_string());
+    assert_eq!("2022-04-05T06:07:08.009Z", OffsetDateTime { datetime: datetime.clone(), offset_minutes: 0 }.to_string());
+    assert_eq!("2022-04-05T06:07:08.009+05:30", OffsetDateTime { datetime: datetime.clone(), offset_minutes: 330 }.to_string());
+    assert_eq!("2022-04-05T06:07:08.009-09:30", OffsetDateTime { datetime, offset_minutes: -570 }.to_string());
+}
+
+#[test]
+fn test_offset_validation() {
+    let c = Calendar::create();
+    let datetime = DateTime { year: 2022, month: 4, day: 5, hour: 6, minute: 7, second: 8, ms: 9 };
+    assert_eq!(Ok(()),                           c.validate_offset_datetime(&OffsetDateTime { datetime: datetime.clone(), offset_minutes: -MAX_OFFSET_MINUTES }));
+    assert_eq!(Err(ValidationError::Invalid),    c.validate_offset_datetime(&OffsetDateTime { datetime: datetime.clone(), offset_minutes: MAX_OFFSET_MINUTES + 1 }));
+    assert_eq!(Err(ValidationError::Invalid),    c.validate_offset_datetime(&OffsetDateTime { datetime: DateTime { month: 13, ..datetime }, offset_minutes: 0 }));
+    // local epoch ahead of UTC falls before 1970 in UTC
+    let epoch = DateTime { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0, ms: 0 };
+    assert_eq!(Err(ValidationError::OutOfScope), c.validate_offset_datetime(&OffsetDateTime { datetime: epoch.clone(), offset_minutes: 60 }));
+    assert_eq!(Ok(()),                           c.validate_offset_datetime(&OffsetDateTime { datetime: epoch, offset_minutes: -60 }));
+}
+
+#[test]
+fn test_next_occurrence_at_offset() {
+    let c = Calendar::create();
+    let schedule = Schedule {
+        start: DateTime { year: 2022, month: 1, day: 25, hour: 9, minute: 0, second: 0, ms: 0 },
+        items: vec![(Frequency::Day, 1)],
+        end: None,
+        ..Default::default()
+    };
+    // 08:00 UTC is 09:00 in +01:00, 13:30 in +05:30, 03:00 in -05:00
+    let now = DateTime { year: 2022, month: 2, day: 1, hour: 8, minute: 0, second: 0, ms: 0 };
+    assert_eq!(c.next_occurrence_ms(&now, &schedule), c.next_occurrence_ms_at_offset(&now, 0, &schedule));
+    assert_eq!(Some(24*60*60*1000), c.next_occurrence_ms_at_offset(&now, 60, &schedule));
+    assert_eq!(Some((19*60+30)*60*1000), c.next_occurrence_ms_at_offset(&now, 330, &schedule));
+    assert_eq!(Some(6*60*60*1000), c.next_occurrence_ms_at_offset(&now, -300, &schedule));
+
+    // local time before epoch
+    let now = DateTime { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0, ms: 0 };
+    assert_eq!(None, c.next_occurrence_ms_at_offset(&now, -60, &schedule));
+}
+
+#[test]
+fn test_format() {
+    let c = Calendar::create();
+    let format = |dt: &DateTime, fmt: &str| {
+        let mut s = String::new();
+        c.format(dt, fmt, &mut s).map(|_| s)
+    };
+    let dt = DateTime { year: 2022, month: 4, day: 5, hour: 13, minute: 7, second: 8, ms: 9 };
+
+    assert_eq!(Ok("2022-04-05".to_string()),             format(&dt, "%Y-%m-%d"));
+    assert_eq!(Ok("05/04/2022 13:07".to_string()),       format(&dt, "%d/%m/%Y %H:%M"));
+    assert_eq!(Ok("Tue Apr  5".to_string()),             format(&dt, "%a %b %e"));
+    assert_eq!(Ok("Tuesday,  5 April".to_string()),      format(&dt, "%A, %e %B"));
+    assert_eq!(Ok("20 22 095 2 2 01PM 009".to_string()), format(&dt, "%C %y %j %u %w %I%p %L"));
+    assert_eq!(Ok("04/05/22 2022-04-05 13:07:08 13:07".to_string()), format(&dt, "%D %F %T %R"));
+    assert_eq!(Ok("1649164028 %\n\t Apr".to_string()), format(&dt, "%s %%%n%t %h"));
+    assert_eq!(Ok("Sun 7 12AM 365".to_string()),         format(&DateTime { year: 2023, month: 12, day: 31, hour: 0, minute: 0, second: 0, ms: 0 }, "%a %u %I%p %j"));
+    assert_eq!(Ok("Thu 366".to_string()),                format(&DateTime { year: 2020, month: 12, day: 31, hour: 0, minute: 0, second: 0, ms: 0 }, "%a %j"));
+    assert_eq!(Ok("no specifiers, ünïcödé".to_string()), format(&dt, "no specifiers, ünïcödé"));
+
+    assert_eq!(Err(FormatError::InvalidSpecifier { fmt_position: 3 }), format(&dt, "%Y-%Q"));
+    assert_eq!(Err(FormatError::InvalidSpecifier { fmt_position: 3 }), format(&dt, "%Y %"));
+    assert_eq!(Err(FormatError::InvalidSpecifier { fmt_position: 3 }), format(&dt, "ü %é"));
+    assert_eq!(Err(FormatError::InvalidDateTime), format(&DateTime { year: 2022, month: 13, day: 5, hour: 0, minute: 0, second: 0, ms: 0 }, "%Y"));
+    assert_eq!(Err(FormatError::Write), c.format(&dt, "%Y", &mut FailingWriter));
+}
+
+#[test]
+fn test_parse_with_format() {
+    let c = Calendar::create();
+    let dt = |year, month, day, hour, minute, second, ms| DateTime { year, month, day, hour, minute, second, ms };
+
+    assert_eq!(Ok(dt(2022, 4, 5, 0, 0, 0, 0)),       c.parse_with_format("2022-04-05", "%Y-%m-%d"));
+    assert_eq!(Ok(dt(2022, 4, 5, 13, 7, 0, 0)),      c.parse_with_format("05/04/2022 13:07", "%d/%m/%Y %H:%M"));
+    assert_eq!(Ok(dt(2022, 4, 5, 0, 0, 0, 0)),       c.parse_with_format("tuesday, 5 APRIL 2022", "%A, %e %B %Y"));
+    assert_eq!(Ok(dt(2022, 4, 5, 13, 7, 8, 9)),      c.parse_with_format("20220405T130708.009", "%Y%m%dT%H%M%S.%L"));
+    assert_eq!(Ok(dt(2022, 4, 5, 13, 7, 8, 0)),      c.parse_with_format("04/05/22 01:07:08 pm", "%D %I:%M:%S %p"));
+    assert_eq!(Ok(dt(1970, 1, 1, 0, 7, 0, 0)),       c.parse_with_format("12:07 AM", "%I:%M %p"));
+    assert_eq!(Ok(dt(1999, 12, 31, 0, 0, 0, 0)),     c.parse_with_format("99 365", "%y %j"));
+    assert_eq!(Ok(dt(2020, 12, 31, 0, 0, 0, 0)),     c.parse_with_format("2020 366 4", "%Y %j %w"));
+    assert_eq!(Ok(dt(2022, 4, 5, 13, 7, 8, 9)),      c.parse_with_format("1649164028.009", "%s.%L"));
+    assert_eq!(Ok(dt(1970, 1, 1, 13, 7, 8, 0)),      c.parse_with_format("13:07:08", "%T"));  // date defaults to epoch
+    assert_eq!(Ok(dt(2022, 4, 5, 13, 7, 0, 0)),      c.parse_with_format("2022-04-05 \t\n13:07 100%", "%F%n%R 100%%"));
+
+    let error = |position, kind| Err(ParseError { position, kind });
+    assert_eq!(error(0, ParseErrorKind::TooShort),         c.parse_with_format("", "%Y"));
+    assert_eq!(error(4, ParseErrorKind::Unexpected),       c.parse_with_format("2022/04/05", "%Y-%m-%d"));
+    assert_eq!(error(5, ParseErrorKind::OutOfRange),       c.parse_with_format("2022-13-05", "%Y-%m-%d"));
+    assert_eq!(error(8, ParseErrorKind::OutOfRange),       c.parse_with_format("2022-02-29", "%Y-%m-%d"));  // not a leap year
+    assert_eq!(error(0, ParseErrorKind::OutOfRange),       c.parse_with_format("1969-12-31", "%Y-%m-%d"));  // out of scope
+    assert_eq!(error(10, ParseErrorKind::Unexpected),      c.parse_with_format("2022-04-05 13:07", "%Y-%m-%d"));
+    assert_eq!(error(7, ParseErrorKind::TooShort),         c.parse_with_format("2022-04", "%Y-%m-%d"));
+    assert_eq!(error(0, ParseErrorKind::OutOfRange),       c.parse_with_format("Mon 2022-04-05", "%a %F"));  // was Tuesday
+    assert_eq!(error(0, ParseErrorKind::Unexpected),       c.parse_with_format("Tux 2022-04-05", "%a %F"));
+    assert_eq!(error(5, ParseErrorKind::OutOfRange),       c.parse_with_format("2022 367", "%Y %j"));
+    assert_eq!(error(11, ParseErrorKind::TooShort),        c.parse_with_format("13:07:08.09", "%T.%L"));
+    assert_eq!(error(11, ParseErrorKind::Unexpected),      c.parse_with_format("13:07:08.09Z", "%T.%LZ"));
+    assert_eq!(error(0, ParseErrorKind::OutOfRange),       c.parse_with_format("99999999999", "%s"));
+    assert_eq!(error(5, ParseErrorKind::InvalidSpecifier), c.parse_with_format("2022-04", "%Y-%Q"));
+    assert_eq!(error(4, ParseErrorKind::InvalidSpecifier), c.parse_with_format("2022", "%Y%"));
+}
+
+#[test]
+fn test_rfc2822() {
+    let c = Calendar::create();
+    let odt = |year, month, day, hour, minute, second, offset_minutes| OffsetDateTime { datetime: DateTime { year, month, day, hour, minute, second, ms: 0 }, offset_minutes };
+    let format = |odt: &OffsetDateTime| {
+        let mut s = String::new();
+        c.format_rfc2822(odt, &mut s).map(|_| s)
+    };
+
+    assert_eq!(Ok("Tue, 05 Apr 2022 13:07:08 +0000".to_string()), format(&odt(2022, 4, 5, 13, 7, 8, 0)));
+    assert_eq!(Ok("Thu, 01 Jan 1970 00:00:00 -0930".to_string()), format(&odt(1970, 1, 1, 0, 0, 0, -570)));
+    assert_eq!(Err(FormatError::InvalidDateTime), format(&odt(1970, 1, 1, 0, 0, 0, 60)));  // 31/12/1969 in UTC
+    assert_eq!(Err(FormatError::InvalidDateTime), format(&odt(2022, 4, 5, 13, 7, 8, 19 * 60)));
+
+    assert_eq!(Ok(odt(2022, 4, 5, 13, 7, 8, 330)),  c.parse_rfc2822("Tue, 05 Apr 2022 13:07:08 +0530"));
+    assert_eq!(Ok(odt(2022, 4, 5, 13, 7, 0, -240)), c.parse_rfc2822("5 Apr 2022 13:07 EDT"));
+    assert_eq!(Ok(odt(2022, 4, 5, 13, 7, 8, 0)),    c.parse_rfc2822("  Tue,5   Apr 22 13:07:08 GMT  "));
+    assert_eq!(Ok(odt(1999, 4, 5, 13, 7, 8, 0)),    c.parse_rfc2822("Mon, 5 Apr 99 13:07:08 -0000"));
+    assert_eq!(Ok(odt(2022, 4, 5, 13, 7, 8, 0)),    c.parse_rfc2822("5 Apr 122 13:07:08 UT"));
+
+    let error = |position, kind| Err(ParseError { position, kind });
+    assert_eq!(error(0, ParseErrorKind::OutOfRange),  c.parse_rfc2822("Wed, 05 Apr 2022 13:07:08 +0530"));
+    assert_eq!(error(8, ParseErrorKind::Unexpected),  c.parse_rfc2822("Tue, 05 Apz 2022 13:07:08 +0530"));
+    assert_eq!(error(26, ParseErrorKind::OutOfRange), c.parse_rfc2822("Tue, 05 Apr 2022 13:07:08 +1900"));
+    assert_eq!(error(30, ParseErrorKind::TooShort),   c.parse_rfc2822("Tue, 05 Apr 2022 13:07:08 +530"));
+    assert_eq!(error(26, ParseErrorKind::Unexpected), c.parse_rfc2822("Tue, 05 Apr 2022 13:07:08 XYZ"));
+    assert_eq!(error(26, ParseErrorKind::TooShort),   c.parse_rfc2822("Tue, 05 Apr 2022 13:07:08 "));
+    assert_eq!(error(25, ParseErrorKind::TooShort),   c.parse_rfc2822("Tue, 05 Apr 2022 13:07:08"));
+    assert_eq!(error(23, ParseErrorKind::OutOfRange), c.parse_rfc2822("Thu, 01 Jan 1970 00:00 +0100"));  // 31/12/1969 in UTC
+}
+
+#[test]
+fn test_http_date() {
+    let c = Calendar::create();
+    let format = |ts| {
+        let mut s = String::new();
+        c.format_http_date(ts, &mut s).map(|_| s)
+    };
+
+    assert_eq!(Ok("Sun, 06 Nov 1994 08:49:37 GMT".to_string()), format(784111777123));
+    assert_eq!(Ok("Thu, 01 Jan 1970 00:00:00 GMT".to_string()), format(0));
+    assert_eq!(Err(FormatError::InvalidDateTime), format(u64::MAX));
+
+    assert_eq!(Ok(784111777000), c.parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError { position: 26, kind: ParseErrorKind::Unexpected }), c.parse_http_date("Sun, 06 Nov 1994 08:49:37 UTC"));
+    assert_eq!(Err(ParseError { position: 0, kind: ParseErrorKind::OutOfRange }), c.parse_http_date("Mon, 06 Nov 1994 08:49:37 GMT"));
+}
+
+struct FailingWriter;
+
+impl core::fmt::Write for FailingWriter {
+    fn write_str(&mut self, _: &str) -> core::fmt::Result {
+        Err(core::fmt::Error)
+    }
+}
+
 #[test]
 fn test_invalid_datetimes() {
     let c = Calendar::create();
@@ -373,3 +1282,475 @@ fn test_gen_calendar_offsets() {
     assert_eq!(NON_LEAP_YEAR_MONTH_OFFSETS, non_leap_year_month_offsets);
     assert_eq!(YEAR_MS_OFFSETS, year_ms_offsets);
 }
+
+#[test]
+fn test_leap_year_helpers() {
+    for year in 1704..=3999 {
+        assert_eq!(LEAP_YEARS.contains(&year), is_leap_year(year), "year {}", year);
+        assert_eq!(days_in_year(year), (1..=12).map(|month| days_in_month(year, month) as u16).sum::<u16>());
+    }
+    assert!(is_leap_year(4000));
+    assert_eq!(days_in_year(2100), 365);
+    assert_eq!(days_in_month(2000, 2), 29);
+    assert_eq!(days_in_month(2022, 12), 31);
+
+    // usable in const contexts
+    const FEBRUARY_2024: u8 = days_in_month(2024, 2);
+    assert_eq!(FEBRUARY_2024, 29);
+}
+
+#[cfg(feature = "tz")]
+#[test]
+fn test_zone_by_name() {
+    assert!(Zone::all().windows(2).all(|w| w[0].name < w[1].name), "zones must be sorted and unique for lookup by name");
+    for zone in Zone::all() {
+        assert_eq!(Some(zone), Zone::by_name(zone.name));
+    }
+    assert_eq!(None, Zone::by_name("utc"));
+}
+
+#[cfg(feature = "tz-europe")]
+#[test]
+fn test_zone_local_conversions() {
+    let c = Calendar::create();
+    let london = Zone::by_name("Europe/London").unwrap();
+    let dt = |month, day, hour, minute| DateTime { year: 2022, month, day, hour, minute, second: 0, ms: 0 };
+
+    // winter GMT, summer BST
+    assert_eq!(OffsetDateTime { datetime: dt(1, 15, 9, 0), offset_minutes: 0 }, c.to_local(c.to_unixtime(&dt(1, 15, 9, 0)), london));
+    assert_eq!(OffsetDateTime { datetime: dt(7, 15, 9, 0), offset_minutes: 60 }, c.to_local(c.to_unixtime(&dt(7, 15, 8, 0)), london));
+    assert_eq!(c.to_unixtime(&dt(1, 15, 9, 0)), c.from_local(&dt(1, 15, 9, 0), london));
+    assert_eq!(c.to_unixtime(&dt(7, 15, 8, 0)), c.from_local(&dt(7, 15, 9, 0), london));
+
+    // clocks go forward on 27/03/2022 at 01:00 UTC, 01:30 local doesn't exist and is shifted to 02:30 BST
+    assert_eq!(UtcOffset { offset_minutes: 0, is_dst: false }, london.offset_at(&c, c.to_unixtime(&dt(3, 27, 0, 59))));
+    assert_eq!(UtcOffset { offset_minutes: 60, is_dst: true }, london.offset_at(&c, c.to_unixtime(&dt(3, 27, 1, 0))));
+    assert_eq!(c.to_unixtime(&dt(3, 27, 1, 30)), c.from_local(&dt(3, 27, 1, 30), london));
+
+    // clocks go back on 30/10/2022 at 01:00 UTC, 01:30 local is ambiguous and resolves to the earlier BST
+    assert_eq!(c.to_unixtime(&dt(10, 30, 0, 30)), c.from_local(&dt(10, 30, 1, 30), london));
+    assert_eq!(c.to_unixtime(&dt(10, 30, 2, 0)), c.from_local(&dt(10, 30, 2, 0), london));
+
+    // historical transitions, British Standard Time all year round in 1970
+    assert_eq!(UtcOffset { offset_minutes: 60, is_dst: false }, london.offset_at(&c, c.to_unixtime(&DateTime { year: 1970, ..dt(1, 1, 0, 0) })));
+}
+
+#[cfg(feature = "tz-europe")]
+#[test]
+fn test_next_occurrence_in_zone() {
+    let c = Calendar::create();
+    let london = Zone::by_name("Europe/London").unwrap();
+    let dt = |month, day, hour, minute| DateTime { year: 2022, month, day, hour, minute, second: 0, ms: 0 };
+    let schedule = Schedule {
+        start: dt(1, 1, 1, 30),  // daily at 01:30 London time
+        items: vec![(Frequency::Day, 1)],
+        end: None,
+        ..Default::default()
+    };
+    let policy = |gap, overlap| LocalTimePolicy { gap, overlap };
+    let hours = |h: u64| h * 60 * 60 * 1000;
+
+    // regular days, in GMT and BST
+    assert_eq!(Some(hours(23) + hours(1) / 2), c.next_occurrence_ms_in_zone(&dt(1, 10, 2, 0), london, &LocalTimePolicy::default(), &schedule));
+    assert_eq!(Some(hours(22) + hours(1) / 2), c.next_occurrence_ms_in_zone(&dt(7, 10, 2, 0), london, &LocalTimePolicy::default(), &schedule));
+
+    // clocks go forward on 27/03/2022 at 01:00 UTC, 01:30 doesn't exist
+    let now = dt(3, 26, 2, 0);
+    assert_eq!(Some(hours(23) + hours(1) / 2), c.next_occurrence_ms_in_zone(&now, london, &policy(GapPolicy::ShiftForward, OverlapPolicy::First), &schedule));
+    assert_eq!(Some(hours(46) + hours(1) / 2), c.next_occurrence_ms_in_zone(&now, london, &policy(GapPolicy::Skip, OverlapPolicy::First), &schedule));
+
+    // clocks go back on 30/10/2022 at 01:00 UTC, 01:30 occurs at 00:30 UTC (BST) and at 01:30 UTC (GMT)
+    let now = dt(10, 29, 2, 0);
+    let first_ms = c.to_unixtime(&dt(10, 30, 0, 30));
+    let second_ms = c.to_unixtime(&dt(10, 30, 1, 30));
+    let next_day_ms = c.to_unixtime(&dt(10, 31, 1, 30));
+    let triggers = |overlap| {
+        let mut now_ms = c.to_unixtime(&now);
+        let mut triggers = vec![];
+        while let Some(delta) = c.next_occurrence_ms_in_zone(&c.from_unixtime(now_ms), london, &policy(GapPolicy::ShiftForward, overlap), &schedule) {
+            now_ms += delta;
+            if now_ms > next_day_ms {
+                break;
+            }
+            triggers.push(now_ms);
+        }
+        triggers
+    };
+    assert_eq!(vec![first_ms, next_day_ms], triggers(OverlapPolicy::First));
+    assert_eq!(vec![second_ms, next_day_ms], triggers(OverlapPolicy::Second));
+    assert_eq!(vec![first_ms, second_ms, next_day_ms], triggers(OverlapPolicy::Both));
+
+    // now within the overlap, after the first occurrence
+    let now = dt(10, 30, 1, 0);
+    assert_eq!(Some(next_day_ms - c.to_unixtime(&now)), c.next_occurrence_ms_in_zone(&now, london, &policy(GapPolicy::ShiftForward, OverlapPolicy::First), &schedule));
+    assert_eq!(Some(second_ms - c.to_unixtime(&now)), c.next_occurrence_ms_in_zone(&now, london, &policy(GapPolicy::ShiftForward, OverlapPolicy::Second), &schedule));
+
+    // schedule end is in local wall time
+    let schedule = Schedule { end: Some(dt(10, 30, 1, 0)), ..schedule };
+    assert_eq!(None, c.next_occurrence_ms_in_zone(&dt(10, 29, 2, 0), london, &LocalTimePolicy::default(), &schedule));
+}
+
+#[test]
+fn test_posix_tz_parse() {
+    let month_weekday = |month, week, weekday| TransitionDay::MonthWeekday { month, week, weekday };
+    let us_dst = |offset_minutes| Some(DstRule { offset_minutes, start: month_weekday(3, 2, 0), start_time_seconds: 7200, end: month_weekday(11, 1, 0), end_time_seconds: 7200 });
+
+    assert_eq!(Ok(TzRule { std_offset_minutes: 0, dst: None }),       TzRule::from_posix("UTC0"));
+    assert_eq!(Ok(TzRule { std_offset_minutes: 330, dst: None }),     TzRule::from_posix("IST-5:30"));
+    assert_eq!(Ok(TzRule { std_offset_minutes: -210, dst: None }),    TzRule::from_posix("<-0330>+3:30"));
+    assert_eq!(Ok(TzRule { std_offset_minutes: -300, dst: us_dst(-240) }), TzRule::from_posix("EST5EDT,M3.2.0,M11.1.0"));
+    assert_eq!(Ok(TzRule { std_offset_minutes: -300, dst: us_dst(-240) }), TzRule::from_posix("EST5EDT"));  // US rules by default
+    assert_eq!(Ok(TzRule { std_offset_minutes: -300, dst: us_dst(-180) }), "EST5EDT3".parse());
+    assert_eq!(Ok(TzRule {
+        std_offset_minutes: 600,
+        dst: Some(DstRule { offset_minutes: 660, start: month_weekday(10, 1, 0), start_time_seconds: 7200, end: month_weekday(4, 1, 0), end_time_seconds: 3 * 3600 }),
+    }), TzRule::from_posix("AEST-10AEDT,M10.1.0,M4.1.0/3"));
+    assert_eq!(Ok(TzRule {
+        std_offset_minutes: -180,
+        dst: Some(DstRule { offset_minutes: -120, start: TransitionDay::Julian { day: 60 }, start_time_seconds: -3600, end: TransitionDay::DayOfYear { day: 300 }, end_time_seconds: 167 * 3600 }),
+    }), TzRule::from_posix("<-03>3<-02>,J60/-1,300/167"));
+
+    let error = |position, kind| Err(ParseError { position, kind });
+    assert_eq!(error(0, ParseErrorKind::TooShort),    TzRule::from_posix(""));
+    assert_eq!(error(2, ParseErrorKind::TooShort),    TzRule::from_posix("CE"));
+    assert_eq!(error(2, ParseErrorKind::Unexpected),  TzRule::from_posix("CE1"));
+    assert_eq!(error(3, ParseErrorKind::TooShort),    TzRule::from_posix("CET"));
+    assert_eq!(error(3, ParseErrorKind::OutOfRange),  TzRule::from_posix("CET25"));
+    assert_eq!(error(3, ParseErrorKind::Unsupported), TzRule::from_posix("CET-1:00:30"));
+    assert_eq!(error(3, ParseErrorKind::Unexpected),  TzRule::from_posix("CET?1"));
+    assert_eq!(error(5, ParseErrorKind::TooShort),    TzRule::from_posix("<-01>"));
+    assert_eq!(error(3, ParseErrorKind::Unexpected),  TzRule::from_posix("<+1>1"));
+    assert_eq!(error(16, ParseErrorKind::TooShort),   TzRule::from_posix("CET-1CEST,M3.5.0"));
+    assert_eq!(error(13, ParseErrorKind::OutOfRange), TzRule::from_posix("CET-1CEST,M3.6.0,M10.5.0/3"));
+    assert_eq!(error(15, ParseErrorKind::OutOfRange), TzRule::from_posix("CET-1CEST,M3.5.7,M10.5.0/3"));
+    assert_eq!(error(11, ParseErrorKind::OutOfRange), TzRule::from_posix("CET-1CEST,J0,J365"));
+    assert_eq!(error(10, ParseErrorKind::OutOfRange), TzRule::from_posix("CET-1CEST,366,0"));
+    assert_eq!(error(26, ParseErrorKind::Unexpected), TzRule::from_posix("CET-1CEST,M3.5.0,M10.5.0/3x"));
+}
+
+#[test]
+fn test_posix_tz_conversions() {
+    let c = Calendar::create();
+    let dt = |year, month, day, hour, minute| DateTime { year, month, day, hour, minute, second: 0, ms: 0 };
+
+    // clocks go forward on second Sunday of March at 02:00 local (07:00 UTC), back on first Sunday of November at 02:00 local (06:00 UTC)
+    let new_york = TzRule::from_posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    assert_eq!(UtcOffset { offset_minutes: -300, is_dst: false }, new_york.offset_at(&c, c.to_unixtime(&dt(2022, 3, 13, 6, 59))));
+    assert_eq!(UtcOffset { offset_minutes: -240, is_dst: true },  new_york.offset_at(&c, c.to_unixtime(&dt(2022, 3, 13, 7, 0))));
+    assert_eq!(UtcOffset { offset_minutes: -240, is_dst: true },  new_york.offset_at(&c, c.to_unixtime(&dt(2022, 11, 6, 5, 59))));
+    assert_eq!(UtcOffset { offset_minutes: -300, is_dst: false }, new_york.offset_at(&c, c.to_unixtime(&dt(2022, 11, 6, 6, 0))));
+    assert_eq!(Some(c.to_unixtime(&dt(2022, 3, 13, 7, 0))), new_york.next_transition(&c, c.to_unixtime(&dt(2022, 1, 1, 0, 0))));
+    assert_eq!(Some(c.to_unixtime(&dt(2023, 3, 12, 7, 0))), new_york.next_transition(&c, c.to_unixtime(&dt(2022, 11, 6, 6, 0))));
+    assert_eq!(OffsetDateTime { datetime: dt(2022, 7, 1, 8, 0), offset_minutes: -240 }, c.to_local(c.to_unixtime(&dt(2022, 7, 1, 12, 0)), &new_york));
+    assert_eq!(c.to_unixtime(&dt(2022, 7, 1, 12, 0)), c.from_local(&dt(2022, 7, 1, 8, 0), &new_york));
+    assert_eq!(None, TzRule::from_posix("UTC0").unwrap().next_transition(&c, 0));
+
+    // Julian days never count 29th of February, zero based days of the year do
+    let julian = TzRule::from_posix("AAA0BBB,J60/0,J61/0").unwrap();
+    let day_of_year = TzRule::from_posix("AAA0BBB,59/0,60/0").unwrap();
+    assert_eq!(Some(c.to_unixtime(&dt(2024, 3, 1, 0, 0))), julian.next_transition(&c, c.to_unixtime(&dt(2024, 1, 1, 0, 0))));
+    assert_eq!(Some(c.to_unixtime(&dt(2024, 2, 29, 0, 0))), day_of_year.next_transition(&c, c.to_unixtime(&dt(2024, 1, 1, 0, 0))));
+    assert_eq!(Some(c.to_unixtime(&dt(2023, 3, 1, 0, 0))), day_of_year.next_transition(&c, c.to_unixtime(&dt(2023, 1, 1, 0, 0))));
+
+    // fields out of range, constructed without parsing, are clamped
+    let rule = |start, end| TzRule { std_offset_minutes: 0, dst: Some(DstRule { offset_minutes: 60, start, start_time_seconds: 0, end, end_time_seconds: 0 }) };
+    let clamped = rule(TransitionDay::MonthWeekday { month: 1, week: 1, weekday: 2 }, TransitionDay::MonthWeekday { month: 12, week: 5, weekday: 3 });
+    let out_of_range = rule(TransitionDay::MonthWeekday { month: 0, week: 0, weekday: 9 }, TransitionDay::MonthWeekday { month: 13, week: 255, weekday: 255 });
+    let from_ms = c.to_unixtime(&dt(2022, 1, 1, 0, 0));
+    assert_eq!(clamped.next_transition(&c, from_ms), out_of_range.next_transition(&c, from_ms));
+    assert_eq!(Some(c.to_unixtime(&dt(2022, 1, 4, 0, 0))), out_of_range.next_transition(&c, from_ms));
+    assert_eq!(Some(c.to_unixtime(&dt(2022, 12, 27, 23, 0))), out_of_range.next_transition(&c, c.to_unixtime(&dt(2022, 1, 4, 0, 0))));
+    let days = |start, end| rule(TransitionDay::Julian { day: start }, TransitionDay::DayOfYear { day: end });
+    assert_eq!(days(1, 365).next_transition(&c, from_ms), days(0, 1000).next_transition(&c, from_ms));
+    assert_eq!(days(1, 365).offset_at(&c, from_ms + 1), days(0, 1000).offset_at(&c, from_ms + 1));
+}
+
+#[cfg(feature = "tz-europe")]
+#[test]
+fn test_posix_tz_matches_embedded_rules() {
+    assert_eq!(Ok(Zone::by_name("Europe/London").unwrap().rule), TzRule::from_posix("GMT0BST,M3.5.0/1,M10.5.0"));
+    assert_eq!(Ok(Zone::by_name("Europe/Berlin").unwrap().rule), TzRule::from_posix("CET-1CEST,M3.5.0,M10.5.0/3"));
+    assert_eq!(Ok(Zone::by_name("Europe/Moscow").unwrap().rule), TzRule::from_posix("MSK-3"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_tzif_zones() {
+    let c = Calendar::create();
+    let dt = |year, month, day, hour, minute| DateTime { year, month, day, hour, minute, second: 0, ms: 0 };
+    let fixture = |name: &str| TzifZone::from_file(format!("{}/fixtures/tzif/{}", env!("CARGO_MANIFEST_DIR"), name)).unwrap();
+
+    // version 2, footer in effect after 2037
+    let london = fixture("Europe_London");
+    assert_eq!(Ok(london.rule), TzRule::from_posix("GMT0BST,M3.5.0/1,M10.5.0").map(Some));
+    assert_eq!(Transition { at_ms: 0, offset: UtcOffset { offset_minutes: 60, is_dst: false } }, london.transitions[0]);
+    assert_eq!(OffsetDateTime { datetime: dt(2022, 7, 1, 13, 0), offset_minutes: 60 }, c.to_local(c.to_unixtime(&dt(2022, 7, 1, 12, 0)), &london));
+    assert_eq!(OffsetDateTime { datetime: dt(2050, 7, 1, 13, 0), offset_minutes: 60 }, c.to_local(c.to_unixtime(&dt(2050, 7, 1, 12, 0)), &london));
+    assert_eq!(c.to_unixtime(&dt(2050, 1, 1, 12, 0)), c.from_local(&dt(2050, 1, 1, 12, 0), &london));
+    assert_eq!(Some(c.to_unixtime(&dt(2050, 3, 27, 1, 0))), london.next_transition(&c, c.to_unixtime(&dt(2050, 1, 1, 0, 0))));
+
+    // version 1, without footer, transitions up to 2037 only
+    let london_v1 = fixture("Europe_London_v1");
+    assert_eq!(None, london_v1.rule);
+    assert_eq!(london.transitions, london_v1.transitions);
+    assert_eq!(UtcOffset { offset_minutes: 0, is_dst: false }, london_v1.offset_at(&c, c.to_unixtime(&dt(2050, 7, 1, 12, 0))));
+    assert_eq!(None, london_v1.next_transition(&c, c.to_unixtime(&dt(2050, 1, 1, 0, 0))));
+
+    // slim, footer in effect since 2007
+    let new_york = fixture("America_New_York_slim");
+    assert_eq!(c.to_unixtime(&dt(2007, 3, 11, 7, 0)), new_york.transitions.last().unwrap().at_ms);
+    assert_eq!(Some(c.to_unixtime(&dt(2022, 3, 13, 7, 0))), new_york.next_transition(&c, c.to_unixtime(&dt(2022, 1, 1, 0, 0))));
+    assert_eq!(c.to_unixtime(&dt(2022, 7, 1, 12, 0)), c.from_local(&dt(2022, 7, 1, 8, 0), &new_york));
+
+    // version 3, with clocks going forward at -01:00 on last Sunday of March, ie. Saturday 23:00 local
+    let nuuk = fixture("America_Nuuk");
+    assert_eq!(OffsetDateTime { datetime: dt(2050, 3, 26, 22, 59), offset_minutes: -120 }, c.to_local(c.to_unixtime(&dt(2050, 3, 27, 0, 59)), &nuuk));
+    assert_eq!(OffsetDateTime { datetime: dt(2050, 3, 27, 0, 0), offset_minutes: -60 }, c.to_local(c.to_unixtime(&dt(2050, 3, 27, 1, 0)), &nuuk));
+
+    // fixed offsets
+    let kolkata = fixture("Asia_Kolkata");
+    assert_eq!(Some(TzRule { std_offset_minutes: 330, dst: None }), kolkata.rule);
+    assert_eq!(None, kolkata.next_transition(&c, 0));
+    assert_eq!(OffsetDateTime { datetime: dt(2022, 7, 1, 17, 30), offset_minutes: 330 }, c.to_local(c.to_unixtime(&dt(2022, 7, 1, 12, 0)), &kolkata));
+    let utc = fixture("UTC");
+    assert_eq!(TzifZone { transitions: vec![Transition { at_ms: 0, offset: UtcOffset::default() }], rule: Some(TzRule { std_offset_minutes: 0, dst: None }) }, utc);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_tzif_errors() {
+    let london = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/tzif/Europe_London")).unwrap();
+    let patched = |position: usize, bytes: &[u8]| {
+        let mut patched = london.clone();
+        patched[position..position + bytes.len()].copy_from_slice(bytes);
+        TzifZone::from_bytes(&patched)
+    };
+    let error = |position, kind| Err(ParseError { position, kind });
+
+    assert_eq!(error(0, ParseErrorKind::TooShort),     TzifZone::from_bytes(b""));
+    assert_eq!(error(0, ParseErrorKind::Unexpected),   TzifZone::from_bytes(b"TZxf"));
+    assert_eq!(error(4, ParseErrorKind::Unsupported),  patched(4, b"9"));
+    assert_eq!(error(28, ParseErrorKind::Unsupported), patched(28, &[0, 0, 0, 1]));  // leap seconds
+    assert_eq!(error(100, ParseErrorKind::TooShort),   TzifZone::from_bytes(&london[..100]));
+    assert_eq!(error(london.len() - 1, ParseErrorKind::TooShort), TzifZone::from_bytes(&london[..london.len() - 1]));
+    // footer of `GMT0BST,M3.5.0/1,M10.5.0\n`
+    let footer_start = london.len() - 25;
+    assert_eq!(error(footer_start + 9, ParseErrorKind::OutOfRange), patched(footer_start + 8, b"M13"));
+
+    let not_found = TzifZone::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/tzif/Mars_Olympus_Mons")).unwrap_err();
+    assert_eq!(std::io::ErrorKind::NotFound, not_found.kind());
+    let invalid = TzifZone::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, invalid.kind());
+    assert_eq!("unexpected input at position 0", invalid.to_string());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_json() {
+    let schedule = Schedule {
+        start: DateTime { year: 2022, month: 4, day: 30, hour: 10, minute: 20, second: 30, ms: 400 },
+        items: vec![(Frequency::Month, 1)],
+        end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 }),
+        times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }, TimeOfDay { hour: 18, minute: 30 }],
+        days_of_month: vec![1, 15],
+    };
+    let json = r#"{"start":"2022-04-30T10:20:30.400","items":[["month",1]],"end":"2025-04-30T00:00:00.000","times_of_day":["09:00","18:30"],"days_of_month":[1,15]}"#;
+    assert_eq!(json, serde_json::to_string(&schedule).unwrap());
+    assert_eq!(schedule, serde_json::from_str(json).unwrap());
+
+    // optional fields, and datetimes without ms/seconds/time
+    let schedule = Schedule { start: DateTime { year: 2022, month: 4, day: 30, hour: 10, minute: 20, second: 0, ms: 0 }, items: vec![(Frequency::Week, 2), (Frequency::Ms, 3)], ..Default::default() };
+    assert_eq!(schedule, serde_json::from_str(r#"{"start":"2022-04-30T10:20","items":[["week",2],["ms",3]]}"#).unwrap());
+    assert_eq!(Schedule { start: DateTime { year: 2022, month: 4, day: 30, ..Default::default() }, ..Default::default() }, serde_json::from_str(r#"{"start":"2022-04-30","items":[]}"#).unwrap());
+
+    // rejected at load time
+    for (json, error) in [
+        (r#"{"start":"2022-04-31","items":[]}"#,                                      "invalid datetime: value out of range at position 8"),
+        (r#"{"start":"2022-04-30","items":[["fortnight",1]]}"#,                        "unknown variant `fortnight`"),
+        (r#"{"start":"2022-04-30","items":[["day",0]]}"#,                              "invalid schedule: Invalid"),
+        (r#"{"start":"2022-04-30","items":[],"end":"2022-04-29"}"#,                    "invalid schedule: Invalid"),
+        (r#"{"start":"2022-04-30","items":[["hour",1]],"times_of_day":["09:00"]}"#,    "invalid schedule: Invalid"),
+        (r#"{"start":"2022-04-30","items":[["day",1]],"times_of_day":["9am"]}"#,      "invalid time of day: premature end of input at position 3"),
+        (r#"{"start":"2022-04-30","items":[],"every":"day"}"#,                         "unknown field `every`"),
+        (r#"{"items":[]}"#,                                                            "missing field `start`"),
+    ] {
+        let e = serde_json::from_str::<Schedule>(json).unwrap_err().to_string();
+        assert!(e.starts_with(error), "{}: {}", json, e);
+    }
+    assert!(serde_json::to_string(&DateTime { year: 2022, month: 2, day: 29, ..Default::default() }).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_toml() {
+    let toml = r#"start = "2022-04-30T00:00:00.000"
+items = [["week", 2], ["day", 3]]
+end = "2025-04-30T00:00:00.000"
+times_of_day = ["09:00"]
+days_of_month = []
+"#;
+    let schedule = Schedule {
+        start: DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
+        items: vec![(Frequency::Week, 2), (Frequency::Day, 3)],
+        end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 }),
+        times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }],
+        days_of_month: vec![],
+    };
+    assert_eq!(schedule, toml::from_str(toml).unwrap());
+    assert_eq!(schedule, toml::from_str(&toml::to_string(&schedule).unwrap()).unwrap());
+    assert!(toml::from_str::<Schedule>("start = \"2022-04-30\"\nitems = [[\"day\", 3]]\nend = \"2021-04-30\"\n").is_err());
+}
+
+/// Human readable formats use ISO 8601 strings, compact ones ms from epoch.
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_tokens() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    let dt = DateTime { year: 2022, month: 4, day: 25, hour: 5, minute: 3, second: 30, ms: 0 };
+    assert_tokens(&dt.clone().readable(), &[Token::Str("2022-04-25T05:03:30.000")]);
+    assert_tokens(&dt.clone().compact(), &[Token::U64(1650863010000)]);
+    assert_de_tokens_error::<serde_test::Compact<DateTime>>(&[Token::U64(u64::MAX)], "invalid value: integer `18446744073709551615`, expected ISO 8601 datetime string or ms from epoch");
+
+    let time_of_day = TimeOfDay { hour: 9, minute: 30 };
+    assert_tokens(&time_of_day.readable(), &[Token::Str("09:30")]);
+    assert_tokens(&time_of_day.compact(), &[Token::Tuple { len: 2 }, Token::U8(9), Token::U8(30), Token::TupleEnd]);
+    assert_de_tokens_error::<serde_test::Compact<TimeOfDay>>(&[Token::Tuple { len: 2 }, Token::U8(24), Token::U8(0), Token::TupleEnd], "invalid value: integer `24`, expected hour within 0..=23");
+    assert_de_tokens_error::<serde_test::Compact<TimeOfDay>>(&[Token::Tuple { len: 2 }, Token::U8(9), Token::U8(60), Token::TupleEnd], "invalid value: integer `60`, expected minute within 0..=59");
+    assert_de_tokens_error::<serde_test::Readable<TimeOfDay>>(&[Token::Str("24:00")], "invalid time of day: value out of range at position 0");
+
+    assert_tokens(&Frequency::Minute, &[Token::UnitVariant { name: "Frequency", variant: "minute" }]);
+
+    let schedule = Schedule { start: dt, items: vec![(Frequency::Day, 1)], ..Default::default() };
+    assert_tokens(&schedule.compact(), &[
+        Token::Struct { name: "Schedule", len: 5 },
+        Token::Str("start"), Token::U64(1650863010000),
+        Token::Str("items"), Token::Seq { len: Some(1) },
+        Token::Tuple { len: 2 }, Token::UnitVariant { name: "Frequency", variant: "day" }, Token::U32(1), Token::TupleEnd,
+        Token::SeqEnd,
+        Token::Str("end"), Token::None,
+        Token::Str("times_of_day"), Token::Seq { len: Some(0) }, Token::SeqEnd,
+        Token::Str("days_of_month"), Token::Seq { len: Some(0) }, Token::SeqEnd,
+        Token::StructEnd,
+    ]);
+}
+
+#[cfg(feature = "scale")]
+#[test]
+fn test_scale_compact_encoding() {
+    use codec::{Decode, Encode, MaxEncodedLen};
+
+    let dt = DateTime { year: 2022, month: 4, day: 25, hour: 5, minute: 3, second: 30, ms: 0 };
+    let compact = CompactDateTime::try_from(dt.clone()).unwrap();
+    assert_eq!(codec::Compact(1650863010000_u64).encode(), compact.encode());
+    assert_eq!(7, compact.encode().len());
+    assert_eq!(9, dt.encode().len());
+    assert_eq!(Ok(compact.clone()), CompactDateTime::decode(&mut &compact.encode()[..]));
+    assert_eq!(Err(ValidationError::Invalid), CompactDateTime::try_from(DateTime { day: 31, ..dt.clone() }));
+    assert_eq!(Err(ValidationError::OutOfScope), CompactDateTime::try_from(DateTime { year: 4000, ..dt.clone() }));
+    assert!(CompactDateTime::decode(&mut &codec::Compact(u64::MAX).encode()[..]).is_err());
+    assert_eq!(7, CompactDateTime::max_encoded_len());
+    assert_eq!(9, DateTime::max_encoded_len());
+    assert_eq!(2, TimeOfDay::max_encoded_len());
+    assert_eq!(1, Frequency::max_encoded_len());
+
+    // bounded lists encode as `Vec`s, failing to decode beyond the bound
+    let items = vec![(Frequency::Week, 2), (Frequency::Day, 3)];
+    let bounded = BoundedList::<_, 2>::try_from(items.clone()).unwrap();
+    assert_eq!(items.encode(), bounded.encode());
+    assert_eq!(Ok(bounded.clone()), Decode::decode(&mut &items.encode()[..]));
+    assert_eq!(&items[..], &bounded[..]);
+    assert_eq!(Err(ValidationError::Invalid), BoundedList::<_, 1>::try_from(items.clone()));
+    assert!(BoundedList::<(Frequency, u32), 1>::decode(&mut &items.encode()[..]).is_err());
+    assert_eq!(1 + 2 * 5, BoundedList::<(Frequency, u32), 2>::max_encoded_len());
+
+    // migration of `Schedule` encoding
+    let schedule = Schedule {
+        start: dt.clone(),
+        items: vec![(Frequency::Month, 1)],
+        end: Some(DateTime { year: 2025, ..dt.clone() }),
+        times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }, TimeOfDay { hour: 18, minute: 30 }],
+        days_of_month: vec![1, 15],
+    };
+    let legacy = schedule.encode();
+    let compact = CompactSchedule::<4>::try_from(Schedule::decode(&mut &legacy[..]).unwrap()).unwrap();
+    assert_eq!(7 + 1 + 5 + 1 + 7 + 1 + 2 * 2 + 1 + 2, compact.encode().len());
+    assert_eq!(Ok(compact.clone()), CompactSchedule::decode(&mut &compact.encode()[..]));
+    assert_eq!(schedule, Schedule::from(compact));
+    assert_eq!(7 + 1 + 4 * 5 + 1 + 7 + 1 + 24 * 2 + 1 + 31, CompactSchedule::<4>::max_encoded_len());
+    assert_eq!(Err(ValidationError::Invalid), CompactSchedule::<0>::try_from(schedule.clone()));
+    assert_eq!(Err(ValidationError::Invalid), CompactSchedule::<4>::try_from(Schedule { end: Some(DateTime { year: 2021, ..dt.clone() }), ..schedule }));
+    // decoding validates as per `TryFrom<Schedule>`, eg. of days of month for weekly items, or end prior to start
+    let start = CompactDateTime::try_from(dt.clone()).unwrap();
+    let weekly = |days_of_month: Vec<u8>, end: Option<DateTime>| (&start, vec![(Frequency::Week, 1_u32)], end.map(|end| CompactDateTime::try_from(end).unwrap()), Vec::<TimeOfDay>::new(), days_of_month).encode();
+    assert!(CompactSchedule::<4>::decode(&mut &weekly(vec![], None)[..]).is_ok());
+    assert!(CompactSchedule::<4>::decode(&mut &weekly(vec![1], None)[..]).is_err());
+    assert!(CompactSchedule::<4>::decode(&mut &weekly(vec![], Some(DateTime { year: 2021, ..dt }))[..]).is_err());
+
+    // migration of `Schedule`s stored by 0.1.x, see `fixtures/scale`
+    let legacy = include_bytes!("../fixtures/scale/schedule_0.1.5");
+    let v1 = ScheduleV1 {
+        start: DateTime { year: 2022, month: 4, day: 30, hour: 10, minute: 20, second: 30, ms: 400 },
+        items: vec![(Frequency::Week, 2), (Frequency::Day, 3)],
+        end: Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 }),
+    };
+    assert_eq!(Ok(v1.clone()), ScheduleV1::decode(&mut &legacy[..]));
+    assert_eq!(&legacy[..], &v1.encode()[..]);
+    assert!(Schedule::decode(&mut &legacy[..]).is_err());  // layout of 0.1.x, prior to expansions
+    let compact = CompactSchedule::<4>::try_from(ScheduleV1::decode(&mut &legacy[..]).unwrap()).unwrap();
+    assert_eq!(Schedule { start: v1.start.clone(), items: v1.items.clone(), end: v1.end.clone(), ..Default::default() }, Schedule::from(compact.clone()));
+    assert_eq!(Ok(compact), CompactSchedule::<4>::try_from(Schedule::from(v1.clone())));
+    assert_eq!(Err(ValidationError::Invalid), CompactSchedule::<1>::try_from(v1));
+}
+
+/// Decodes frozen encodings, see `fixtures/scale`.
+#[cfg(feature = "scale")]
+#[test]
+fn test_versioned_schedule_fixtures() {
+    use codec::{Decode, Encode};
+
+    let start = DateTime { year: 2022, month: 4, day: 30, hour: 10, minute: 20, second: 30, ms: 400 };
+    let end = Some(DateTime { year: 2025, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 });
+    let v1 = ScheduleV1 { start: start.clone(), items: vec![(Frequency::Week, 2), (Frequency::Day, 3)], end: end.clone() };
+    let v2 = ScheduleV2 {
+        start: start.clone(),
+        items: vec![(Frequency::Month, 1)],
+        end: end.clone(),
+        times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }, TimeOfDay { hour: 18, minute: 30 }],
+        days_of_month: vec![1, 15],
+    };
+
+    // unversioned 0.1.5 encoding
+    let legacy = include_bytes!("../fixtures/scale/schedule_0.1.5");
+    assert_eq!(Ok(v1.clone()), ScheduleV1::decode(&mut &legacy[..]));
+    assert_eq!(&legacy[..], &v1.encode()[..]);
+
+    for (fixture, versioned) in [
+        (&include_bytes!("../fixtures/scale/versioned_schedule_v1")[..], VersionedSchedule::V1(v1.clone())),
+        (&include_bytes!("../fixtures/scale/versioned_schedule_v2")[..], VersionedSchedule::V2(v2.clone())),
+    ] {
+        assert_eq!(Ok(versioned.clone()), VersionedSchedule::decode(&mut &fixture[..]));
+        assert_eq!(fixture, &versioned.encode()[..]);
+    }
+    assert!(VersionedSchedule::decode(&mut &[0_u8][..]).is_err());
+    assert!(VersionedSchedule::decode(&mut &[3_u8][..]).is_err());
+
+    // lossless upgrades
+    let upgraded = VersionedSchedule::V1(v1.clone()).upgrade();
+    assert_eq!(VersionedSchedule::LATEST_VERSION, upgraded.version());
+    assert_eq!(VersionedSchedule::V2(ScheduleV2 { start: start.clone(), items: v1.items.clone(), end: end.clone(), times_of_day: vec![], days_of_month: vec![] }), upgraded);
+    assert_eq!(Schedule { start: start.clone(), items: v1.items.clone(), end: end.clone(), ..Default::default() }, Schedule::from(VersionedSchedule::V1(v1.clone())));
+    let schedule = Schedule::from(VersionedSchedule::V2(v2.clone()));
+    assert_eq!(Ok(()), Calendar::create().validate_schedule(&schedule));
+    assert_eq!(v2.encode(), schedule.encode());  // fields added to `Schedule` require a new version
+    assert_eq!(VersionedSchedule::V2(v2.clone()), VersionedSchedule::from(schedule));
+
+    // migrations of stored schedules to `CompactSchedule`
+    for (fixture, schedule) in [
+        (&include_bytes!("../fixtures/scale/versioned_schedule_v1")[..], Schedule::from(v1)),
+        (&include_bytes!("../fixtures/scale/versioned_schedule_v2")[..], Schedule::from(v2)),
+    ] {
+        let versioned = VersionedSchedule::decode(&mut &fixture[..]).unwrap();
+        assert_eq!(Ok(schedule), CompactSchedule::<4>::try_from(versioned).map(Schedule::from));
+    }
+}
@@ -1,11 +1,37 @@
 use crate::constants::{EPOCH_YEAR, MS_IN_DAY, MS_IN_HOUR, MS_IN_SEC, MS_IN_MIN};
 use super::prelude::*;
 
-use chrono::{NaiveDateTime, TimeZone, Utc, Datelike, Timelike};
-use quickcheck::QuickCheck;
+use chrono::{NaiveDate, TimeZone, Utc, Datelike, Timelike};
+use quickcheck::{QuickCheck, StdThreadGen, Testable};
 use std::panic;
 
 const PROPS_TESTS: u64 = 1000;  // 10 x the norm
+/// Size of generated numbers for timestamps spanning the whole range, default of 100 only covers the first ms of epoch.
+const TIMESTAMP_GEN_SIZE: usize = 4102444800000;  // 2100
+
+/// Spreads a value over `[0, range)`, as values generated by `QuickCheck::new()` are bounded by the size of generated vectors.
+fn spread(x: u64, range: u64) -> u64 {
+    x.wrapping_mul(104_729_000_003) % range
+}
+
+/// Prints the failed case, if not `valid`.
+fn report(valid: bool, case: impl FnOnce() -> String) -> bool {
+    if !valid {
+        println!("Failed on {}", case());
+    }
+    valid
+}
+
+/// Runs `tests` of a property, with generated numbers and vectors bounded by the default size, see `spread()`.
+fn props<A: Testable>(tests: u64, prop: A) {
+    QuickCheck::new().tests(tests).max_tests(PROPS_TESTS * 100).quickcheck(prop)
+}
+
+/// Runs `tests` of a property, with generated numbers spanning timestamps up to `TIMESTAMP_GEN_SIZE`.
+/// Narrower unsigned types wrap the size, eg. `u8`s are always 0, hence `props()` with `spread()` for those.
+fn timestamp_props<A: Testable>(tests: u64, prop: A) {
+    QuickCheck::with_gen(StdThreadGen::new(TIMESTAMP_GEN_SIZE)).tests(tests).max_tests(PROPS_TESTS * 100).quickcheck(prop)
+}
 
 /// For default quickcheck behaviour, with not panic hook initialization or test run settings, could also just do:
 /// ```no_run
@@ -25,7 +51,7 @@ fn validate_light_vs_chrono(year: u16, month: u8, day: u8, hour: u8, minute: u8,
     let second = second % 65;
     let ms = ms % 1100;
     let dt_light = DateTime { year, month, day, hour, minute, second, ms};
-    let dt_chrono_res = panic::catch_unwind(|| Utc.ymd(year as i32, month as u32, day as u32).and_hms_milli(hour as u32, minute as u32, second as u32, ms as u32));
+    let dt_chrono_res = panic::catch_unwind(|| Utc.from_utc_datetime(&NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32).unwrap().and_hms_milli_opt(hour as u32, minute as u32, second as u32, ms as u32).unwrap()));
     let dt_chrono_light_res = panic::catch_unwind(|| c.to_unixtime(&dt_light));
     let validation_result = c.validate_datetime(&dt_light);
 
@@ -51,9 +77,9 @@ fn validate_scheduler_after_start(start_ms: u64, delta_ms: u64, freq: u8, freq_m
     let c = Calendar::create();
 
     fn get_exact_months(start_ms: u64, now_ms: u64, next_occurrence: u64) -> Option<u32> {
-        let start = NaiveDateTime::from_timestamp(start_ms as i64 / 1000, ((start_ms % 1000) * 1_000_000) as u32);
+        let start = chrono::DateTime::from_timestamp(start_ms as i64 / 1000, ((start_ms % 1000) * 1_000_000) as u32).unwrap();
         let end_ms = now_ms + next_occurrence;
-        let end = NaiveDateTime::from_timestamp(end_ms as i64 / 1000, ((end_ms % 1000) * 1_000_000) as u32);
+        let end = chrono::DateTime::from_timestamp(end_ms as i64 / 1000, ((end_ms % 1000) * 1_000_000) as u32).unwrap();
         if start.day() == end.day() && start.hour() == end.hour() && start.minute() == end.minute() && start.second() == end.second()
            && start.timestamp_subsec_millis() == end.timestamp_subsec_millis() {
             Some(((end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32) as u32)
@@ -83,7 +109,7 @@ fn validate_scheduler_after_start(start_ms: u64, delta_ms: u64, freq: u8, freq_m
     let now_ms = start_ms + delta_ms;
 
     let now = c.from_unixtime(now_ms);
-    let next_occurrence = c.next_occurrence_ms(&now, &Schedule { start: start.clone(), items: vec![(freq, freq_multiplier as u32)], end: None }).unwrap();
+    let next_occurrence = c.next_occurrence_ms(&now, &Schedule { start: start.clone(), items: vec![(freq, freq_multiplier as u32)], end: None, ..Default::default() }).unwrap();
 
     match freq {
         Frequency::Year => {
@@ -155,9 +181,556 @@ fn test_start_equals_now() {
             start: now,
             items: vec![(freq, freq_multiplier)],
             end: None,
+            ..Default::default()
         });
 
-        res.map_or(false, |x| is_trigger_valid(freq, freq_multiplier, x))
+        res.is_some_and(|x| is_trigger_valid(freq, freq_multiplier, x))
     }
     QuickCheck::new().tests(PROPS_TESTS / 10).max_tests(PROPS_TESTS * 10).quickcheck(validate_start_equals_now as fn(u64, u8) -> bool)
-}
\ No newline at end of file
+}
+
+/// Purpose of this test is to confirm the correctness of the generated tz data and rules.
+#[cfg(feature = "tz")]
+#[test]
+fn test_zones_vs_chrono_tz() {
+    use chrono::Offset;
+    use chrono_tz::OffsetComponents;
+
+    fn validate_zone_offset(ts: u64, zone_index: usize) -> bool {
+        let c = Calendar::create();
+        let zone = &Zone::all()[zone_index % Zone::all().len()];
+        let ts = ts % 4102444800000;  // up to 2100
+        let tz: chrono_tz::Tz = zone.name.parse().unwrap();
+        let chrono_offset = tz.offset_from_utc_datetime(&chrono::DateTime::from_timestamp_millis(ts as i64).unwrap().naive_utc());
+        let expected = UtcOffset {
+            offset_minutes: (chrono_offset.fix().local_minus_utc() / 60) as i16,
+            is_dst: !chrono_offset.dst_offset().is_zero(),
+        };
+        let offset = crate::tz::TimeZone::offset_at(zone, &c, ts);
+        report(offset == expected, || format!("zone: {}, ts: {}, light: {:?}, chrono-tz: {:?}", zone.name, ts, offset, expected))
+    }
+    timestamp_props(PROPS_TESTS * 10, validate_zone_offset as fn(u64, usize) -> bool)
+}
+
+/// Compares zoned schedule evaluation with brute force resolution of local times, on days of offset transitions.
+#[cfg(feature = "tz")]
+#[test]
+fn test_zone_schedule_on_transition_days() {
+    use crate::tz::TimeZone as LightTimeZone;
+
+    fn validate_transition_day(year: u16, zone_index: usize, transition_index: bool, minute_of_day: u16, policy: u8) -> bool {
+        let c = Calendar::create();
+        let zone = &Zone::all()[spread(zone_index as u64, Zone::all().len() as u64) as usize];
+        let year = 1971 + spread(year as u64, 130) as u16;
+        let policy = LocalTimePolicy {
+            gap: [GapPolicy::ShiftForward, GapPolicy::Skip][policy as usize % 2],
+            overlap: [OverlapPolicy::First, OverlapPolicy::Second, OverlapPolicy::Both][policy as usize / 2 % 3],
+        };
+        let new_year_ms = c.to_unixtime(&DateTime { year, month: 1, day: 1, hour: 0, minute: 0, second: 0, ms: 0 });
+        let transition_ms = match zone.next_transition(&c, new_year_ms) {
+            Some(transition_ms) if transition_index => zone.next_transition(&c, transition_ms),
+            transition_ms => transition_ms,
+        };
+        let transition_ms = match transition_ms {
+            Some(transition_ms) => transition_ms,
+            None => return true,  // no transitions left
+        };
+        // target local times within 3 hours of the transition
+        let transition_local = c.to_local(transition_ms, zone).datetime;
+        let transition_minute_of_day = transition_local.hour as u32 * 60 + transition_local.minute as u32;
+        let minute_of_day = (transition_minute_of_day + 24 * 60 + spread(minute_of_day as u64, 360) as u32 - 180) % (24 * 60);
+        let schedule = Schedule {
+            start: DateTime { year: year - 1, month: 1, day: 1, hour: (minute_of_day / 60) as u8, minute: (minute_of_day % 60) as u8, second: 0, ms: 0 },
+            items: vec![(Frequency::Day, 1)],
+            end: None,
+            ..Default::default()
+        };
+        let from_ms = transition_ms - 2 * MS_IN_DAY;
+        let to_ms = transition_ms + 2 * MS_IN_DAY;
+
+        let mut actual = vec![];
+        let mut now_ms = from_ms;
+        while let Some(delta) = c.next_occurrence_ms_in_zone(&c.from_unixtime(now_ms), zone, &policy, &schedule) {
+            now_ms += delta;
+            if now_ms >= to_ms {
+                break;
+            }
+            actual.push(now_ms);
+        }
+
+        let mut expected = vec![];
+        let first_day_ms = (from_ms / MS_IN_DAY - 1) * MS_IN_DAY;
+        for local_ms in (0..7).map(|day| first_day_ms + day * MS_IN_DAY + minute_of_day as u64 * MS_IN_MIN) {
+            let offset_before = zone.offset_at(&c, local_ms - MS_IN_DAY).offset_minutes as i64;
+            let offset_after = zone.offset_at(&c, local_ms + MS_IN_DAY).offset_minutes as i64;
+            let mut instants: Vec<u64> = [offset_before, offset_after].iter()
+                .map(|&offset| (local_ms as i64 - offset * MS_IN_MIN as i64) as u64)
+                .filter(|&ts| c.to_local(ts, zone).datetime == c.from_unixtime(local_ms))
+                .collect();
+            instants.dedup();
+            match (instants.as_slice(), policy.gap, policy.overlap) {
+                ([], GapPolicy::ShiftForward, _) => expected.push((local_ms as i64 - offset_before * MS_IN_MIN as i64) as u64),
+                ([], GapPolicy::Skip, _) => (),
+                ([ts], _, _) => expected.push(*ts),
+                ([first, _], _, OverlapPolicy::First) => expected.push(*first),
+                ([_, second], _, OverlapPolicy::Second) => expected.push(*second),
+                (both, _, _) => expected.extend(both),
+            }
+        }
+        expected.retain(|&ts| ts > from_ms && ts < to_ms);
+
+        report(actual == expected, || format!("zone: {}, transition: {}, minute of day: {}, policy: {:?}, actual: {:?}, expected: {:?}", zone.name, transition_ms, minute_of_day, policy, actual, expected))
+    }
+    props(PROPS_TESTS, validate_transition_day as fn(u16, usize, bool, u16, u8) -> bool)
+}
+
+/// Compares rules parsed from POSIX TZ strings with chrono-tz, for years since the rules have been in effect.
+#[test]
+fn test_posix_tz_vs_chrono_tz() {
+    use chrono::Offset;
+
+    fn validate_posix_offset(ts: u64, zone_index: usize) -> bool {
+        let c = Calendar::create();
+        let (name, posix) = [
+            ("America/New_York", "EST5EDT,M3.2.0,M11.1.0"),
+            ("Europe/Berlin", "CET-1CEST,M3.5.0,M10.5.0/3"),
+            ("Australia/Sydney", "AEST-10AEDT,M10.1.0,M4.1.0/3"),
+        ][zone_index % 3];
+        let ts = 1199145600000 + ts % (4102444800000 - 1199145600000);  // 2008 until 2100
+        let tz: chrono_tz::Tz = name.parse().unwrap();
+        let expected = tz.offset_from_utc_datetime(&chrono::DateTime::from_timestamp_millis(ts as i64).unwrap().naive_utc()).fix().local_minus_utc() / 60;
+        let offset = crate::tz::TimeZone::offset_at(&TzRule::from_posix(posix).unwrap(), &c, ts).offset_minutes as i32;
+        report(offset == expected, || format!("rule: {}, ts: {}, light: {}, chrono-tz: {}", posix, ts, offset, expected))
+    }
+    timestamp_props(PROPS_TESTS, validate_posix_offset as fn(u64, usize) -> bool)
+}
+
+/// Compares zones read from TZif fixtures with chrono-tz, before and after the last transitions of the files.
+#[test]
+fn test_tzif_zones_vs_chrono_tz() {
+    use chrono::Offset;
+    use chrono_tz::OffsetComponents;
+
+    fn validate_tzif_offset(ts: u64, zone_index: usize) -> bool {
+        let c = Calendar::create();
+        let (name, fixture) = [
+            ("Europe/London", "Europe_London"),
+            ("America/New_York", "America_New_York_slim"),
+            ("America/Nuuk", "America_Nuuk"),
+            ("Australia/Sydney", "Australia_Sydney"),
+            ("Asia/Kolkata", "Asia_Kolkata"),
+            ("UTC", "UTC"),
+        ][zone_index % 6];
+        let zone = TzifZone::from_file(format!("{}/fixtures/tzif/{}", env!("CARGO_MANIFEST_DIR"), fixture)).unwrap();
+        let ts = ts % 4102444800000;  // up to 2100
+        let tz: chrono_tz::Tz = name.parse().unwrap();
+        let chrono_offset = tz.offset_from_utc_datetime(&chrono::DateTime::from_timestamp_millis(ts as i64).unwrap().naive_utc());
+        let expected = UtcOffset {
+            offset_minutes: (chrono_offset.fix().local_minus_utc() / 60) as i16,
+            is_dst: !chrono_offset.dst_offset().is_zero(),
+        };
+        let offset = crate::tz::TimeZone::offset_at(&zone, &c, ts);
+        // chrono-tz keeps DST for the 2 hours of Nuuk's move to standard -02 in 2023, where zic does not
+        let nuuk_2023 = fixture == "America_Nuuk" && (1679792400000..1679799600000).contains(&ts);
+        let valid = offset == expected || nuuk_2023 && offset.offset_minutes == expected.offset_minutes;
+        report(valid, || format!("fixture: {}, ts: {}, light: {:?}, chrono-tz: {:?}", fixture, ts, offset, expected))
+    }
+    timestamp_props(PROPS_TESTS * 10, validate_tzif_offset as fn(u64, usize) -> bool)
+}
+
+/// Compares formatting with chrono, for all supported specifiers, with ms as `%L` rather than chrono's `%3f`.
+#[test]
+fn test_format_vs_chrono() {
+    fn validate_format(ts: u64) -> bool {
+        let c = Calendar::create();
+        let fmt = "%Y %C %y %m %B %b %h %d %e %j %A %a %u %w %H %I %p %M %S %L %s %D %F %T %R %n%t%%";
+        let mut formatted = String::new();
+        c.format(&c.from_unixtime(ts), fmt, &mut formatted).unwrap();
+        let expected = chrono::DateTime::from_timestamp_millis(ts as i64).unwrap().format(&fmt.replace("%L", "%3f")).to_string();
+        report(formatted == expected, || format!("ts: {}, light: {}, chrono: {}", ts, formatted, expected))
+    }
+    timestamp_props(PROPS_TESTS, validate_format as fn(u64) -> bool)
+}
+
+/// Parses formatted `DateTime`s back, for formats with all fields.
+#[test]
+fn test_format_parse_roundtrip() {
+    fn validate_roundtrip(ts: u64, format_index: usize) -> bool {
+        let c = Calendar::create();
+        let fmt = [
+            "%Y-%m-%d %H:%M:%S.%L",
+            "%a %b %e %T.%L %Y",
+            "%A, %d %B %Y %I:%M:%S.%L %p",
+            "%C%y%j%H%M%S%L",
+            "%s.%L",
+            "%u %F%n%R:%S.%L",
+        ][format_index % 6];
+        let dt = c.from_unixtime(ts);
+        let mut formatted = String::new();
+        c.format(&dt, fmt, &mut formatted).unwrap();
+        let parsed = c.parse_with_format(&formatted, fmt);
+        report(parsed.as_ref() == Ok(&dt), || format!("ts: {}, fmt: {}, formatted: {}, parsed: {:?}", ts, fmt, formatted, parsed))
+    }
+    timestamp_props(PROPS_TESTS, validate_roundtrip as fn(u64, usize) -> bool)
+}
+
+/// Compares RFC 2822 and HTTP dates with chrono, and parses both back.
+#[test]
+fn test_rfc2822_vs_chrono() {
+    fn validate_rfc2822(ts: u64, offset_minutes: i16) -> bool {
+        let c = Calendar::create();
+        let ts = ts - ts % MS_IN_SEC + MS_IN_DAY;  // whole seconds, with local time of negative offsets in scope
+        let offset_minutes = (offset_minutes as i32 * 37 % (MAX_OFFSET_MINUTES as i32 + 1)) as i16;
+        let odt = c.from_unixtime_with_offset(ts, offset_minutes);
+        let mut formatted = String::new();
+        c.format_rfc2822(&odt, &mut formatted).unwrap();
+        let mut http_date = String::new();
+        c.format_http_date(ts, &mut http_date).unwrap();
+
+        let chrono_dt = chrono::DateTime::from_timestamp_millis(ts as i64).unwrap();
+        let chrono_odt = chrono_dt.with_timezone(&chrono::FixedOffset::east_opt(offset_minutes as i32 * 60).unwrap());
+        // chrono doesn't pad days with zeros, both being valid
+        let expected = chrono_odt.format("%a, %d %b %Y %H:%M:%S %z").to_string();
+        let expected_http_date = chrono_dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let parsed = c.parse_rfc2822(&formatted).and(c.parse_rfc2822(&chrono_odt.to_rfc2822()));
+        let parsed_http_date = c.parse_http_date(&http_date);
+        let valid = formatted == expected && http_date == expected_http_date && parsed == Ok(odt) && parsed_http_date == Ok(ts);
+        report(valid, || format!("ts: {}, offset: {}, light: {} / {}, chrono: {} / {}, parsed: {:?} / {:?}", ts, offset_minutes, formatted, http_date, expected, expected_http_date, parsed, parsed_http_date))
+    }
+    timestamp_props(PROPS_TESTS, validate_rfc2822 as fn(u64, i16) -> bool)
+}
+
+/// Parses schedules back from their DSL.
+#[test]
+fn test_schedule_dsl_roundtrip() {
+    fn validate_dsl_roundtrip(start_ts: u64, end_delta_ms: Option<u64>, items: Vec<(u8, u32)>, times_of_day: Vec<(u8, u8)>, days_of_month: Vec<u8>) -> bool {
+        let c = Calendar::create();
+        let (start_ts, end_delta_ms) = (spread(start_ts, TIMESTAMP_GEN_SIZE as u64), end_delta_ms.map(|delta| spread(delta, TIMESTAMP_GEN_SIZE as u64)));
+        let freqs = [Frequency::Year, Frequency::Month, Frequency::Week, Frequency::Day, Frequency::Hour, Frequency::Minute, Frequency::Second, Frequency::Ms];
+        let items: Vec<_> = items.into_iter().map(|(freq, multiplier)| (freqs[freq as usize % 8], multiplier.max(1))).collect();
+        let max_freq = items.iter().map(|(freq, _)| *freq).max().unwrap_or(Frequency::Year);
+        let schedule = Schedule {
+            start: c.from_unixtime(start_ts),
+            end: end_delta_ms.map(|delta| c.from_unixtime((start_ts + delta).min(TIMESTAMP_GEN_SIZE as u64))),
+            times_of_day: if max_freq <= Frequency::Day {
+                times_of_day.into_iter().take(MAX_TIMES_OF_DAY).map(|(hour, minute)| TimeOfDay { hour: hour % 24, minute: minute % 60 }).collect()
+            } else {
+                vec![]
+            },
+            days_of_month: if max_freq <= Frequency::Month {
+                days_of_month.into_iter().take(MAX_DAYS_OF_MONTH).map(|day| day % 31 + 1).collect()
+            } else {
+                vec![]
+            },
+            items,
+        };
+        let dsl = schedule.to_string();
+        let parsed = dsl.parse::<Schedule>();
+        report(parsed.as_ref() == Ok(&schedule), || format!("schedule: {:?}, dsl: {}, parsed: {:?}", schedule, dsl, parsed))
+    }
+    props(PROPS_TESTS, validate_dsl_roundtrip as fn(u64, Option<u64>, Vec<(u8, u32)>, Vec<(u8, u8)>, Vec<u8>) -> bool)
+}
+
+/// Compares adding of durations with chrono, and parses durations back from ISO 8601.
+#[test]
+fn test_add_duration_vs_chrono() {
+    fn validate_add_duration(ts: u64, months: u64, fixed_ms: u64) -> bool {
+        let c = Calendar::create();
+        let (months, fixed_ms) = ((months % 1200) as u32, fixed_ms % (100 * 366 * MS_IN_DAY));
+        let duration = IsoDuration {
+            years: months / 12,
+            months: months % 12,
+            days: (fixed_ms / MS_IN_DAY) as u32,
+            seconds: ((fixed_ms % MS_IN_DAY) / MS_IN_SEC) as u32,
+            ms: (fixed_ms % MS_IN_SEC) as u32,
+            ..Default::default()
+        };
+        let added = c.add_duration(&c.from_unixtime(ts), &duration).map(|dt| c.to_unixtime(&dt));
+        let expected = chrono::DateTime::from_timestamp_millis(ts as i64).unwrap().naive_utc()
+            .checked_add_months(chrono::Months::new(months)).unwrap()
+            + chrono::TimeDelta::milliseconds(fixed_ms as i64);
+        let expected = Some(expected.and_utc().timestamp_millis() as u64).filter(|_| expected.year() < 4000);
+        let parsed = duration.to_string().parse::<IsoDuration>();
+        report(added == expected && parsed == Ok(duration), || format!("ts: {}, duration: {}, light: {:?}, chrono: {:?}, parsed: {:?}", ts, duration, added, expected, parsed))
+    }
+    timestamp_props(PROPS_TESTS, validate_add_duration as fn(u64, u64, u64) -> bool)
+}
+
+/// Compares triggers of schedules converted from repeating intervals with repetitions of start + n * duration, which clamp the day
+/// rather than rolling over into the following month, hence months and years starting after the 28th are rejected.
+#[test]
+fn test_repeating_interval_vs_schedule() {
+    fn validate_repeating_interval(ts: u64, freq: u8, multiplier: u8, repetitions: u8) -> bool {
+        let c = Calendar::create();
+        let multiplier = multiplier as u32 % 24 + 1;
+        let duration = |n: u32| match freq % 8 {
+            0 => IsoDuration { years: multiplier * n, ..Default::default() },
+            1 => IsoDuration { months: multiplier * n, ..Default::default() },
+            2 => IsoDuration { weeks: multiplier * n, ..Default::default() },
+            3 => IsoDuration { days: multiplier * n, ..Default::default() },
+            4 => IsoDuration { hours: multiplier * n, ..Default::default() },
+            5 => IsoDuration { minutes: multiplier * n, ..Default::default() },
+            6 => IsoDuration { seconds: multiplier * n, ..Default::default() },
+            _ => IsoDuration { ms: multiplier * n, ..Default::default() },
+        };
+        let ri = RepeatingInterval { repetitions: Some(repetitions as u32 % 24 + 1), start: c.from_unixtime(spread(ts, TIMESTAMP_GEN_SIZE as u64)), duration: duration(1) };
+        let schedule = c.schedule_from_repeating_interval(&ri);
+        let valid = match &schedule {
+            Err(ValidationError::Invalid) => freq % 8 <= 1 && ri.start.day > 28,
+            Err(_) => true,  // last repetition out of scope
+            Ok(schedule) => {
+                let expected: Vec<_> = (0..ri.repetitions.unwrap()).map(|n| c.to_unixtime(&c.add_duration(&ri.start, &duration(n)).unwrap())).collect();
+                let mut triggers = vec![c.to_unixtime(&ri.start)];
+                while let Some(delta) = c.next_occurrence_ms(&c.from_unixtime(triggers[triggers.len() - 1]), schedule).filter(|_| triggers.len() <= expected.len()) {
+                    triggers.push(triggers[triggers.len() - 1] + delta);
+                }
+                triggers == expected
+            }
+        };
+        report(valid, || format!("repeating interval: {}, schedule: {:?}", ri, schedule))
+    }
+    props(PROPS_TESTS, validate_repeating_interval as fn(u64, u8, u8, u8) -> bool)
+}
+
+/// Serializes `DateTime`s as ISO 8601 strings, matching chrono, and deserializes them back.
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_roundtrip() {
+    fn validate_serde_roundtrip(ts: u64) -> bool {
+        let c = Calendar::create();
+        let dt = c.from_unixtime(ts);
+        let json = serde_json::to_string(&dt).unwrap();
+        let expected = format!("\"{}\"", chrono::DateTime::from_timestamp_millis(ts as i64).unwrap().format("%Y-%m-%dT%H:%M:%S%.3f"));
+        let parsed = serde_json::from_str::<DateTime>(&json);
+        report(json == expected && parsed.as_ref().ok() == Some(&dt), || format!("ts: {}, light: {}, chrono: {}, parsed: {:?}", ts, json, expected, parsed))
+    }
+    timestamp_props(PROPS_TESTS, validate_serde_roundtrip as fn(u64) -> bool)
+}
+
+/// Decodes `CompactDateTime`s back, encoded as compact ms from epoch.
+#[cfg(feature = "scale")]
+#[test]
+fn test_scale_compact_roundtrip() {
+    use codec::{Compact, Decode, Encode, MaxEncodedLen};
+
+    fn validate_compact_roundtrip(ts: u64) -> bool {
+        let c = Calendar::create();
+        let compact = CompactDateTime::try_from(c.from_unixtime(ts)).unwrap();
+        let encoded = compact.encode();
+        let decoded = CompactDateTime::decode(&mut &encoded[..]);
+        let valid = encoded == Compact(ts).encode() && encoded.len() <= CompactDateTime::max_encoded_len() && decoded.as_ref() == Ok(&compact);
+        report(valid, || format!("ts: {}, encoded: {:?}, decoded: {:?}", ts, encoded, decoded))
+    }
+    timestamp_props(PROPS_TESTS, validate_compact_roundtrip as fn(u64) -> bool)
+}
+
+/// Rounds next occurrences of schedules to blocks produced at or after, and prior to the occurrence, as per `BlockRounding`.
+#[test]
+fn test_next_occurrence_block() {
+    fn validate_next_occurrence_block(anchor_ms: u64, now_block: u32, block_time_ms: u16, freq: u8, multiplier: u8) -> bool {
+        let c = Calendar::create();
+        let clock = BlockClock { anchor_block: 1_000_000, anchor_ms: spread(anchor_ms, TIMESTAMP_GEN_SIZE as u64 / 2), block_time_ms: spread(block_time_ms as u64, 1 << 16) + 1 };
+        let freq = [Frequency::Year, Frequency::Month, Frequency::Week, Frequency::Day, Frequency::Hour, Frequency::Minute, Frequency::Second, Frequency::Ms][freq as usize % 8];
+        let schedule = Schedule { start: c.from_unixtime(clock.anchor_ms), items: vec![(freq, multiplier as u32 % 10 + 1)], ..Default::default() };
+        let now_block = clock.anchor_block + spread(now_block as u64, 100_000);
+        let now_ms = clock.block_to_ms(now_block).unwrap();
+        let occurrence_ms = now_ms + c.next_occurrence_ms(&c.from_unixtime(now_ms), &schedule).unwrap();
+        [BlockRounding::Up, BlockRounding::Down, BlockRounding::Nearest].into_iter().all(|rounding| {
+            let block = c.next_occurrence_block(&clock, now_block, rounding, &schedule).unwrap();
+            let block_ms = clock.block_to_ms(block).unwrap();
+            let rounded_up = block_ms >= occurrence_ms && block_ms - occurrence_ms < clock.block_time_ms;
+            let valid = block > now_block && match rounding {
+                BlockRounding::Up      => rounded_up,
+                BlockRounding::Down    => (block_ms <= occurrence_ms && occurrence_ms - block_ms < clock.block_time_ms) || (block == now_block + 1 && rounded_up),
+                BlockRounding::Nearest => block_ms.abs_diff(occurrence_ms) * 2 <= clock.block_time_ms || (block == now_block + 1 && rounded_up),
+            };
+            report(valid, || format!("clock: {:?}, now_block: {}, schedule: {:?}, rounding: {:?}, occurrence_ms: {}, block: {}", clock, now_block, schedule, rounding, occurrence_ms, block))
+        })
+    }
+    props(PROPS_TESTS, validate_next_occurrence_block as fn(u64, u32, u16, u8, u8) -> bool)
+}
+
+/// Catches up on past triggers in batches of at most `catch_up_limit`, resuming from the last trigger of every batch,
+/// as per uncapped `Calendar::next_occurrence_ms_with_past_triggers()`.
+#[test]
+fn test_capped_catch_up_vs_uncapped() {
+    fn validate_capped_catch_up(start_ms: u64, last_run_delta_ms: u64, now_delta_ms: u64, items: Vec<(u8, u8)>, times_of_day: Vec<(u8, u8)>, days_of_month: Vec<u8>, catch_up_limit: u8) -> bool {
+        let c = Calendar::create();
+        let start_ms = spread(start_ms, TIMESTAMP_GEN_SIZE as u64);
+        let last_run_ms = start_ms.saturating_sub(MS_IN_DAY) + spread(last_run_delta_ms, 400 * MS_IN_DAY);
+        let now_ms = last_run_ms + spread(now_delta_ms, 100 * MS_IN_DAY);
+        let freqs = [Frequency::Year, Frequency::Month, Frequency::Week, Frequency::Day, Frequency::Hour];
+        let items: Vec<_> = items.into_iter().take(3).map(|(freq, multiplier)| (freqs[freq as usize % 5], multiplier as u32 % 5 + 1)).collect();
+        let max_freq = items.iter().map(|(freq, _)| *freq).max().unwrap_or(Frequency::Year);
+        let schedule = Schedule {
+            start: c.from_unixtime(start_ms),
+            times_of_day: if max_freq <= Frequency::Day {
+                times_of_day.into_iter().take(MAX_TIMES_OF_DAY).map(|(hour, minute)| TimeOfDay { hour: hour % 24, minute: minute % 60 }).collect()
+            } else {
+                vec![]
+            },
+            days_of_month: if max_freq <= Frequency::Month { days_of_month.into_iter().take(MAX_DAYS_OF_MONTH).map(|day| day % 31 + 1).collect() } else { vec![] },
+            items,
+            ..Default::default()
+        };
+        let catch_up_limit = catch_up_limit as u32 % 20 + 1;
+        let now = c.from_unixtime(now_ms);
+        let (expected, expected_delta) = c.next_occurrence_ms_with_past_triggers(Some(&c.from_unixtime(last_run_ms)), &now, &schedule);
+
+        let (mut triggers, mut batches, mut last_run_ms) = (Vec::<u64>::new(), Vec::new(), last_run_ms);
+        let delta = loop {
+            let (batch, delta) = c.next_occurrence_ms_with_past_triggers_capped(Some(&c.from_unixtime(last_run_ms)), &now, &schedule, catch_up_limit);
+            triggers.extend(&batch);
+            batches.push(batch.len() as u32);
+            match (delta, batch.last()) {
+                // more due, at the limit
+                (Some(0), Some(&last_ms)) => last_run_ms = last_ms,
+                _ => break delta,
+            }
+        };
+        // full batches, other than the last
+        let valid = batches.iter().rev().skip(1).all(|&len| len == catch_up_limit) && batches.iter().all(|&len| len <= catch_up_limit);
+        report(valid && triggers == expected && delta == expected_delta, || format!("schedule: {:?}, catch up limit: {}, batches: {:?}, triggers: {}, expected: {}", schedule, catch_up_limit, batches, triggers.len(), expected.len()))
+    }
+    props(PROPS_TESTS, validate_capped_catch_up as fn(u64, u64, u64, Vec<(u8, u8)>, Vec<(u8, u8)>, Vec<u8>, u8) -> bool)
+}
+
+/// Pops due triggers of multiple jobs as per `Calendar::next_occurrence_ms_with_past_triggers()` of every job, ordered by trigger and job id.
+#[test]
+fn test_scheduler_queue_pop_due() {
+    fn validate_pop_due(now_ms: u64, jobs: Vec<(u8, u8, u32)>, window_ms: u32) -> bool {
+        let c = Calendar::create();
+        let now_ms = spread(now_ms, TIMESTAMP_GEN_SIZE as u64);
+        let due_ms = now_ms + spread(window_ms as u64, 2 * MS_IN_DAY);
+        let mut queue = SchedulerQueue::new();
+        let mut expected = Vec::new();
+        for (id, (freq, multiplier, start_offset_ms)) in jobs.into_iter().take(10).enumerate() {
+            let freq = [Frequency::Month, Frequency::Week, Frequency::Day, Frequency::Hour, Frequency::Minute][freq as usize % 5];
+            let schedule = Schedule { start: c.from_unixtime(now_ms + spread(start_offset_ms as u64, MS_IN_DAY)), items: vec![(freq, multiplier as u32 % 10 + 1)], ..Default::default() };
+            let (triggers, _) = c.next_occurrence_ms_with_past_triggers(Some(&c.from_unixtime(now_ms)), &c.from_unixtime(due_ms), &schedule);
+            expected.extend(triggers.into_iter().map(|trigger_ms| (trigger_ms, id)));
+            queue.insert(id, schedule, now_ms).unwrap();
+        }
+        expected.sort();
+        let due = queue.pop_due(due_ms);
+        let valid = due == expected && queue.peek().is_none_or(|(trigger_ms, _)| trigger_ms > due_ms);
+        report(valid, || format!("now_ms: {}, due_ms: {}, due: {:?}, expected: {:?}", now_ms, due_ms, due, expected))
+    }
+    props(PROPS_TESTS, validate_pop_due as fn(u64, Vec<(u8, u8, u32)>, u32) -> bool)
+}
+
+/// Restores `SchedulerState` after crashes between firing triggers and saving the state, with every trigger fired at least once,
+/// under the same sequence number, and no triggers of the saved state fired again.
+#[test]
+fn test_scheduler_state_crash_recovery() {
+    fn validate_crash_recovery(now_ms: u64, jobs: Vec<(u8, u8, u8)>, steps: Vec<(u32, u8, bool)>) -> bool {
+        let c = Calendar::create();
+        let mut now_ms = spread(now_ms, TIMESTAMP_GEN_SIZE as u64);
+        let mut state = SchedulerState::default();
+        let mut expected = std::collections::BTreeMap::new();
+        let steps = &steps[..steps.len().min(20)];
+        let end_ms = now_ms + steps.iter().map(|&(advance_ms, _, _)| spread(advance_ms as u64, 6 * MS_IN_HOUR)).sum::<u64>();
+        for (id, &(freq, multiplier, days_of_month)) in jobs.iter().take(10).enumerate() {
+            let freq = [Frequency::Month, Frequency::Week, Frequency::Day, Frequency::Hour][freq as usize % 4];
+            let schedule = Schedule {
+                start: c.from_unixtime(now_ms),
+                items: vec![(freq, multiplier as u32 % 10 + 1)],
+                days_of_month: if freq == Frequency::Month { (1..=31).filter(|day| days_of_month as u32 & (1 << (day % 8)) != 0).collect() } else { vec![] },
+                ..Default::default()
+            };
+            let (triggers, _) = c.next_occurrence_ms_with_past_triggers(Some(&c.from_unixtime(now_ms)), &c.from_unixtime(end_ms), &schedule);
+            expected.extend(triggers.into_iter().zip(1..).map(|(trigger_ms, seq)| ((id as u64, seq), trigger_ms)));
+            state.insert(id as u64, schedule, c.from_unixtime(now_ms)).unwrap();
+        }
+
+        let mut saved = state.to_bytes();
+        let mut fired = std::collections::BTreeMap::new();
+        let mut valid = true;
+        for &(advance_ms, fires, save) in steps {
+            now_ms += spread(advance_ms as u64, 6 * MS_IN_HOUR);
+            let due = state.due(&c.from_unixtime(now_ms));
+            for &(trigger_ms, id, seq) in due.iter().take(fires as usize) {
+                // fired again only if not saved, always as the same trigger
+                valid &= *fired.entry((id, seq)).or_insert(trigger_ms) == trigger_ms;
+                valid &= state.record_fire(id, trigger_ms);
+            }
+            if save || fires as usize >= due.len() {
+                saved = state.to_bytes();
+            } else {
+                // crash after firing, prior to saving
+                let restored = SchedulerState::from_bytes(&saved).unwrap();
+                valid &= restored.due(&c.from_unixtime(now_ms)).iter().all(|&(_, id, seq)| seq > restored.jobs()[&id].fire_count);
+                state = restored;
+            }
+        }
+        // recovering from the last crash
+        let state = SchedulerState::from_bytes(&saved).unwrap();
+        for (trigger_ms, id, seq) in state.due(&c.from_unixtime(now_ms)) {
+            valid &= *fired.entry((id, seq)).or_insert(trigger_ms) == trigger_ms;
+        }
+        valid &= fired == expected;
+        report(valid, || format!("jobs: {:?}, steps: {:?}, fired: {:?}, expected: {:?}", jobs, steps, fired, expected))
+    }
+    props(PROPS_TESTS, validate_crash_recovery as fn(u64, Vec<(u8, u8, u8)>, Vec<(u32, u8, bool)>) -> bool)
+}
+
+/// Advances `TimingWheel` as per `SchedulerQueue::pop_due()`, over steps of 1 ms up to decades, cascading through all levels.
+#[test]
+fn test_timing_wheel_vs_scheduler_queue() {
+    fn validate_timing_wheel(now_ms: u64, jobs: Vec<(u8, u8, u32, u32)>, steps: Vec<(u8, u64)>) -> bool {
+        let c = Calendar::create();
+        let now_ms = spread(now_ms, TIMESTAMP_GEN_SIZE as u64);
+        let mut wheel = TimingWheel::new(now_ms);
+        let mut queue = SchedulerQueue::new();
+        let mut keys = std::collections::BTreeMap::new();
+        for (id, &(freq, multiplier, start_offset_ms, end_offset)) in jobs.iter().take(10).enumerate() {
+            let period_ms = [1, MS_IN_SEC, MS_IN_MIN, MS_IN_HOUR, MS_IN_DAY, 7 * MS_IN_DAY, 31 * MS_IN_DAY, 366 * MS_IN_DAY];
+            let freq_index = freq as usize % 8;
+            let multiplier = multiplier as u32 % 10 + 1;
+            let start_ms = now_ms + spread(start_offset_ms as u64, MS_IN_DAY);
+            // bounded number of triggers
+            let end_ms = start_ms + spread(end_offset as u64, 100 * period_ms[freq_index] * multiplier as u64);
+            let schedule = Schedule {
+                start: c.from_unixtime(start_ms),
+                items: vec![([Frequency::Ms, Frequency::Second, Frequency::Minute, Frequency::Hour, Frequency::Day, Frequency::Week, Frequency::Month, Frequency::Year][freq_index], multiplier)],
+                end: Some(c.from_unixtime(end_ms)),
+                ..Default::default()
+            };
+            keys.insert(wheel.insert(id, schedule.clone()).unwrap(), id);
+            queue.insert(id, schedule, now_ms).unwrap();
+        }
+        let mut now_ms = now_ms;
+        steps.iter().take(20).all(|&(bits, step_ms)| {
+            now_ms += spread(step_ms, 1 << (bits % 40));
+            let due = wheel.advance(now_ms).into_iter().map(|(trigger_ms, key)| (trigger_ms, keys[&key])).collect::<Vec<_>>();
+            let mut expected = queue.pop_due(now_ms);
+            // ordered by key, rather than id, within the same ms
+            expected.sort_by_key(|&(trigger_ms, id)| (trigger_ms, keys.iter().find(|(_, &key_id)| key_id == id).map(|(&key, _)| key)));
+            let valid = due == expected && wheel.len() == queue.len() && wheel.now_ms() == now_ms;
+            report(valid, || format!("jobs: {:?}, now_ms: {}, due: {:?}, expected: {:?}", jobs, now_ms, due, expected))
+        })
+    }
+    props(PROPS_TESTS, validate_timing_wheel as fn(u64, Vec<(u8, u8, u32, u32)>, Vec<(u8, u64)>) -> bool)
+}
+
+/// Converts batches as per single conversions, for sorted runs of steps up to years, with steps back in time.
+#[test]
+fn test_batch_conversions_vs_single() {
+    fn validate_batch_conversions(start_ms: u64, steps: Vec<(u8, u64, bool)>) -> bool {
+        let c = Calendar::create();
+        let mut ts = spread(start_ms, TIMESTAMP_GEN_SIZE as u64);
+        let timestamps = steps.iter().map(|&(bits, step_ms, back)| {
+            let step_ms = spread(step_ms, 1 << (bits % 41));
+            ts = if back { ts.saturating_sub(step_ms) } else { (ts + step_ms) % TIMESTAMP_GEN_SIZE as u64 };
+            ts
+        }).collect::<Vec<_>>();
+        let mut datetimes = vec![DateTime::default(); timestamps.len()];
+        c.from_unixtime_batch(&timestamps, &mut datetimes);
+        let mut roundtrip = vec![0; timestamps.len()];
+        c.to_unixtime_batch(&datetimes, &mut roundtrip);
+        let expected = timestamps.iter().map(|&ts| c.from_unixtime(ts)).collect::<Vec<_>>();
+        let valid = datetimes == expected && roundtrip == timestamps;
+        report(valid, || format!("timestamps: {:?}, datetimes: {:?}, expected: {:?}", timestamps, datetimes, expected))
+    }
+    props(PROPS_TESTS, validate_batch_conversions as fn(u64, Vec<(u8, u64, bool)>) -> bool)
+}
@@ -0,0 +1,253 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+};
+
+/// Slots per level of `TimingWheel`, ie. 6 bits of ms per level.
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+/// Levels of `TimingWheel`, covering 2^48 ms, beyond the scope of `Calendar`.
+const LEVELS: usize = 8;
+const NIL: usize = usize::MAX;
+
+/// Key of a job of `TimingWheel`, invalidated on removal of the job.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WheelKey {
+    index:      usize,
+    generation: u64,
+}
+
+struct Entry<T> {
+    job:        T,
+    schedule:   Schedule,
+    trigger_ms: u64,
+    generation: u64,
+    level:      usize,
+    slot:       usize,
+    prev:       usize,
+    next:       usize,
+}
+
+/// Hierarchical timing wheel of `Schedule`s, bucketing jobs by next trigger in ms, for large numbers of schedules.
+///
+/// Every level splits the range of a slot of the level above into 64 slots, down to slots of 1 ms.
+/// Jobs are inserted and removed in O(1), into the lowest level spanning their next trigger,
+/// and cascaded down the levels as the wheel advances, until they trigger in the lowest level.
+/// Triggered jobs are re-bucketed by their next occurrence, jobs without further triggers are dropped.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let now_ms: u64 = 1650412800000;  // 20/04/2022 00:00:00:000
+/// let start = DateTime { year: 2022, month: 4, day: 20, hour: 0, minute: 0, second: 0, ms: 0 };
+/// let mut wheel = TimingWheel::new(now_ms);
+/// let minutely = wheel.insert("minutely", Schedule { start: start.clone(), items: vec![(Frequency::Minute, 1)], ..Default::default() }).unwrap();
+/// let hourly = wheel.insert("hourly", Schedule { start, items: vec![(Frequency::Hour, 1)], ..Default::default() }).unwrap();
+///
+/// let due = wheel.advance(now_ms + 60 * 60_000);
+/// assert_eq!(due.len(), 61);
+/// assert_eq!(due[59..], [(now_ms + 60 * 60_000, minutely), (now_ms + 60 * 60_000, hourly)]);
+/// assert_eq!(wheel.get(hourly), Some(&"hourly"));
+/// assert_eq!(wheel.next_trigger(hourly), Some(now_ms + 2 * 60 * 60_000));
+/// ```
+pub struct TimingWheel<T> {
+    calendar:        Calendar,
+    now_ms:          u64,
+    entries:         Vec<Option<Entry<T>>>,
+    free:            Vec<usize>,
+    next_generation: u64,
+    len:             usize,
+    heads:           [[usize; SLOTS]; LEVELS],
+    occupied:        [u64; LEVELS],
+}
+
+impl<T> TimingWheel<T> {
+    /// Constructor of an empty wheel at `now_ms`.
+    pub fn new(now_ms: u64) -> Self {
+        Self::with_calendar(now_ms, Calendar::create())
+    }
+
+    /// Constructor of an empty wheel at `now_ms`, validating `Schedule`s by `calendar`, eg. configured by `Calendar::with_cost_budget()`.
+    pub fn with_calendar(now_ms: u64, calendar: Calendar) -> Self {
+        Self {
+            calendar,
+            now_ms,
+            entries: Vec::new(),
+            free: Vec::new(),
+            next_generation: 0,
+            len: 0,
+            heads: [[NIL; SLOTS]; LEVELS],
+            occupied: [0; LEVELS],
+        }
+    }
+
+    /// Time the wheel has advanced to, in ms from epoch.
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    /// Number of jobs.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reserves capacity for at least `additional` more jobs.
+    pub fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional.saturating_sub(self.free.len()));
+    }
+
+    pub fn get(&self, key: WheelKey) -> Option<&T> {
+        self.entry(key).map(|entry| &entry.job)
+    }
+
+    /// Next trigger of job `key`, in ms from epoch.
+    pub fn next_trigger(&self, key: WheelKey) -> Option<u64> {
+        self.entry(key).map(|entry| entry.trigger_ms)
+    }
+
+    /// Inserts `job` triggering at the next occurrence of validated `schedule` after `TimingWheel::now_ms()`.
+    /// Jobs without such occurrence are dropped, with the returned key not found.
+    /// Fails with `ValidationError::OutOfScope` if the wheel advanced past the scope of `Calendar`.
+    pub fn insert(&mut self, job: T, schedule: Schedule) -> Result<WheelKey, ValidationError> {
+        self.calendar.validate_schedule(&schedule)?;
+        if self.now_ms >= YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1] {
+            return Err(ValidationError::OutOfScope);
+        }
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        let trigger_ms = match self.next_trigger_after(&schedule, self.now_ms) {
+            Some(trigger_ms) => trigger_ms,
+            None => return Ok(WheelKey { index: NIL, generation }),
+        };
+        let entry = Entry { job, schedule, trigger_ms, generation, level: 0, slot: 0, prev: NIL, next: NIL };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.entries[index] = Some(entry);
+                index
+            }
+            None => {
+                self.entries.push(Some(entry));
+                self.entries.len() - 1
+            }
+        };
+        self.len += 1;
+        self.link(index);
+        Ok(WheelKey { index, generation })
+    }
+
+    /// Removes job `key`, returning the job and its `Schedule`.
+    pub fn remove(&mut self, key: WheelKey) -> Option<(T, Schedule)> {
+        self.entry(key)?;
+        self.unlink(key.index);
+        Some(self.release(key.index))
+    }
+
+    /// Advances the wheel to `now_ms`, returning all triggers up to and including `now_ms`, ordered by trigger and key.
+    /// Jobs triggering multiple times up to `now_ms` are returned for every trigger.
+    pub fn advance(&mut self, now_ms: u64) -> Vec<(u64, WheelKey)> /* triggers_in_ms */ {
+        let mut due = Vec::new();
+        while let Some((level, slot, slot_ms)) = self.next_expiration().filter(|&(_, _, slot_ms)| slot_ms <= now_ms) {
+            self.now_ms = slot_ms;
+            let mut index = self.heads[level][slot];
+            self.heads[level][slot] = NIL;
+            self.occupied[level] &= !(1 << slot);
+            if level == 0 {
+                // all jobs of the slot trigger at `slot_ms`
+                let first = due.len();
+                while index != NIL {
+                    let entry = self.entries[index].as_ref().expect("linked entry");
+                    let next = entry.next;
+                    due.push((slot_ms, WheelKey { index, generation: entry.generation }));
+                    match self.next_trigger_after(&entry.schedule, slot_ms) {
+                        Some(trigger_ms) => {
+                            self.entries[index].as_mut().expect("linked entry").trigger_ms = trigger_ms;
+                            self.link(index);
+                        }
+                        None => {
+                            self.release(index);
+                        }
+                    }
+                    index = next;
+                }
+                due[first..].sort_unstable();
+            } else {
+                // cascade to lower levels
+                while index != NIL {
+                    let next = self.entries[index].as_ref().expect("linked entry").next;
+                    self.link(index);
+                    index = next;
+                }
+            }
+        }
+        self.now_ms = self.now_ms.max(now_ms);
+        due
+    }
+
+    fn entry(&self, key: WheelKey) -> Option<&Entry<T>> {
+        self.entries.get(key.index)?.as_ref().filter(|entry| entry.generation == key.generation)
+    }
+
+    /// Next occurrence of `schedule` after `after_ms`, if within scope of `Calendar`.
+    fn next_trigger_after(&self, schedule: &Schedule, after_ms: u64) -> Option<u64> {
+        let delta = self.calendar.next_occurrence_ms(&self.calendar.from_unixtime(after_ms), schedule)?;
+        Some(after_ms + delta).filter(|&ts| ts < YEAR_MS_OFFSETS[YEAR_MS_OFFSETS.len() - 1])
+    }
+
+    /// Earliest occupied slot, as (level, slot, start of slot in ms). Lower levels span triggers prior to higher levels.
+    fn next_expiration(&self) -> Option<(usize, usize, u64)> {
+        (0..LEVELS).find_map(|level| {
+            let shift = level as u32 * SLOT_BITS;
+            let current = (self.now_ms >> shift) as usize % SLOTS;
+            // slots at or after the current slot, as slots of a level never wrap
+            let occupied = self.occupied[level] & (u64::MAX << current);
+            (occupied != 0).then(|| {
+                let slot = occupied.trailing_zeros() as usize;
+                let level_start_ms = self.now_ms >> (shift + SLOT_BITS) << (shift + SLOT_BITS);
+                (level, slot, level_start_ms + ((slot as u64) << shift))
+            })
+        })
+    }
+
+    /// Links entry into the slot of its trigger, at the level of the highest ms bits differing from `now_ms`.
+    fn link(&mut self, index: usize) {
+        let now_ms = self.now_ms;
+        let entry = self.entries[index].as_mut().expect("linked entry");
+        let significant = 63 - ((entry.trigger_ms ^ now_ms) | (SLOTS as u64 - 1)).leading_zeros();
+        let level = significant as usize / SLOT_BITS as usize;
+        let slot = (entry.trigger_ms >> (level as u32 * SLOT_BITS)) as usize % SLOTS;
+        let head = self.heads[level][slot];
+        (entry.level, entry.slot, entry.prev, entry.next) = (level, slot, NIL, head);
+        if head != NIL {
+            self.entries[head].as_mut().expect("linked entry").prev = index;
+        }
+        self.heads[level][slot] = index;
+        self.occupied[level] |= 1 << slot;
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let entry = self.entries[index].as_ref().expect("linked entry");
+        let (level, slot, prev, next) = (entry.level, entry.slot, entry.prev, entry.next);
+        match prev {
+            NIL => self.heads[level][slot] = next,
+            prev => self.entries[prev].as_mut().expect("linked entry").next = next,
+        }
+        if next != NIL {
+            self.entries[next].as_mut().expect("linked entry").prev = prev;
+        }
+        if self.heads[level][slot] == NIL {
+            self.occupied[level] &= !(1 << slot);
+        }
+    }
+
+    fn release(&mut self, index: usize) -> (T, Schedule) {
+        let entry = self.entries[index].take().expect("linked entry");
+        self.free.push(index);
+        self.len -= 1;
+        (entry.job, entry.schedule)
+    }
+}
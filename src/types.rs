@@ -1,9 +1,12 @@
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
+use core::fmt;
 #[cfg(feature = "scale")]
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 #[cfg(feature = "scale")]
 use scale_info::TypeInfo;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use super::constants::*;
 
@@ -18,8 +21,10 @@ use super::constants::*;
 /// 
 /// Note: other values will be accepted, but will be classified invalid by the calendar, and if used,
 /// appropriate values will be added on top, eg. 32/01 -> 01/02.
+///
+/// With `serde` feature, serialized as ISO 8601 string in human readable formats, eg. `2022-04-30T10:20:30.400`, and as ms from epoch otherwise.
 #[derive(Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
-#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
 pub struct DateTime {
     // date
     pub year:   u16,
@@ -44,18 +49,219 @@ impl DateTime {
     }
 }
 
+/// Formats as ISO 8601, eg. 2022-04-30T10:20:30.400
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}", self.year, self.month, self.day, self.hour, self.minute, self.second, self.ms)
+    }
+}
+
+/// `DateTime` in local wall time of a fixed UTC offset, eg. +05:30 is represented by `offset_minutes` of 330.
+/// Valid offsets are within [-18:00, +18:00], see `MAX_OFFSET_MINUTES`.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
+pub struct OffsetDateTime {
+    pub datetime:       DateTime,
+    pub offset_minutes: i16,
+}
+
+/// Max absolute value of `OffsetDateTime.offset_minutes`.
+pub const MAX_OFFSET_MINUTES: i16 = 18 * 60;
+
+/// Formats as ISO 8601 with offset, eg. 2022-04-30T10:20:30.400+05:30, or 2022-04-30T10:20:30.400Z for UTC
+impl fmt::Display for OffsetDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.datetime)?;
+        if self.offset_minutes == 0 {
+            write!(f, "Z")
+        } else {
+            let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+            let offset = self.offset_minutes.unsigned_abs();
+            write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+        }
+    }
+}
+
 /// Schedule, represented by a `start` `DateTime`, optional `end` `DateTime`, and multiple pairs of (`Frequency`, `multiplier`).
 /// Next occurrence of trigger time is calculated by taking the earliest occurrence of `Frequency` * `multiplier`, from `start`, but before `end`.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Optionally, every period of a `Frequency` can be expanded into multiple triggers:
+/// - `times_of_day` replaces hour/minute of `start` (for `Year`, `Month`, `Week`, `Day` frequencies), eg. daily at 09:00, 13:30 and 18:00
+/// - `days_of_month` replaces day of `start` (for `Year`, `Month` frequencies), eg. monthly on 1st and 15th, skipping months without such day
+///
+/// When expanded, only triggers at or after `start` are considered. Both lists are bounded by `MAX_TIMES_OF_DAY` and `MAX_DAYS_OF_MONTH`,
+/// as checked by `Calendar::validate_schedule()` only.
+///
+/// With `serde` feature, serialized with all fields, eg. in JSON
+/// `{"start":"2022-04-30T00:00:00.000","items":[["week",2]],"end":null,"times_of_day":["09:00"],"days_of_month":[]}`, see `DateTime`, `Frequency` and `TimeOfDay`.
+/// When deserializing, `end`, `times_of_day` and `days_of_month` are optional, and the result is validated by `Calendar::validate_schedule()`.
+///
+/// With `scale` feature, encoded with all fields. Note: as of 0.2.0, `Schedule`s encoded by 0.1.x don't decode as `Schedule`, but as `ScheduleV1`.
+/// The lists encode as `Vec`s, without bound on the length of the encoding, so decoded `Schedule`s are to be checked by `Calendar::validate_schedule()`.
+/// Storage requiring `MaxEncodedLen` is bounded by `CompactSchedule` instead.
+#[derive(Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Schedule {
     pub start: DateTime,
     pub items: Vec<(Frequency, u32)>,  // frequency with multiplier
     pub end: Option<DateTime>,
+    pub times_of_day: Vec<TimeOfDay>,
+    pub days_of_month: Vec<u8>,
+}
+
+/// Max number of `Schedule.times_of_day`, as checked by `Calendar::validate_schedule()`.
+pub const MAX_TIMES_OF_DAY: usize = 24;
+/// Max number of `Schedule.days_of_month`, as checked by `Calendar::validate_schedule()`.
+pub const MAX_DAYS_OF_MONTH: usize = 31;
+
+impl Schedule {
+    /// Human readable description, eg. `every 2 weeks and every 3 days starting 2022-04-30 00:00:00, until 2025-04-30`.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let schedule = Schedule {
+    ///     start: DateTime { year: 2022, month: 4, day: 30, hour: 0, minute: 0, second: 0, ms: 0 },
+    ///     items: vec![(Frequency::Month, 1)],
+    ///     days_of_month: vec![1, 15],
+    ///     times_of_day: vec![TimeOfDay { hour: 9, minute: 0 }, TimeOfDay { hour: 18, minute: 30 }],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(schedule.describe().to_string(), "every month on days 1 and 15 at 09:00 and 18:30 starting 2022-04-30 00:00:00");
+    /// ```
+    pub fn describe(&self) -> ScheduleDescription<'_> {
+        ScheduleDescription(self)
+    }
+}
+
+/// Human readable description of a `Schedule`, see `Schedule::describe()`.
+pub struct ScheduleDescription<'a>(&'a Schedule);
+
+impl fmt::Display for ScheduleDescription<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let schedule = self.0;
+        if schedule.items.is_empty() {
+            write!(f, "once at ")?;
+            return write_human_datetime(f, &schedule.start, false);
+        }
+        write_list(f, schedule.items.iter().map(|(freq, multiplier)| EveryFrequency(*freq, *multiplier)))?;
+        if !schedule.days_of_month.is_empty() {
+            write!(f, " on {} ", if schedule.days_of_month.len() == 1 { "day" } else { "days" })?;
+            write_list(f, schedule.days_of_month.iter())?;
+        }
+        if !schedule.times_of_day.is_empty() {
+            write!(f, " at ")?;
+            write_list(f, schedule.times_of_day.iter())?;
+        }
+        write!(f, " starting ")?;
+        write_human_datetime(f, &schedule.start, false)?;
+        if let Some(end) = &schedule.end {
+            write!(f, ", until ")?;
+            write_human_datetime(f, end, true)?;
+        }
+        Ok(())
+    }
+}
+
+/// Frequency with multiplier, eg. `every 2 weeks`, or `every day`.
+struct EveryFrequency(Frequency, u32);
+
+impl fmt::Display for EveryFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.0 {
+            Frequency::Year   => "year",
+            Frequency::Month  => "month",
+            Frequency::Week   => "week",
+            Frequency::Day    => "day",
+            Frequency::Hour   => "hour",
+            Frequency::Minute => "minute",
+            Frequency::Second => "second",
+            Frequency::Ms     => "millisecond",
+        };
+        match self.1 {
+            1 => write!(f, "every {}", name),
+            multiplier => write!(f, "every {} {}s", multiplier, name),
+        }
+    }
+}
+
+/// Writes items separated by commas, with the last one separated by `and`, eg. `1, 2 and 3`.
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: impl ExactSizeIterator<Item = T>) -> fmt::Result {
+    let len = items.len();
+    for (i, item) in items.enumerate() {
+        match i {
+            0 => (),
+            i if i == len - 1 => write!(f, " and ")?,
+            _ => write!(f, ", ")?,
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Writes `DateTime` as eg. `2022-04-30 10:20:30`, with ms only if set, optionally omitting midnight time.
+fn write_human_datetime(f: &mut fmt::Formatter<'_>, dt: &DateTime, omit_midnight: bool) -> fmt::Result {
+    write!(f, "{:04}-{:02}-{:02}", dt.year, dt.month, dt.day)?;
+    let is_midnight = dt.hour == 0 && dt.minute == 0 && dt.second == 0 && dt.ms == 0;
+    if !(omit_midnight && is_midnight) {
+        write!(f, " {:02}:{:02}:{:02}", dt.hour, dt.minute, dt.second)?;
+        if dt.ms != 0 {
+            write!(f, ".{:03}", dt.ms)?;
+        }
+    }
+    Ok(())
+}
+
+/// Time of day used for expansion of `Schedule` periods. Valid values are:
+/// - hour:   [0: 23]
+/// - minute: [0, 59]
+///
+/// With `serde` feature, serialized as `HH:MM` string in human readable formats, eg. `09:30`, and as (`hour`, `minute`) tuple otherwise.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
+pub struct TimeOfDay {
+    pub hour:   u8,
+    pub minute: u8,
+}
+
+/// Formats as `HH:MM`, eg. 09:30
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
 }
 
+/// ISO 8601 duration, eg. `P1Y2M3DT4H` or `PT0.5S`, see `Calendar::add_duration()`. Named apart from `core::time::Duration`, as exported by the prelude.
+/// Years and months are added as per calendar, remaining components as fixed number of ms.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
+pub struct IsoDuration {
+    // calendar
+    pub years:   u32,
+    pub months:  u32,
+
+    // fixed
+    pub weeks:   u32,
+    pub days:    u32,
+    pub hours:   u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub ms:      u32,
+}
+
+/// ISO 8601 repeating interval of `start` and `duration`, eg. `R5/2022-01-01T00:00:00Z/P1M`, repeating indefinitely if `repetitions` are `None`.
+/// `start` is in UTC, see `Calendar::schedule_from_repeating_interval()` for conversion to `Schedule`.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
+pub struct RepeatingInterval {
+    pub repetitions: Option<u32>,
+    pub start:       DateTime,
+    pub duration:    IsoDuration,
+}
+
+/// Unit of `Schedule` repetition. With `serde` feature, serialized as lowercase names, eg. `week`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo, MaxEncodedLen))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
 #[repr(u8)]
 pub enum Frequency {
     Year,
@@ -89,5 +295,75 @@ pub enum ValidationError {
     /// `DateTime` not covered by this library, eg. 01/01/1000 00:00:00:000, 01/01/5000 00:00:00:000
     OutOfScope,
     /// Invalid `DateTime`, eg. 32/13/2000 66:66:66:6666, 29/02/2021 10:10:10:000 (non leap year)
-    Invalid
+    Invalid,
+    /// `Schedule` with `Calendar::cost_bound()` exceeding the budget of `Calendar::with_cost_budget()`
+    OverBudget,
+}
+
+/// Error of parsing text or binary input, at byte `position` of the input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub position: usize,
+    pub kind:     ParseErrorKind,
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseErrorKind {
+    /// Unexpected character, eg. `X` in `CET-1X`
+    Unexpected,
+    /// Input ended prematurely, eg. `CET-1CEST,M3.5`
+    TooShort,
+    /// Number out of range, eg. `M13.5.0`
+    OutOfRange,
+    /// Value not supported by this library, eg. offsets with seconds
+    Unsupported,
+    /// Unknown or incomplete specifier of a format string, eg. `%Q`, at byte `position` of the input matched up to it
+    InvalidSpecifier,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ParseErrorKind::Unexpected       => "unexpected input",
+            ParseErrorKind::TooShort         => "premature end of input",
+            ParseErrorKind::OutOfRange       => "value out of range",
+            ParseErrorKind::Unsupported      => "unsupported value",
+            ParseErrorKind::InvalidSpecifier => "invalid specifier",
+        };
+        write!(f, "{} at position {}", description, self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Error of formatting a `DateTime`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FormatError {
+    /// Unknown or incomplete specifier at byte `fmt_position` of the format string, eg. `%Q`, or trailing `%`
+    InvalidSpecifier { fmt_position: usize },
+    /// `DateTime` classified invalid or out of scope by the calendar
+    InvalidDateTime,
+    /// Failure of the underlying writer
+    Write,
+}
+
+impl From<fmt::Error> for FormatError {
+    fn from(_: fmt::Error) -> Self {
+        FormatError::Write
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::InvalidSpecifier { fmt_position } => write!(f, "invalid specifier at position {} of format string", fmt_position),
+            FormatError::InvalidDateTime => write!(f, "invalid datetime"),
+            FormatError::Write => write!(f, "failed to write"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FormatError {}
+
@@ -0,0 +1,316 @@
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+    utils::*,
+};
+#[cfg(feature = "tz")]
+use super::tz_data::ZONES;
+
+/// UTC offset in effect at a given instant, and whether it is due to daylight saving time.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct UtcOffset {
+    pub offset_minutes: i16,
+    pub is_dst:         bool,
+}
+
+/// Source of UTC offsets for any instant within the scope of the `Calendar`.
+pub trait TimeZone {
+    /// Finds the UTC offset in effect at ms from epoch (UTC).
+    fn offset_at(&self, calendar: &Calendar, ts: u64) -> UtcOffset;
+
+    /// Finds the first ms from epoch (UTC) after `ts` at which the UTC offset changes, `None` if it never changes again.
+    fn next_transition(&self, calendar: &Calendar, ts: u64) -> Option<u64>;
+}
+
+/// Resolution of local wall time that doesn't exist, ie. falls into a gap when clocks go forward.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum GapPolicy {
+    /// Shift forward by the length of the gap, eg. 01:30 becomes 02:30 when clocks go forward at 01:00 by 1 hour.
+    #[default]
+    ShiftForward,
+    /// Skip the occurrence.
+    Skip,
+}
+
+/// Resolution of local wall time that occurs twice, ie. falls into an overlap when clocks go back.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Trigger at the first occurrence, ie. before clocks go back.
+    #[default]
+    First,
+    /// Trigger at the second occurrence, ie. after clocks go back.
+    Second,
+    /// Trigger at both occurrences.
+    Both,
+}
+
+/// Policies for evaluation of local wall time around daylight saving time transitions.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct LocalTimePolicy {
+    pub gap:     GapPolicy,
+    pub overlap: OverlapPolicy,
+}
+
+/// Day of the year on which a daylight saving time transition occurs.
+/// Fields out of range, eg. of variants constructed without `TzRule::from_posix()`, are clamped to the nearest valid value,
+/// while `weekday` is taken modulo 7.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionDay {
+    /// `weekday` (0 = Sunday) of the `week` (1..=5, 5 = last) of the `month` (1..=12), eg. last Sunday of March.
+    MonthWeekday { month: u8, week: u8, weekday: u8 },
+    /// Day of the year (1..=365), never counting 29th of February, eg. 60 is always 1st of March.
+    Julian { day: u16 },
+    /// Zero based day of the year (0..=365), counting 29th of February in leap years.
+    DayOfYear { day: u16 },
+}
+
+/// Daylight saving time, with transition times in seconds of local time, ie. `start` in standard time, `end` in daylight saving time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DstRule {
+    pub offset_minutes:     i16,
+    pub start:              TransitionDay,
+    pub start_time_seconds: i32,
+    pub end:                TransitionDay,
+    pub end_time_seconds:   i32,
+}
+
+/// Rule of a time zone, represented by a standard UTC offset, and optional daylight saving time, eg. `CET-1CEST,M3.5.0,M10.5.0/3`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TzRule {
+    pub std_offset_minutes: i16,
+    pub dst:                Option<DstRule>,
+}
+
+/// Change of UTC offset, in effect from `at_ms` ms from epoch (UTC).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub at_ms:  u64,
+    pub offset: UtcOffset,
+}
+
+/// IANA time zone, represented by historical `transitions`, and the `rule` in effect from the last of the `transitions`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Zone {
+    pub name:        &'static str,
+    pub transitions: &'static [Transition],
+    pub rule:        TzRule,
+}
+
+#[cfg(feature = "tz")]
+impl Zone {
+    /// Finds an embedded zone by its IANA name, eg. `Europe/London`. Only zones of enabled `tz-*` features are available.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// assert_eq!(Zone::by_name("UTC").map(|z| z.name), Some("UTC"));
+    /// assert_eq!(Zone::by_name("Mars/Olympus_Mons"), None);
+    /// ```
+    pub fn by_name(name: &str) -> Option<&'static Zone> {
+        ZONES.binary_search_by(|zone| zone.name.cmp(name)).ok().map(|i| &ZONES[i])
+    }
+
+    /// All embedded zones, sorted by name.
+    pub fn all() -> &'static [Zone] {
+        ZONES
+    }
+}
+
+impl TimeZone for Zone {
+    fn offset_at(&self, calendar: &Calendar, ts: u64) -> UtcOffset {
+        // transitions start at epoch, the last one marks the start of the rule
+        match self.transitions.partition_point(|t| t.at_ms <= ts) {
+            i if i > 0 && i < self.transitions.len() => self.transitions[i - 1].offset,
+            _ => self.rule.offset_at(calendar, ts),
+        }
+    }
+
+    fn next_transition(&self, calendar: &Calendar, ts: u64) -> Option<u64> {
+        match self.transitions.partition_point(|t| t.at_ms <= ts) {
+            i if i < self.transitions.len() => Some(self.transitions[i].at_ms),
+            _ => self.rule.next_transition(calendar, ts),
+        }
+    }
+}
+
+impl TzRule {
+    /// Finds the month and day of month of a `TransitionDay` in a given year.
+    fn month_and_day(calendar: &Calendar, year: u16, day: &TransitionDay) -> (u8, u8) {
+        match *day {
+            TransitionDay::MonthWeekday { month, week, weekday } => {
+                let (month, week, weekday) = (month.clamp(1, 12), week.clamp(1, 5), weekday % 7);
+                let first_of_month_ms = calendar.to_unixtime(&DateTime { year, month, day: 1, hour: 0, minute: 0, second: 0, ms: 0 });
+                let first_weekday = ((first_of_month_ms / MS_IN_DAY + EPOCH_WEEKDAY) % 7) as u8;
+                let mut day = 1 + (weekday + 7 - first_weekday) % 7 + (week - 1) * 7;
+                while day > days_in_month(year, month) {
+                    day -= 7;
+                }
+                (month, day)
+            }
+            TransitionDay::Julian { day } => Self::month_and_day_of_year(MONTH_FOR_NON_LEAP_YEAR, day.clamp(1, 365)),
+            TransitionDay::DayOfYear { day } => {
+                let days_in_months = if is_leap_year(year) { &MONTH_FOR_LEAP_YEAR } else { &MONTH_FOR_NON_LEAP_YEAR };
+                Self::month_and_day_of_year(days_in_months, day.min(365) + 1)
+            }
+        }
+    }
+
+    /// Finds the month and day of month of 1 based day of the year, overflowing into December for days beyond the year.
+    fn month_and_day_of_year(days_in_months: &[u8], mut day: u16) -> (u8, u8) {
+        for (month, &days) in days_in_months.iter().enumerate().take(11) {
+            if day <= days as u16 {
+                return (month as u8 + 1, day as u8);
+            }
+            day -= days as u16;
+        }
+        (12, day as u8)
+    }
+
+    /// Finds ms from epoch (UTC) of a transition in a given year, with time in local time of `offset_minutes`.
+    fn transition_ms(calendar: &Calendar, year: u16, day: &TransitionDay, time_seconds: i32, offset_minutes: i16) -> i64 {
+        let (month, day) = Self::month_and_day(calendar, year, day);
+        let date_ms = calendar.to_unixtime(&DateTime { year, month, day, hour: 0, minute: 0, second: 0, ms: 0 });
+        date_ms as i64 + time_seconds as i64 * MS_IN_SEC as i64 - offset_minutes as i64 * MS_IN_MIN as i64
+    }
+}
+
+impl TimeZone for TzRule {
+    fn offset_at(&self, calendar: &Calendar, ts: u64) -> UtcOffset {
+        let std = UtcOffset { offset_minutes: self.std_offset_minutes, is_dst: false };
+        match &self.dst {
+            None => std,
+            Some(dst) => {
+                let year = calendar.from_unixtime(ts).year;
+                let start_ms = Self::transition_ms(calendar, year, &dst.start, dst.start_time_seconds, self.std_offset_minutes);
+                let end_ms = Self::transition_ms(calendar, year, &dst.end, dst.end_time_seconds, dst.offset_minutes);
+                let ts = ts as i64;
+                // southern hemisphere starts dst towards the end of the year
+                let is_dst = if start_ms < end_ms {
+                    start_ms <= ts && ts < end_ms
+                } else {
+                    ts < end_ms || start_ms <= ts
+                };
+                if is_dst {
+                    UtcOffset { offset_minutes: dst.offset_minutes, is_dst: true }
+                } else {
+                    std
+                }
+            }
+        }
+    }
+
+    fn next_transition(&self, calendar: &Calendar, ts: u64) -> Option<u64> {
+        let dst = self.dst.as_ref()?;
+        let year = calendar.from_unixtime(ts).year;
+        (year..=year + 1)
+            .filter(|&year| calendar.validate_datetime(&DateTime { year, month: 1, day: 1, hour: 0, minute: 0, second: 0, ms: 0 }).is_ok())
+            .flat_map(|year| [
+                Self::transition_ms(calendar, year, &dst.start, dst.start_time_seconds, self.std_offset_minutes),
+                Self::transition_ms(calendar, year, &dst.end, dst.end_time_seconds, dst.offset_minutes),
+            ])
+            .filter(|&transition_ms| transition_ms > ts as i64)
+            .min()
+            .map(|transition_ms| transition_ms as u64)
+    }
+}
+
+impl Calendar {
+    /// Converts ms from epoch (UTC) to `OffsetDateTime` in local wall time of the `zone`.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let c = Calendar::create();
+    /// let utc = TzRule::from_posix("UTC0").unwrap();
+    /// assert_eq!(c.to_local(1286705410010, &utc), OffsetDateTime { datetime: c.from_unixtime(1286705410010), offset_minutes: 0 });
+    /// ```
+    pub fn to_local(&self, ts: u64, zone: &impl TimeZone) -> OffsetDateTime {
+        self.from_unixtime_with_offset(ts, zone.offset_at(self, ts).offset_minutes)
+    }
+
+    /// Converts a `&DateTime` in local wall time of the `zone` to ms from epoch (UTC).
+    /// Ambiguous local time (clocks going back) resolves to the earlier instant,
+    /// nonexistent local time (clocks going forward) is shifted forward by the length of the gap.
+    /// Note: may panic if invalid `DateTime` specified.
+    pub fn from_local(&self, dt: &DateTime, zone: &impl TimeZone) -> u64 {
+        let local_ms = self.to_unixtime(dt);
+        // offsets never change more than once within a couple of days
+        let before = zone.offset_at(self, local_ms.saturating_sub(MS_IN_DAY)).offset_minutes;
+        let after = zone.offset_at(self, local_ms + MS_IN_DAY).offset_minutes;
+        let candidate = |offset_minutes: i16| shift_by_offset(local_ms, -offset_minutes)
+            .filter(|&ts| zone.offset_at(self, ts).offset_minutes == offset_minutes);
+        match (candidate(before), candidate(after)) {
+            (Some(ts_before), Some(ts_after)) => ts_before.min(ts_after),
+            (Some(ts), None) | (None, Some(ts)) => ts,
+            (None, None) => shift_by_offset(local_ms, -before).expect("failed to calc utc time"),
+        }
+    }
+
+    /// Given a `now` (UTC) `DateTime` and `Schedule` in local wall time of the `zone`, finds ms delta when the next occurrence should trigger.
+    /// Occurrences falling into daylight saving time transitions are resolved as per `policy`.
+    /// If cut of by `Schedule.end`, or `now` falls out of scope in local wall time, returns a `None`.
+    pub fn next_occurrence_ms_in_zone(&self, now: &DateTime, zone: &impl TimeZone, policy: &LocalTimePolicy, schedule: &Schedule) -> Option<u64> /* delta_in_ms */ {
+        let now_ms = self.to_unixtime(now);
+        // first occurrence in local wall time at or after `from_ms`
+        let next_local = |from_ms: u64| -> Option<u64> {
+            let from_ms = from_ms.saturating_sub(1);
+            self.next_occurrence_ms(&self.from_unixtime(from_ms), schedule).map(|delta| from_ms + delta)
+        };
+
+        // walk segments of constant offset, from the one containing `now`
+        let mut segment_start_ms = now_ms;
+        let mut offset = zone.offset_at(self, now_ms).offset_minutes;
+        let mut local_from_ms = shift_by_offset(now_ms, offset)? + 1;
+        // previous segment, if ended within the last day, ie. potentially overlapping with the current one
+        let day_ago_ms = now_ms.saturating_sub(MS_IN_DAY);
+        let day_ago_offset = zone.offset_at(self, day_ago_ms).offset_minutes;
+        let mut prev_segment = zone.next_transition(self, day_ago_ms)
+            .filter(|&transition_ms| transition_ms <= now_ms && day_ago_offset != offset)
+            .map(|transition_ms| (transition_ms, day_ago_offset));
+        let mut gap_trigger: Option<u64> = None;
+
+        loop {
+            let segment_end = zone.next_transition(self, segment_start_ms)
+                .map(|transition_ms| (transition_ms, zone.offset_at(self, transition_ms).offset_minutes));
+
+            // overlap at the start of the segment repeats local times of the previous segment
+            if let Some((transition_ms, prev_offset)) = prev_segment {
+                if policy.overlap == OverlapPolicy::First && prev_offset > offset {
+                    local_from_ms = local_from_ms.max(shift_by_offset(transition_ms, prev_offset)?);
+                }
+            }
+            // overlap at the end of the segment is repeated by local times of the next segment
+            let local_to_ms = match segment_end {
+                Some((transition_ms, next_offset)) if policy.overlap == OverlapPolicy::Second && next_offset < offset => shift_by_offset(transition_ms, next_offset),
+                Some((transition_ms, _)) => shift_by_offset(transition_ms, offset),
+                None => None,
+            };
+
+            let next_local_ms = next_local(local_from_ms);
+            let trigger = next_local_ms
+                .filter(|&local_ms| local_to_ms.is_none_or(|to_ms| local_ms < to_ms))
+                .and_then(|local_ms| shift_by_offset(local_ms, -offset));
+            // triggers shifted out of the gap fall after the end of the previous segment, hence compete with the current one
+            let trigger = match (trigger, gap_trigger) {
+                (Some(trigger), Some(gap_trigger)) => Some(trigger.min(gap_trigger)),
+                (trigger, gap_trigger) => trigger.or(gap_trigger),
+            };
+            if let Some(trigger_ms) = trigger {
+                return Some(trigger_ms - now_ms);
+            } else if next_local_ms.is_none() {
+                return None;
+            }
+
+            let (transition_ms, next_offset) = segment_end?;
+            // gap at the end of the segment skips local times, that either get shifted forward or skipped
+            if policy.gap == GapPolicy::ShiftForward && next_offset > offset {
+                let gap_from_ms = shift_by_offset(transition_ms, offset)?;
+                let gap_to_ms = shift_by_offset(transition_ms, next_offset)?;
+                gap_trigger = next_local(gap_from_ms)
+                    .filter(|&local_ms| local_ms < gap_to_ms)
+                    .and_then(|local_ms| shift_by_offset(local_ms, -offset));
+            }
+            prev_segment = Some((transition_ms, offset));
+            segment_start_ms = transition_ms;
+            offset = next_offset;
+            local_from_ms = shift_by_offset(transition_ms, offset)?;
+        }
+    }
+}
@@ -0,0 +1,545 @@
+//! Embedded subset of the IANA tz database 2025b, generated by `scripts/gen_tz_data.py` (and subsequently safeguarded by tests_props.rs).
+use super::tz::*;
+
+#[allow(dead_code)]
+const fn t(at_ms: u64, offset_minutes: i16, is_dst: bool) -> Transition {
+    Transition { at_ms, offset: UtcOffset { offset_minutes, is_dst } }
+}
+
+#[allow(dead_code)]
+const fn mwd(month: u8, week: u8, weekday: u8) -> TransitionDay {
+    TransitionDay::MonthWeekday { month, week, weekday }
+}
+
+pub(crate) static ZONES: &[Zone] = &[
+    #[cfg(feature = "tz-africa")]
+    AFRICA_CAIRO,
+    #[cfg(feature = "tz-africa")]
+    AFRICA_JOHANNESBURG,
+    #[cfg(feature = "tz-africa")]
+    AFRICA_LAGOS,
+    #[cfg(feature = "tz-africa")]
+    AFRICA_NAIROBI,
+    #[cfg(feature = "tz-america")]
+    AMERICA_CHICAGO,
+    #[cfg(feature = "tz-america")]
+    AMERICA_DENVER,
+    #[cfg(feature = "tz-america")]
+    AMERICA_LOS_ANGELES,
+    #[cfg(feature = "tz-america")]
+    AMERICA_MEXICO_CITY,
+    #[cfg(feature = "tz-america")]
+    AMERICA_NEW_YORK,
+    #[cfg(feature = "tz-america")]
+    AMERICA_PHOENIX,
+    #[cfg(feature = "tz-america")]
+    AMERICA_SAO_PAULO,
+    #[cfg(feature = "tz-america")]
+    AMERICA_TORONTO,
+    #[cfg(feature = "tz-asia")]
+    ASIA_DUBAI,
+    #[cfg(feature = "tz-asia")]
+    ASIA_HONG_KONG,
+    #[cfg(feature = "tz-asia")]
+    ASIA_KOLKATA,
+    #[cfg(feature = "tz-asia")]
+    ASIA_SHANGHAI,
+    #[cfg(feature = "tz-asia")]
+    ASIA_SINGAPORE,
+    #[cfg(feature = "tz-asia")]
+    ASIA_TOKYO,
+    #[cfg(feature = "tz-oceania")]
+    AUSTRALIA_MELBOURNE,
+    #[cfg(feature = "tz-oceania")]
+    AUSTRALIA_PERTH,
+    #[cfg(feature = "tz-oceania")]
+    AUSTRALIA_SYDNEY,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_AMSTERDAM,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_BERLIN,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_LONDON,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_MADRID,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_MOSCOW,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_PARIS,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_ROME,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_WARSAW,
+    #[cfg(feature = "tz-europe")]
+    EUROPE_ZURICH,
+    #[cfg(feature = "tz-oceania")]
+    PACIFIC_AUCKLAND,
+    UTC,
+];
+
+#[cfg(feature = "tz-africa")]
+const AFRICA_CAIRO: Zone = Zone {
+    name: "Africa/Cairo",
+    transitions: &[
+        t(0, 120, false), t(10364400000, 180, true), t(23587200000, 120, false), t(41900400000, 180, true), t(55123200000, 120, false), t(73522800000, 180, true),
+        t(86745600000, 120, false), t(105058800000, 180, true), t(118281600000, 120, false), t(136594800000, 180, true), t(149817600000, 120, false), t(168130800000, 180, true),
+        t(181353600000, 120, false), t(199753200000, 180, true), t(212976000000, 120, false), t(231289200000, 180, true), t(244512000000, 120, false), t(262825200000, 180, true),
+        t(276048000000, 120, false), t(294361200000, 180, true), t(307584000000, 120, false), t(325983600000, 180, true), t(339206400000, 120, false), t(357519600000, 180, true),
+        t(370742400000, 120, false), t(396399600000, 180, true), t(402278400000, 120, false), t(426812400000, 180, true), t(433814400000, 120, false), t(452214000000, 180, true),
+        t(465436800000, 120, false), t(483750000000, 180, true), t(496972800000, 120, false), t(515286000000, 180, true), t(528508800000, 120, false), t(546822000000, 180, true),
+        t(560044800000, 120, false), t(578444400000, 180, true), t(591667200000, 120, false), t(610412400000, 180, true), t(623203200000, 120, false), t(641516400000, 180, true),
+        t(654739200000, 120, false), t(673052400000, 180, true), t(686275200000, 120, false), t(704674800000, 180, true), t(717897600000, 120, false), t(736210800000, 180, true),
+        t(749433600000, 120, false), t(767746800000, 180, true), t(780969600000, 120, false), t(799020000000, 180, true), t(812322000000, 120, false), t(830469600000, 180, true),
+        t(843771600000, 120, false), t(861919200000, 180, true), t(875221200000, 120, false), t(893368800000, 180, true), t(906670800000, 120, false), t(925423200000, 180, true),
+        t(938725200000, 120, false), t(956872800000, 180, true), t(970174800000, 120, false), t(988322400000, 180, true), t(1001624400000, 120, false), t(1019772000000, 180, true),
+        t(1033074000000, 120, false), t(1051221600000, 180, true), t(1064523600000, 120, false), t(1083276000000, 180, true), t(1096578000000, 120, false), t(1114725600000, 180, true),
+        t(1128027600000, 120, false), t(1146175200000, 180, true), t(1158872400000, 120, false), t(1177624800000, 180, true), t(1189112400000, 120, false), t(1209074400000, 180, true),
+        t(1219957200000, 120, false), t(1240524000000, 180, true), t(1250802000000, 120, false), t(1272578400000, 180, true), t(1281474000000, 120, false), t(1284069600000, 180, true),
+        t(1285880400000, 120, false), t(1400191200000, 180, true), t(1403816400000, 120, false), t(1406844000000, 180, true), t(1411678800000, 120, false), t(1682632800000, 180, true),
+    ],
+    rule: TzRule { std_offset_minutes: 120, dst: Some(DstRule { offset_minutes: 180, start: mwd(4, 5, 5), start_time_seconds: 0, end: mwd(10, 5, 4), end_time_seconds: 86400 }) },
+};
+
+#[cfg(feature = "tz-africa")]
+const AFRICA_JOHANNESBURG: Zone = Zone {
+    name: "Africa/Johannesburg",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: 120, dst: None },
+};
+
+#[cfg(feature = "tz-africa")]
+const AFRICA_LAGOS: Zone = Zone {
+    name: "Africa/Lagos",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: 60, dst: None },
+};
+
+#[cfg(feature = "tz-africa")]
+const AFRICA_NAIROBI: Zone = Zone {
+    name: "Africa/Nairobi",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: 180, dst: None },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_CHICAGO: Zone = Zone {
+    name: "America/Chicago",
+    transitions: &[
+        t(0, -360, false), t(9964800000, -300, true), t(25686000000, -360, false), t(41414400000, -300, true), t(57740400000, -360, false), t(73468800000, -300, true),
+        t(89190000000, -360, false), t(104918400000, -300, true), t(120639600000, -360, false), t(126691200000, -300, true), t(152089200000, -360, false), t(162374400000, -300, true),
+        t(183538800000, -360, false), t(199267200000, -300, true), t(215593200000, -360, false), t(230716800000, -300, true), t(247042800000, -360, false), t(262771200000, -300, true),
+        t(278492400000, -360, false), t(294220800000, -300, true), t(309942000000, -360, false), t(325670400000, -300, true), t(341391600000, -360, false), t(357120000000, -300, true),
+        t(372841200000, -360, false), t(388569600000, -300, true), t(404895600000, -360, false), t(420019200000, -300, true), t(436345200000, -360, false), t(452073600000, -300, true),
+        t(467794800000, -360, false), t(483523200000, -300, true), t(499244400000, -360, false), t(514972800000, -300, true), t(530694000000, -360, false), t(544608000000, -300, true),
+        t(562143600000, -360, false), t(576057600000, -300, true), t(594198000000, -360, false), t(607507200000, -300, true), t(625647600000, -360, false), t(638956800000, -300, true),
+        t(657097200000, -360, false), t(671011200000, -300, true), t(688546800000, -360, false), t(702460800000, -300, true), t(719996400000, -360, false), t(733910400000, -300, true),
+        t(752050800000, -360, false), t(765360000000, -300, true), t(783500400000, -360, false), t(796809600000, -300, true), t(814950000000, -360, false), t(828864000000, -300, true),
+        t(846399600000, -360, false), t(860313600000, -300, true), t(877849200000, -360, false), t(891763200000, -300, true), t(909298800000, -360, false), t(923212800000, -300, true),
+        t(941353200000, -360, false), t(954662400000, -300, true), t(972802800000, -360, false), t(986112000000, -300, true), t(1004252400000, -360, false), t(1018166400000, -300, true),
+        t(1035702000000, -360, false), t(1049616000000, -300, true), t(1067151600000, -360, false), t(1081065600000, -300, true), t(1099206000000, -360, false), t(1112515200000, -300, true),
+        t(1130655600000, -360, false), t(1143964800000, -300, true), t(1162105200000, -360, false), t(1173600000000, -300, true),
+    ],
+    rule: TzRule { std_offset_minutes: -360, dst: Some(DstRule { offset_minutes: -300, start: mwd(3, 2, 0), start_time_seconds: 7200, end: mwd(11, 1, 0), end_time_seconds: 7200 }) },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_DENVER: Zone = Zone {
+    name: "America/Denver",
+    transitions: &[
+        t(0, -420, false), t(9968400000, -360, true), t(25689600000, -420, false), t(41418000000, -360, true), t(57744000000, -420, false), t(73472400000, -360, true),
+        t(89193600000, -420, false), t(104922000000, -360, true), t(120643200000, -420, false), t(126694800000, -360, true), t(152092800000, -420, false), t(162378000000, -360, true),
+        t(183542400000, -420, false), t(199270800000, -360, true), t(215596800000, -420, false), t(230720400000, -360, true), t(247046400000, -420, false), t(262774800000, -360, true),
+        t(278496000000, -420, false), t(294224400000, -360, true), t(309945600000, -420, false), t(325674000000, -360, true), t(341395200000, -420, false), t(357123600000, -360, true),
+        t(372844800000, -420, false), t(388573200000, -360, true), t(404899200000, -420, false), t(420022800000, -360, true), t(436348800000, -420, false), t(452077200000, -360, true),
+        t(467798400000, -420, false), t(483526800000, -360, true), t(499248000000, -420, false), t(514976400000, -360, true), t(530697600000, -420, false), t(544611600000, -360, true),
+        t(562147200000, -420, false), t(576061200000, -360, true), t(594201600000, -420, false), t(607510800000, -360, true), t(625651200000, -420, false), t(638960400000, -360, true),
+        t(657100800000, -420, false), t(671014800000, -360, true), t(688550400000, -420, false), t(702464400000, -360, true), t(720000000000, -420, false), t(733914000000, -360, true),
+        t(752054400000, -420, false), t(765363600000, -360, true), t(783504000000, -420, false), t(796813200000, -360, true), t(814953600000, -420, false), t(828867600000, -360, true),
+        t(846403200000, -420, false), t(860317200000, -360, true), t(877852800000, -420, false), t(891766800000, -360, true), t(909302400000, -420, false), t(923216400000, -360, true),
+        t(941356800000, -420, false), t(954666000000, -360, true), t(972806400000, -420, false), t(986115600000, -360, true), t(1004256000000, -420, false), t(1018170000000, -360, true),
+        t(1035705600000, -420, false), t(1049619600000, -360, true), t(1067155200000, -420, false), t(1081069200000, -360, true), t(1099209600000, -420, false), t(1112518800000, -360, true),
+        t(1130659200000, -420, false), t(1143968400000, -360, true), t(1162108800000, -420, false), t(1173603600000, -360, true),
+    ],
+    rule: TzRule { std_offset_minutes: -420, dst: Some(DstRule { offset_minutes: -360, start: mwd(3, 2, 0), start_time_seconds: 7200, end: mwd(11, 1, 0), end_time_seconds: 7200 }) },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_LOS_ANGELES: Zone = Zone {
+    name: "America/Los_Angeles",
+    transitions: &[
+        t(0, -480, false), t(9972000000, -420, true), t(25693200000, -480, false), t(41421600000, -420, true), t(57747600000, -480, false), t(73476000000, -420, true),
+        t(89197200000, -480, false), t(104925600000, -420, true), t(120646800000, -480, false), t(126698400000, -420, true), t(152096400000, -480, false), t(162381600000, -420, true),
+        t(183546000000, -480, false), t(199274400000, -420, true), t(215600400000, -480, false), t(230724000000, -420, true), t(247050000000, -480, false), t(262778400000, -420, true),
+        t(278499600000, -480, false), t(294228000000, -420, true), t(309949200000, -480, false), t(325677600000, -420, true), t(341398800000, -480, false), t(357127200000, -420, true),
+        t(372848400000, -480, false), t(388576800000, -420, true), t(404902800000, -480, false), t(420026400000, -420, true), t(436352400000, -480, false), t(452080800000, -420, true),
+        t(467802000000, -480, false), t(483530400000, -420, true), t(499251600000, -480, false), t(514980000000, -420, true), t(530701200000, -480, false), t(544615200000, -420, true),
+        t(562150800000, -480, false), t(576064800000, -420, true), t(594205200000, -480, false), t(607514400000, -420, true), t(625654800000, -480, false), t(638964000000, -420, true),
+        t(657104400000, -480, false), t(671018400000, -420, true), t(688554000000, -480, false), t(702468000000, -420, true), t(720003600000, -480, false), t(733917600000, -420, true),
+        t(752058000000, -480, false), t(765367200000, -420, true), t(783507600000, -480, false), t(796816800000, -420, true), t(814957200000, -480, false), t(828871200000, -420, true),
+        t(846406800000, -480, false), t(860320800000, -420, true), t(877856400000, -480, false), t(891770400000, -420, true), t(909306000000, -480, false), t(923220000000, -420, true),
+        t(941360400000, -480, false), t(954669600000, -420, true), t(972810000000, -480, false), t(986119200000, -420, true), t(1004259600000, -480, false), t(1018173600000, -420, true),
+        t(1035709200000, -480, false), t(1049623200000, -420, true), t(1067158800000, -480, false), t(1081072800000, -420, true), t(1099213200000, -480, false), t(1112522400000, -420, true),
+        t(1130662800000, -480, false), t(1143972000000, -420, true), t(1162112400000, -480, false), t(1173607200000, -420, true),
+    ],
+    rule: TzRule { std_offset_minutes: -480, dst: Some(DstRule { offset_minutes: -420, start: mwd(3, 2, 0), start_time_seconds: 7200, end: mwd(11, 1, 0), end_time_seconds: 7200 }) },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_MEXICO_CITY: Zone = Zone {
+    name: "America/Mexico_City",
+    transitions: &[
+        t(0, -360, false), t(828864000000, -300, true), t(846399600000, -360, false), t(860313600000, -300, true), t(877849200000, -360, false), t(891763200000, -300, true),
+        t(909298800000, -360, false), t(923212800000, -300, true), t(941353200000, -360, false), t(954662400000, -300, true), t(972802800000, -360, false), t(989136000000, -300, true),
+        t(1001833200000, -360, false), t(1018166400000, -300, true), t(1035702000000, -360, false), t(1049616000000, -300, true), t(1067151600000, -360, false), t(1081065600000, -300, true),
+        t(1099206000000, -360, false), t(1112515200000, -300, true), t(1130655600000, -360, false), t(1143964800000, -300, true), t(1162105200000, -360, false), t(1175414400000, -300, true),
+        t(1193554800000, -360, false), t(1207468800000, -300, true), t(1225004400000, -360, false), t(1238918400000, -300, true), t(1256454000000, -360, false), t(1270368000000, -300, true),
+        t(1288508400000, -360, false), t(1301817600000, -300, true), t(1319958000000, -360, false), t(1333267200000, -300, true), t(1351407600000, -360, false), t(1365321600000, -300, true),
+        t(1382857200000, -360, false), t(1396771200000, -300, true), t(1414306800000, -360, false), t(1428220800000, -300, true), t(1445756400000, -360, false), t(1459670400000, -300, true),
+        t(1477810800000, -360, false), t(1491120000000, -300, true), t(1509260400000, -360, false), t(1522569600000, -300, true), t(1540710000000, -360, false), t(1554624000000, -300, true),
+        t(1572159600000, -360, false), t(1586073600000, -300, true), t(1603609200000, -360, false), t(1617523200000, -300, true), t(1635663600000, -360, false), t(1648972800000, -300, true),
+        t(1667113200000, -360, false),
+    ],
+    rule: TzRule { std_offset_minutes: -360, dst: None },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_NEW_YORK: Zone = Zone {
+    name: "America/New_York",
+    transitions: &[
+        t(0, -300, false), t(9961200000, -240, true), t(25682400000, -300, false), t(41410800000, -240, true), t(57736800000, -300, false), t(73465200000, -240, true),
+        t(89186400000, -300, false), t(104914800000, -240, true), t(120636000000, -300, false), t(126687600000, -240, true), t(152085600000, -300, false), t(162370800000, -240, true),
+        t(183535200000, -300, false), t(199263600000, -240, true), t(215589600000, -300, false), t(230713200000, -240, true), t(247039200000, -300, false), t(262767600000, -240, true),
+        t(278488800000, -300, false), t(294217200000, -240, true), t(309938400000, -300, false), t(325666800000, -240, true), t(341388000000, -300, false), t(357116400000, -240, true),
+        t(372837600000, -300, false), t(388566000000, -240, true), t(404892000000, -300, false), t(420015600000, -240, true), t(436341600000, -300, false), t(452070000000, -240, true),
+        t(467791200000, -300, false), t(483519600000, -240, true), t(499240800000, -300, false), t(514969200000, -240, true), t(530690400000, -300, false), t(544604400000, -240, true),
+        t(562140000000, -300, false), t(576054000000, -240, true), t(594194400000, -300, false), t(607503600000, -240, true), t(625644000000, -300, false), t(638953200000, -240, true),
+        t(657093600000, -300, false), t(671007600000, -240, true), t(688543200000, -300, false), t(702457200000, -240, true), t(719992800000, -300, false), t(733906800000, -240, true),
+        t(752047200000, -300, false), t(765356400000, -240, true), t(783496800000, -300, false), t(796806000000, -240, true), t(814946400000, -300, false), t(828860400000, -240, true),
+        t(846396000000, -300, false), t(860310000000, -240, true), t(877845600000, -300, false), t(891759600000, -240, true), t(909295200000, -300, false), t(923209200000, -240, true),
+        t(941349600000, -300, false), t(954658800000, -240, true), t(972799200000, -300, false), t(986108400000, -240, true), t(1004248800000, -300, false), t(1018162800000, -240, true),
+        t(1035698400000, -300, false), t(1049612400000, -240, true), t(1067148000000, -300, false), t(1081062000000, -240, true), t(1099202400000, -300, false), t(1112511600000, -240, true),
+        t(1130652000000, -300, false), t(1143961200000, -240, true), t(1162101600000, -300, false), t(1173596400000, -240, true),
+    ],
+    rule: TzRule { std_offset_minutes: -300, dst: Some(DstRule { offset_minutes: -240, start: mwd(3, 2, 0), start_time_seconds: 7200, end: mwd(11, 1, 0), end_time_seconds: 7200 }) },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_PHOENIX: Zone = Zone {
+    name: "America/Phoenix",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: -420, dst: None },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_SAO_PAULO: Zone = Zone {
+    name: "America/Sao_Paulo",
+    transitions: &[
+        t(0, -180, false), t(499748400000, -120, true), t(511236000000, -180, false), t(530593200000, -120, true), t(540266400000, -180, false), t(562129200000, -120, true),
+        t(571197600000, -180, false), t(592974000000, -120, true), t(602042400000, -180, false), t(624423600000, -120, true), t(634701600000, -180, false), t(656478000000, -120, true),
+        t(666756000000, -180, false), t(687927600000, -120, true), t(697600800000, -180, false), t(719982000000, -120, true), t(728445600000, -180, false), t(750826800000, -120, true),
+        t(761709600000, -180, false), t(782276400000, -120, true), t(793159200000, -180, false), t(813726000000, -120, true), t(824004000000, -180, false), t(844570800000, -120, true),
+        t(856058400000, -180, false), t(876106800000, -120, true), t(888717600000, -180, false), t(908074800000, -120, true), t(919562400000, -180, false), t(938919600000, -120, true),
+        t(951616800000, -180, false), t(970974000000, -120, true), t(982461600000, -180, false), t(1003028400000, -120, true), t(1013911200000, -180, false), t(1036292400000, -120, true),
+        t(1045360800000, -180, false), t(1066532400000, -120, true), t(1076810400000, -180, false), t(1099364400000, -120, true), t(1108864800000, -180, false), t(1129431600000, -120, true),
+        t(1140314400000, -180, false), t(1162695600000, -120, true), t(1172368800000, -180, false), t(1192330800000, -120, true), t(1203213600000, -180, false), t(1224385200000, -120, true),
+        t(1234663200000, -180, false), t(1255834800000, -120, true), t(1266717600000, -180, false), t(1287284400000, -120, true), t(1298167200000, -180, false), t(1318734000000, -120, true),
+        t(1330221600000, -180, false), t(1350788400000, -120, true), t(1361066400000, -180, false), t(1382238000000, -120, true), t(1392516000000, -180, false), t(1413687600000, -120, true),
+        t(1424570400000, -180, false), t(1445137200000, -120, true), t(1456020000000, -180, false), t(1476586800000, -120, true), t(1487469600000, -180, false), t(1508036400000, -120, true),
+        t(1518919200000, -180, false), t(1541300400000, -120, true), t(1550368800000, -180, false),
+    ],
+    rule: TzRule { std_offset_minutes: -180, dst: None },
+};
+
+#[cfg(feature = "tz-america")]
+const AMERICA_TORONTO: Zone = Zone {
+    name: "America/Toronto",
+    transitions: &[
+        t(0, -300, false), t(9961200000, -240, true), t(25682400000, -300, false), t(41410800000, -240, true), t(57736800000, -300, false), t(73465200000, -240, true),
+        t(89186400000, -300, false), t(104914800000, -240, true), t(120636000000, -300, false), t(136364400000, -240, true), t(152085600000, -300, false), t(167814000000, -240, true),
+        t(183535200000, -300, false), t(199263600000, -240, true), t(215589600000, -300, false), t(230713200000, -240, true), t(247039200000, -300, false), t(262767600000, -240, true),
+        t(278488800000, -300, false), t(294217200000, -240, true), t(309938400000, -300, false), t(325666800000, -240, true), t(341388000000, -300, false), t(357116400000, -240, true),
+        t(372837600000, -300, false), t(388566000000, -240, true), t(404892000000, -300, false), t(420015600000, -240, true), t(436341600000, -300, false), t(452070000000, -240, true),
+        t(467791200000, -300, false), t(483519600000, -240, true), t(499240800000, -300, false), t(514969200000, -240, true), t(530690400000, -300, false), t(544604400000, -240, true),
+        t(562140000000, -300, false), t(576054000000, -240, true), t(594194400000, -300, false), t(607503600000, -240, true), t(625644000000, -300, false), t(638953200000, -240, true),
+        t(657093600000, -300, false), t(671007600000, -240, true), t(688543200000, -300, false), t(702457200000, -240, true), t(719992800000, -300, false), t(733906800000, -240, true),
+        t(752047200000, -300, false), t(765356400000, -240, true), t(783496800000, -300, false), t(796806000000, -240, true), t(814946400000, -300, false), t(828860400000, -240, true),
+        t(846396000000, -300, false), t(860310000000, -240, true), t(877845600000, -300, false), t(891759600000, -240, true), t(909295200000, -300, false), t(923209200000, -240, true),
+        t(941349600000, -300, false), t(954658800000, -240, true), t(972799200000, -300, false), t(986108400000, -240, true), t(1004248800000, -300, false), t(1018162800000, -240, true),
+        t(1035698400000, -300, false), t(1049612400000, -240, true), t(1067148000000, -300, false), t(1081062000000, -240, true), t(1099202400000, -300, false), t(1112511600000, -240, true),
+        t(1130652000000, -300, false), t(1143961200000, -240, true), t(1162101600000, -300, false), t(1173596400000, -240, true),
+    ],
+    rule: TzRule { std_offset_minutes: -300, dst: Some(DstRule { offset_minutes: -240, start: mwd(3, 2, 0), start_time_seconds: 7200, end: mwd(11, 1, 0), end_time_seconds: 7200 }) },
+};
+
+#[cfg(feature = "tz-asia")]
+const ASIA_DUBAI: Zone = Zone {
+    name: "Asia/Dubai",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: 240, dst: None },
+};
+
+#[cfg(feature = "tz-asia")]
+const ASIA_HONG_KONG: Zone = Zone {
+    name: "Asia/Hong_Kong",
+    transitions: &[
+        t(0, 480, false), t(9315000000, 540, true), t(25036200000, 480, false), t(40764600000, 540, true), t(56485800000, 480, false), t(72214200000, 540, true),
+        t(88540200000, 480, false), t(104268600000, 540, true), t(119989800000, 480, false), t(126041400000, 540, true), t(151439400000, 480, false), t(167167800000, 540, true),
+        t(182889000000, 480, false), t(198617400000, 540, true), t(214338600000, 480, false), t(295385400000, 540, true), t(309292200000, 480, false),
+    ],
+    rule: TzRule { std_offset_minutes: 480, dst: None },
+};
+
+#[cfg(feature = "tz-asia")]
+const ASIA_KOLKATA: Zone = Zone {
+    name: "Asia/Kolkata",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: 330, dst: None },
+};
+
+#[cfg(feature = "tz-asia")]
+const ASIA_SHANGHAI: Zone = Zone {
+    name: "Asia/Shanghai",
+    transitions: &[
+        t(0, 480, false), t(515527200000, 540, true), t(527014800000, 480, false), t(545162400000, 540, true), t(558464400000, 480, false), t(577216800000, 540, true),
+        t(589914000000, 480, false), t(608666400000, 540, true), t(621968400000, 480, false), t(640116000000, 540, true), t(653418000000, 480, false), t(671565600000, 540, true),
+        t(684867600000, 480, false),
+    ],
+    rule: TzRule { std_offset_minutes: 480, dst: None },
+};
+
+#[cfg(feature = "tz-asia")]
+const ASIA_SINGAPORE: Zone = Zone {
+    name: "Asia/Singapore",
+    transitions: &[
+        t(0, 450, false), t(378662400000, 480, false),
+    ],
+    rule: TzRule { std_offset_minutes: 480, dst: None },
+};
+
+#[cfg(feature = "tz-asia")]
+const ASIA_TOKYO: Zone = Zone {
+    name: "Asia/Tokyo",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: 540, dst: None },
+};
+
+#[cfg(feature = "tz-oceania")]
+const AUSTRALIA_MELBOURNE: Zone = Zone {
+    name: "Australia/Melbourne",
+    transitions: &[
+        t(0, 600, false), t(57686400000, 660, true), t(67968000000, 600, false), t(89136000000, 660, true), t(100022400000, 600, false), t(120585600000, 660, true),
+        t(131472000000, 600, false), t(152035200000, 660, true), t(162921600000, 600, false), t(183484800000, 660, true), t(194976000000, 600, false), t(215539200000, 660, true),
+        t(226425600000, 600, false), t(246988800000, 660, true), t(257875200000, 600, false), t(278438400000, 660, true), t(289324800000, 600, false), t(309888000000, 660, true),
+        t(320774400000, 600, false), t(341337600000, 660, true), t(352224000000, 600, false), t(372787200000, 660, true), t(384278400000, 600, false), t(404841600000, 660, true),
+        t(415728000000, 600, false), t(436291200000, 660, true), t(447177600000, 600, false), t(467740800000, 660, true), t(478627200000, 600, false), t(499190400000, 660, true),
+        t(511286400000, 600, false), t(530035200000, 660, true), t(542736000000, 600, false), t(561484800000, 660, true), t(574790400000, 600, false), t(594144000000, 660, true),
+        t(606240000000, 600, false), t(625593600000, 660, true), t(637689600000, 600, false), t(657043200000, 660, true), t(667929600000, 600, false), t(688492800000, 660, true),
+        t(699379200000, 600, false), t(719942400000, 660, true), t(731433600000, 600, false), t(751996800000, 660, true), t(762883200000, 600, false), t(783446400000, 660, true),
+        t(796147200000, 600, false), t(814896000000, 660, true), t(828201600000, 600, false), t(846345600000, 660, true), t(859651200000, 600, false), t(877795200000, 660, true),
+        t(891100800000, 600, false), t(909244800000, 660, true), t(922550400000, 600, false), t(941299200000, 660, true), t(954000000000, 600, false), t(967305600000, 660, true),
+        t(985449600000, 600, false), t(1004198400000, 660, true), t(1017504000000, 600, false), t(1035648000000, 660, true), t(1048953600000, 600, false), t(1067097600000, 660, true),
+        t(1080403200000, 600, false), t(1099152000000, 660, true), t(1111852800000, 600, false), t(1130601600000, 660, true), t(1143907200000, 600, false), t(1162051200000, 660, true),
+        t(1174752000000, 600, false), t(1193500800000, 660, true),
+    ],
+    rule: TzRule { std_offset_minutes: 600, dst: Some(DstRule { offset_minutes: 660, start: mwd(10, 1, 0), start_time_seconds: 7200, end: mwd(4, 1, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-oceania")]
+const AUSTRALIA_PERTH: Zone = Zone {
+    name: "Australia/Perth",
+    transitions: &[
+        t(0, 480, false), t(152042400000, 540, true), t(162928800000, 480, false), t(436298400000, 540, true), t(447184800000, 480, false), t(690314400000, 540, true),
+        t(699386400000, 480, false), t(1165082400000, 540, true), t(1174759200000, 480, false), t(1193508000000, 540, true), t(1206813600000, 480, false), t(1224957600000, 540, true),
+        t(1238263200000, 480, false),
+    ],
+    rule: TzRule { std_offset_minutes: 480, dst: None },
+};
+
+#[cfg(feature = "tz-oceania")]
+const AUSTRALIA_SYDNEY: Zone = Zone {
+    name: "Australia/Sydney",
+    transitions: &[
+        t(0, 600, false), t(57686400000, 660, true), t(67968000000, 600, false), t(89136000000, 660, true), t(100022400000, 600, false), t(120585600000, 660, true),
+        t(131472000000, 600, false), t(152035200000, 660, true), t(162921600000, 600, false), t(183484800000, 660, true), t(194976000000, 600, false), t(215539200000, 660, true),
+        t(226425600000, 600, false), t(246988800000, 660, true), t(257875200000, 600, false), t(278438400000, 660, true), t(289324800000, 600, false), t(309888000000, 660, true),
+        t(320774400000, 600, false), t(341337600000, 660, true), t(352224000000, 600, false), t(372787200000, 660, true), t(386697600000, 600, false), t(404841600000, 660, true),
+        t(415728000000, 600, false), t(436291200000, 660, true), t(447177600000, 600, false), t(467740800000, 660, true), t(478627200000, 600, false), t(499190400000, 660, true),
+        t(511286400000, 600, false), t(530035200000, 660, true), t(542736000000, 600, false), t(562089600000, 660, true), t(574790400000, 600, false), t(594144000000, 660, true),
+        t(606240000000, 600, false), t(625593600000, 660, true), t(636480000000, 600, false), t(657043200000, 660, true), t(667929600000, 600, false), t(688492800000, 660, true),
+        t(699379200000, 600, false), t(719942400000, 660, true), t(731433600000, 600, false), t(751996800000, 660, true), t(762883200000, 600, false), t(783446400000, 660, true),
+        t(794332800000, 600, false), t(814896000000, 660, true), t(828201600000, 600, false), t(846345600000, 660, true), t(859651200000, 600, false), t(877795200000, 660, true),
+        t(891100800000, 600, false), t(909244800000, 660, true), t(922550400000, 600, false), t(941299200000, 660, true), t(954000000000, 600, false), t(967305600000, 660, true),
+        t(985449600000, 600, false), t(1004198400000, 660, true), t(1017504000000, 600, false), t(1035648000000, 660, true), t(1048953600000, 600, false), t(1067097600000, 660, true),
+        t(1080403200000, 600, false), t(1099152000000, 660, true), t(1111852800000, 600, false), t(1130601600000, 660, true), t(1143907200000, 600, false), t(1162051200000, 660, true),
+        t(1174752000000, 600, false), t(1193500800000, 660, true),
+    ],
+    rule: TzRule { std_offset_minutes: 600, dst: Some(DstRule { offset_minutes: 660, start: mwd(10, 1, 0), start_time_seconds: 7200, end: mwd(4, 1, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_AMSTERDAM: Zone = Zone {
+    name: "Europe/Amsterdam",
+    transitions: &[
+        t(0, 60, false), t(228877200000, 120, true), t(243997200000, 60, false), t(260326800000, 120, true), t(276051600000, 60, false), t(291776400000, 120, true),
+        t(307501200000, 60, false), t(323830800000, 120, true), t(338950800000, 60, false), t(354675600000, 120, true), t(370400400000, 60, false), t(386125200000, 120, true),
+        t(401850000000, 60, false), t(417574800000, 120, true), t(433299600000, 60, false), t(449024400000, 120, true), t(465354000000, 60, false), t(481078800000, 120, true),
+        t(496803600000, 60, false), t(512528400000, 120, true), t(528253200000, 60, false), t(543978000000, 120, true), t(559702800000, 60, false), t(575427600000, 120, true),
+        t(591152400000, 60, false), t(606877200000, 120, true), t(622602000000, 60, false), t(638326800000, 120, true), t(654656400000, 60, false), t(670381200000, 120, true),
+        t(686106000000, 60, false), t(701830800000, 120, true), t(717555600000, 60, false), t(733280400000, 120, true), t(749005200000, 60, false), t(764730000000, 120, true),
+        t(780454800000, 60, false), t(796179600000, 120, true), t(811904400000, 60, false), t(828234000000, 120, true),
+    ],
+    rule: TzRule { std_offset_minutes: 60, dst: Some(DstRule { offset_minutes: 120, start: mwd(3, 5, 0), start_time_seconds: 7200, end: mwd(10, 5, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_BERLIN: Zone = Zone {
+    name: "Europe/Berlin",
+    transitions: &[
+        t(0, 60, false), t(323830800000, 120, true), t(338950800000, 60, false), t(354675600000, 120, true), t(370400400000, 60, false), t(386125200000, 120, true),
+        t(401850000000, 60, false), t(417574800000, 120, true), t(433299600000, 60, false), t(449024400000, 120, true), t(465354000000, 60, false), t(481078800000, 120, true),
+        t(496803600000, 60, false), t(512528400000, 120, true), t(528253200000, 60, false), t(543978000000, 120, true), t(559702800000, 60, false), t(575427600000, 120, true),
+        t(591152400000, 60, false), t(606877200000, 120, true), t(622602000000, 60, false), t(638326800000, 120, true), t(654656400000, 60, false), t(670381200000, 120, true),
+        t(686106000000, 60, false), t(701830800000, 120, true), t(717555600000, 60, false), t(733280400000, 120, true), t(749005200000, 60, false), t(764730000000, 120, true),
+        t(780454800000, 60, false), t(796179600000, 120, true), t(811904400000, 60, false), t(828234000000, 120, true),
+    ],
+    rule: TzRule { std_offset_minutes: 60, dst: Some(DstRule { offset_minutes: 120, start: mwd(3, 5, 0), start_time_seconds: 7200, end: mwd(10, 5, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_LONDON: Zone = Zone {
+    name: "Europe/London",
+    transitions: &[
+        t(0, 60, false), t(57722400000, 0, false), t(69818400000, 60, true), t(89172000000, 0, false), t(101268000000, 60, true), t(120621600000, 0, false),
+        t(132717600000, 60, true), t(152071200000, 0, false), t(164167200000, 60, true), t(183520800000, 0, false), t(196221600000, 60, true), t(214970400000, 0, false),
+        t(227671200000, 60, true), t(246420000000, 0, false), t(259120800000, 60, true), t(278474400000, 0, false), t(290570400000, 60, true), t(309924000000, 0, false),
+        t(322020000000, 60, true), t(341373600000, 0, false), t(354675600000, 60, true), t(372819600000, 0, false), t(386125200000, 60, true), t(404269200000, 0, false),
+        t(417574800000, 60, true), t(435718800000, 0, false), t(449024400000, 60, true), t(467773200000, 0, false), t(481078800000, 60, true), t(499222800000, 0, false),
+        t(512528400000, 60, true), t(530672400000, 0, false), t(543978000000, 60, true), t(562122000000, 0, false), t(575427600000, 60, true), t(593571600000, 0, false),
+        t(606877200000, 60, true), t(625626000000, 0, false), t(638326800000, 60, true), t(657075600000, 0, false), t(670381200000, 60, true), t(688525200000, 0, false),
+        t(701830800000, 60, true), t(719974800000, 0, false), t(733280400000, 60, true), t(751424400000, 0, false), t(764730000000, 60, true), t(782874000000, 0, false),
+        t(796179600000, 60, true), t(814323600000, 0, false), t(828234000000, 60, true),
+    ],
+    rule: TzRule { std_offset_minutes: 0, dst: Some(DstRule { offset_minutes: 60, start: mwd(3, 5, 0), start_time_seconds: 3600, end: mwd(10, 5, 0), end_time_seconds: 7200 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_MADRID: Zone = Zone {
+    name: "Europe/Madrid",
+    transitions: &[
+        t(0, 60, false), t(135122400000, 120, true), t(150246000000, 60, false), t(166572000000, 120, true), t(181695600000, 60, false), t(196812000000, 120, true),
+        t(212540400000, 60, false), t(228866400000, 120, true), t(243990000000, 60, false), t(260326800000, 120, true), t(276051600000, 60, false), t(291776400000, 120, true),
+        t(307501200000, 60, false), t(323830800000, 120, true), t(338950800000, 60, false), t(354675600000, 120, true), t(370400400000, 60, false), t(386125200000, 120, true),
+        t(401850000000, 60, false), t(417574800000, 120, true), t(433299600000, 60, false), t(449024400000, 120, true), t(465354000000, 60, false), t(481078800000, 120, true),
+        t(496803600000, 60, false), t(512528400000, 120, true), t(528253200000, 60, false), t(543978000000, 120, true), t(559702800000, 60, false), t(575427600000, 120, true),
+        t(591152400000, 60, false), t(606877200000, 120, true), t(622602000000, 60, false), t(638326800000, 120, true), t(654656400000, 60, false), t(670381200000, 120, true),
+        t(686106000000, 60, false), t(701830800000, 120, true), t(717555600000, 60, false), t(733280400000, 120, true), t(749005200000, 60, false), t(764730000000, 120, true),
+        t(780454800000, 60, false), t(796179600000, 120, true), t(811904400000, 60, false), t(828234000000, 120, true),
+    ],
+    rule: TzRule { std_offset_minutes: 60, dst: Some(DstRule { offset_minutes: 120, start: mwd(3, 5, 0), start_time_seconds: 7200, end: mwd(10, 5, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_MOSCOW: Zone = Zone {
+    name: "Europe/Moscow",
+    transitions: &[
+        t(0, 180, false), t(354920400000, 240, true), t(370728000000, 180, false), t(386456400000, 240, true), t(402264000000, 180, false), t(417992400000, 240, true),
+        t(433800000000, 180, false), t(449614800000, 240, true), t(465346800000, 180, false), t(481071600000, 240, true), t(496796400000, 180, false), t(512521200000, 240, true),
+        t(528246000000, 180, false), t(543970800000, 240, true), t(559695600000, 180, false), t(575420400000, 240, true), t(591145200000, 180, false), t(606870000000, 240, true),
+        t(622594800000, 180, false), t(638319600000, 240, true), t(654649200000, 180, false), t(670374000000, 180, true), t(686102400000, 120, false), t(695779200000, 180, false),
+        t(701823600000, 240, true), t(717548400000, 180, false), t(733273200000, 240, true), t(748998000000, 180, false), t(764722800000, 240, true), t(780447600000, 180, false),
+        t(796172400000, 240, true), t(811897200000, 180, false), t(828226800000, 240, true), t(846370800000, 180, false), t(859676400000, 240, true), t(877820400000, 180, false),
+        t(891126000000, 240, true), t(909270000000, 180, false), t(922575600000, 240, true), t(941324400000, 180, false), t(954025200000, 240, true), t(972774000000, 180, false),
+        t(985474800000, 240, true), t(1004223600000, 180, false), t(1017529200000, 240, true), t(1035673200000, 180, false), t(1048978800000, 240, true), t(1067122800000, 180, false),
+        t(1080428400000, 240, true), t(1099177200000, 180, false), t(1111878000000, 240, true), t(1130626800000, 180, false), t(1143327600000, 240, true), t(1162076400000, 180, false),
+        t(1174777200000, 240, true), t(1193526000000, 180, false), t(1206831600000, 240, true), t(1224975600000, 180, false), t(1238281200000, 240, true), t(1256425200000, 180, false),
+        t(1269730800000, 240, true), t(1288479600000, 180, false), t(1301180400000, 240, false), t(1414274400000, 180, false),
+    ],
+    rule: TzRule { std_offset_minutes: 180, dst: None },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_PARIS: Zone = Zone {
+    name: "Europe/Paris",
+    transitions: &[
+        t(0, 60, false), t(196819200000, 120, true), t(212540400000, 60, false), t(228877200000, 120, true), t(243997200000, 60, false), t(260326800000, 120, true),
+        t(276051600000, 60, false), t(291776400000, 120, true), t(307501200000, 60, false), t(323830800000, 120, true), t(338950800000, 60, false), t(354675600000, 120, true),
+        t(370400400000, 60, false), t(386125200000, 120, true), t(401850000000, 60, false), t(417574800000, 120, true), t(433299600000, 60, false), t(449024400000, 120, true),
+        t(465354000000, 60, false), t(481078800000, 120, true), t(496803600000, 60, false), t(512528400000, 120, true), t(528253200000, 60, false), t(543978000000, 120, true),
+        t(559702800000, 60, false), t(575427600000, 120, true), t(591152400000, 60, false), t(606877200000, 120, true), t(622602000000, 60, false), t(638326800000, 120, true),
+        t(654656400000, 60, false), t(670381200000, 120, true), t(686106000000, 60, false), t(701830800000, 120, true), t(717555600000, 60, false), t(733280400000, 120, true),
+        t(749005200000, 60, false), t(764730000000, 120, true), t(780454800000, 60, false), t(796179600000, 120, true), t(811904400000, 60, false), t(828234000000, 120, true),
+    ],
+    rule: TzRule { std_offset_minutes: 60, dst: Some(DstRule { offset_minutes: 120, start: mwd(3, 5, 0), start_time_seconds: 7200, end: mwd(10, 5, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_ROME: Zone = Zone {
+    name: "Europe/Rome",
+    transitions: &[
+        t(0, 60, false), t(12956400000, 120, true), t(23238000000, 60, false), t(43801200000, 120, true), t(54687600000, 60, false), t(75855600000, 120, true),
+        t(86742000000, 60, false), t(107910000000, 120, true), t(118191600000, 60, false), t(138754800000, 120, true), t(149641200000, 60, false), t(170809200000, 120, true),
+        t(181090800000, 60, false), t(202258800000, 120, true), t(212540400000, 60, false), t(233103600000, 120, true), t(243990000000, 60, false), t(265158000000, 120, true),
+        t(276044400000, 60, false), t(296607600000, 120, true), t(307494000000, 60, false), t(323830800000, 120, true), t(338950800000, 60, false), t(354675600000, 120, true),
+        t(370400400000, 60, false), t(386125200000, 120, true), t(401850000000, 60, false), t(417574800000, 120, true), t(433299600000, 60, false), t(449024400000, 120, true),
+        t(465354000000, 60, false), t(481078800000, 120, true), t(496803600000, 60, false), t(512528400000, 120, true), t(528253200000, 60, false), t(543978000000, 120, true),
+        t(559702800000, 60, false), t(575427600000, 120, true), t(591152400000, 60, false), t(606877200000, 120, true), t(622602000000, 60, false), t(638326800000, 120, true),
+        t(654656400000, 60, false), t(670381200000, 120, true), t(686106000000, 60, false), t(701830800000, 120, true), t(717555600000, 60, false), t(733280400000, 120, true),
+        t(749005200000, 60, false), t(764730000000, 120, true), t(780454800000, 60, false), t(796179600000, 120, true), t(811904400000, 60, false), t(828234000000, 120, true),
+    ],
+    rule: TzRule { std_offset_minutes: 60, dst: Some(DstRule { offset_minutes: 120, start: mwd(3, 5, 0), start_time_seconds: 7200, end: mwd(10, 5, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_WARSAW: Zone = Zone {
+    name: "Europe/Warsaw",
+    transitions: &[
+        t(0, 60, false), t(228873600000, 120, true), t(243993600000, 60, false), t(260323200000, 120, true), t(276048000000, 60, false), t(291772800000, 120, true),
+        t(307497600000, 60, false), t(323827200000, 120, true), t(338947200000, 60, false), t(354672000000, 120, true), t(370396800000, 60, false), t(386121600000, 120, true),
+        t(401846400000, 60, false), t(417571200000, 120, true), t(433296000000, 60, false), t(449020800000, 120, true), t(465350400000, 60, false), t(481075200000, 120, true),
+        t(496800000000, 60, false), t(512524800000, 120, true), t(528249600000, 60, false), t(543974400000, 120, true), t(559699200000, 60, false), t(575427600000, 120, true),
+        t(591152400000, 60, false), t(606877200000, 120, true), t(622602000000, 60, false), t(638326800000, 120, true), t(654656400000, 60, false), t(670381200000, 120, true),
+        t(686106000000, 60, false), t(701830800000, 120, true), t(717555600000, 60, false), t(733280400000, 120, true), t(749005200000, 60, false), t(764730000000, 120, true),
+        t(780454800000, 60, false), t(796179600000, 120, true), t(811904400000, 60, false), t(828234000000, 120, true),
+    ],
+    rule: TzRule { std_offset_minutes: 60, dst: Some(DstRule { offset_minutes: 120, start: mwd(3, 5, 0), start_time_seconds: 7200, end: mwd(10, 5, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-europe")]
+const EUROPE_ZURICH: Zone = Zone {
+    name: "Europe/Zurich",
+    transitions: &[
+        t(0, 60, false), t(354675600000, 120, true), t(370400400000, 60, false), t(386125200000, 120, true), t(401850000000, 60, false), t(417574800000, 120, true),
+        t(433299600000, 60, false), t(449024400000, 120, true), t(465354000000, 60, false), t(481078800000, 120, true), t(496803600000, 60, false), t(512528400000, 120, true),
+        t(528253200000, 60, false), t(543978000000, 120, true), t(559702800000, 60, false), t(575427600000, 120, true), t(591152400000, 60, false), t(606877200000, 120, true),
+        t(622602000000, 60, false), t(638326800000, 120, true), t(654656400000, 60, false), t(670381200000, 120, true), t(686106000000, 60, false), t(701830800000, 120, true),
+        t(717555600000, 60, false), t(733280400000, 120, true), t(749005200000, 60, false), t(764730000000, 120, true), t(780454800000, 60, false), t(796179600000, 120, true),
+        t(811904400000, 60, false), t(828234000000, 120, true),
+    ],
+    rule: TzRule { std_offset_minutes: 60, dst: Some(DstRule { offset_minutes: 120, start: mwd(3, 5, 0), start_time_seconds: 7200, end: mwd(10, 5, 0), end_time_seconds: 10800 }) },
+};
+
+#[cfg(feature = "tz-oceania")]
+const PACIFIC_AUCKLAND: Zone = Zone {
+    name: "Pacific/Auckland",
+    transitions: &[
+        t(0, 720, false), t(152632800000, 780, true), t(162309600000, 720, false), t(183477600000, 780, true), t(194968800000, 720, false), t(215532000000, 780, true),
+        t(226418400000, 720, false), t(246981600000, 780, true), t(257868000000, 720, false), t(278431200000, 780, true), t(289317600000, 720, false), t(309880800000, 780, true),
+        t(320767200000, 720, false), t(341330400000, 780, true), t(352216800000, 720, false), t(372780000000, 780, true), t(384271200000, 720, false), t(404834400000, 780, true),
+        t(415720800000, 720, false), t(436284000000, 780, true), t(447170400000, 720, false), t(467733600000, 780, true), t(478620000000, 720, false), t(499183200000, 780, true),
+        t(510069600000, 720, false), t(530632800000, 780, true), t(541519200000, 720, false), t(562082400000, 780, true), t(573573600000, 720, false), t(594136800000, 780, true),
+        t(605023200000, 720, false), t(623772000000, 780, true), t(637682400000, 720, false), t(655221600000, 780, true), t(669132000000, 720, false), t(686671200000, 780, true),
+        t(700581600000, 720, false), t(718120800000, 780, true), t(732636000000, 720, false), t(749570400000, 780, true), t(764085600000, 720, false), t(781020000000, 780, true),
+        t(795535200000, 720, false), t(812469600000, 780, true), t(826984800000, 720, false), t(844524000000, 780, true), t(858434400000, 720, false), t(875973600000, 780, true),
+        t(889884000000, 720, false), t(907423200000, 780, true), t(921938400000, 720, false), t(938872800000, 780, true), t(953388000000, 720, false), t(970322400000, 780, true),
+        t(984837600000, 720, false), t(1002376800000, 780, true), t(1016287200000, 720, false), t(1033826400000, 780, true), t(1047736800000, 720, false), t(1065276000000, 780, true),
+        t(1079791200000, 720, false), t(1096725600000, 780, true), t(1111240800000, 720, false), t(1128175200000, 780, true), t(1142690400000, 720, false), t(1159624800000, 780, true),
+        t(1174140000000, 720, false), t(1191074400000, 780, true),
+    ],
+    rule: TzRule { std_offset_minutes: 720, dst: Some(DstRule { offset_minutes: 780, start: mwd(9, 5, 0), start_time_seconds: 7200, end: mwd(4, 1, 0), end_time_seconds: 10800 }) },
+};
+
+const UTC: Zone = Zone {
+    name: "UTC",
+    transitions: &[],
+    rule: TzRule { std_offset_minutes: 0, dst: None },
+};
@@ -0,0 +1,209 @@
+use core::str::FromStr;
+use super::{
+    tz::*,
+    types::*,
+};
+
+/// Transition time when not specified, ie. 02:00:00.
+const DEFAULT_TRANSITION_TIME_SECONDS: i32 = 2 * 60 * 60;
+/// Transition times may exceed a day, as per RFC 8536, eg. `M3.5.0/-1` or `M10.5.0/167`.
+const MAX_TRANSITION_HOURS: u32 = 167;
+/// Offsets are limited to 24 hours, as per POSIX.
+const MAX_OFFSET_HOURS: u32 = 24;
+
+impl TzRule {
+    /// Parses a POSIX TZ string, eg. `CET-1CEST,M3.5.0,M10.5.0/3`, with offsets west of UTC as per POSIX.
+    /// When daylight saving time is specified without rules, US rules `M3.2.0,M11.1.0` are assumed.
+    /// ```rust
+    /// # use chrono_light::prelude::*;
+    /// let rule = TzRule::from_posix("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// assert_eq!(rule.std_offset_minutes, 60);
+    /// assert_eq!(rule.dst.map(|dst| dst.offset_minutes), Some(120));
+    /// assert_eq!(TzRule::from_posix("CET-1CEST,M3.5.0,M13.5.0/3"), Err(ParseError { position: 18, kind: ParseErrorKind::OutOfRange }));
+    /// ```
+    pub fn from_posix(s: &str) -> Result<TzRule, ParseError> {
+        let mut parser = PosixParser { input: s.as_bytes(), position: 0 };
+        let rule = parser.rule()?;
+        parser.end()?;
+        Ok(rule)
+    }
+}
+
+impl FromStr for TzRule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TzRule::from_posix(s)
+    }
+}
+
+struct PosixParser<'a> {
+    input:    &'a [u8],
+    position: usize,
+}
+
+impl PosixParser<'_> {
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError { position: self.position, kind }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(x) if x == c => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(_) => Err(self.error(ParseErrorKind::Unexpected)),
+            None => Err(self.error(ParseErrorKind::TooShort)),
+        }
+    }
+
+    fn end(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error(ParseErrorKind::Unexpected)),
+        }
+    }
+
+    /// `std offset [dst [offset] [,start[/time],end[/time]]]`
+    fn rule(&mut self) -> Result<TzRule, ParseError> {
+        self.name()?;
+        let std_offset_minutes = -self.offset()?;
+        if self.peek().is_none() {
+            return Ok(TzRule { std_offset_minutes, dst: None });
+        }
+
+        self.name()?;
+        let offset_minutes = match self.peek() {
+            Some(b'+' | b'-' | b'0'..=b'9') => -self.offset()?,
+            _ => std_offset_minutes + 60,
+        };
+        let (start, start_time_seconds, end, end_time_seconds) = if self.peek().is_none() {
+            (
+                TransitionDay::MonthWeekday { month: 3, week: 2, weekday: 0 }, DEFAULT_TRANSITION_TIME_SECONDS,
+                TransitionDay::MonthWeekday { month: 11, week: 1, weekday: 0 }, DEFAULT_TRANSITION_TIME_SECONDS,
+            )
+        } else {
+            self.expect(b',')?;
+            let (start, start_time_seconds) = self.transition()?;
+            self.expect(b',')?;
+            let (end, end_time_seconds) = self.transition()?;
+            (start, start_time_seconds, end, end_time_seconds)
+        };
+        Ok(TzRule { std_offset_minutes, dst: Some(DstRule { offset_minutes, start, start_time_seconds, end, end_time_seconds }) })
+    }
+
+    /// Either at least 3 alphabetic characters, or `<` alphanumeric, `+`, `-` characters `>`.
+    fn name(&mut self) -> Result<(), ParseError> {
+        let start = self.position;
+        if self.peek() == Some(b'<') {
+            self.position += 1;
+            while let Some(b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'+' | b'-') = self.peek() {
+                self.position += 1;
+            }
+            if self.position - start - 1 < 3 {
+                return Err(self.error(if self.peek().is_none() { ParseErrorKind::TooShort } else { ParseErrorKind::Unexpected }));
+            }
+            self.expect(b'>')
+        } else {
+            while let Some(b'a'..=b'z' | b'A'..=b'Z') = self.peek() {
+                self.position += 1;
+            }
+            if self.position - start < 3 {
+                return Err(self.error(if self.peek().is_none() { ParseErrorKind::TooShort } else { ParseErrorKind::Unexpected }));
+            }
+            Ok(())
+        }
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in minutes, with whole minutes only.
+    fn offset(&mut self) -> Result<i16, ParseError> {
+        let start = self.position;
+        let seconds = self.time(MAX_OFFSET_HOURS)?;
+        if seconds % 60 != 0 {
+            return Err(ParseError { position: start, kind: ParseErrorKind::Unsupported });
+        }
+        Ok((seconds / 60) as i16)
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in seconds.
+    fn time(&mut self, max_hours: u32) -> Result<i32, ParseError> {
+        let sign = match self.peek() {
+            Some(b'-') => { self.position += 1; -1 }
+            Some(b'+') => { self.position += 1; 1 }
+            _ => 1,
+        };
+        let hours = self.number(max_hours)?;
+        let mut seconds = hours * 60 * 60;
+        if self.peek() == Some(b':') {
+            self.position += 1;
+            seconds += self.number(59)? * 60;
+            if self.peek() == Some(b':') {
+                self.position += 1;
+                seconds += self.number(59)?;
+            }
+        }
+        Ok(sign * seconds as i32)
+    }
+
+    /// `Jn`, `n` or `Mm.w.d`, followed by optional `/time`.
+    fn transition(&mut self) -> Result<(TransitionDay, i32), ParseError> {
+        let day = match self.peek() {
+            Some(b'J') => {
+                self.position += 1;
+                let start = self.position;
+                let day = self.number(365)?;
+                if day == 0 {
+                    return Err(ParseError { position: start, kind: ParseErrorKind::OutOfRange });
+                }
+                TransitionDay::Julian { day: day as u16 }
+            }
+            Some(b'M') => {
+                self.position += 1;
+                let month = self.number_in(1, 12)? as u8;
+                self.expect(b'.')?;
+                let week = self.number_in(1, 5)? as u8;
+                self.expect(b'.')?;
+                let weekday = self.number(6)? as u8;
+                TransitionDay::MonthWeekday { month, week, weekday }
+            }
+            _ => TransitionDay::DayOfYear { day: self.number(365)? as u16 },
+        };
+        let time_seconds = if self.peek() == Some(b'/') {
+            self.position += 1;
+            self.time(MAX_TRANSITION_HOURS)?
+        } else {
+            DEFAULT_TRANSITION_TIME_SECONDS
+        };
+        Ok((day, time_seconds))
+    }
+
+    fn number_in(&mut self, min: u32, max: u32) -> Result<u32, ParseError> {
+        let start = self.position;
+        match self.number(max)? {
+            x if x < min => Err(ParseError { position: start, kind: ParseErrorKind::OutOfRange }),
+            x => Ok(x),
+        }
+    }
+
+    /// Decimal number, up to `max`.
+    fn number(&mut self, max: u32) -> Result<u32, ParseError> {
+        let start = self.position;
+        let mut value = 0_u32;
+        while let Some(c @ b'0'..=b'9') = self.peek() {
+            value = value.saturating_mul(10).saturating_add((c - b'0') as u32);
+            self.position += 1;
+        }
+        if self.position == start {
+            Err(self.error(if self.peek().is_none() { ParseErrorKind::TooShort } else { ParseErrorKind::Unexpected }))
+        } else if value > max {
+            Err(ParseError { position: start, kind: ParseErrorKind::OutOfRange })
+        } else {
+            Ok(value)
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::{fs, io, path::Path};
+use super::{
+    calendar::Calendar,
+    constants::*,
+    types::*,
+    tz::*,
+};
+
+/// Magic of TZif header, as per RFC 8536.
+const MAGIC: &[u8] = b"TZif";
+
+/// Time zone loaded from a TZif file, eg. `/usr/share/zoneinfo/Europe/London`, of versions 1 to 3 as per RFC 8536.
+/// Represented by `transitions` from epoch, and the POSIX TZ `rule` of the footer (version 2+), in effect from the last of the `transitions`.
+/// Without a `rule`, the last of the `transitions` remains in effect.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// let c = Calendar::create();
+/// let london = TzifZone::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/tzif/Europe_London")).unwrap();
+/// let summer = c.to_unixtime(&DateTime { year: 2022, month: 7, day: 1, hour: 12, minute: 0, second: 0, ms: 0 });
+/// assert_eq!(c.to_local(summer, &london).to_string(), "2022-07-01T13:00:00.000+01:00");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TzifZone {
+    pub transitions: Vec<Transition>,
+    pub rule:        Option<TzRule>,
+}
+
+impl TzifZone {
+    /// Reads a TZif file, failing with `io::ErrorKind::InvalidData` wrapping a `ParseError` if its contents are invalid.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<TzifZone> {
+        let bytes = fs::read(path)?;
+        TzifZone::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Parses contents of a TZif file. Leap seconds, and offsets with seconds in effect after epoch, are not supported.
+    pub fn from_bytes(bytes: &[u8]) -> Result<TzifZone, ParseError> {
+        let mut reader = TzifReader { input: bytes, position: 0 };
+        let mut header = reader.header()?;
+        // version 2+ repeats the data with 64 bit times, followed by the footer
+        if header.version >= 2 {
+            reader.data(&header, 4)?;
+            header = reader.header()?;
+        }
+        let time_size = if header.version >= 2 { 8 } else { 4 };
+        let (times, types) = reader.data(&header, time_size)?;
+        let rule = if header.version >= 2 { reader.footer()? } else { None };
+
+        // local time type of a transition, with offsets in whole minutes
+        let offset = |index: u8, index_position: usize| -> Result<UtcOffset, ParseError> {
+            let &(position, offset_seconds, is_dst) = types.get(index as usize)
+                .ok_or(ParseError { position: index_position, kind: ParseErrorKind::OutOfRange })?;
+            if offset_seconds % 60 != 0 {
+                return Err(ParseError { position, kind: ParseErrorKind::Unsupported });
+            }
+            Ok(UtcOffset { offset_minutes: (offset_seconds / 60) as i16, is_dst })
+        };
+
+        // time type at epoch is of the last transition before, or the first type, as per RFC 8536
+        let (at_epoch, at_epoch_position) = times.iter().rev().find(|(_, time, _)| *time <= 0).map_or((0, 0), |&(position, _, index)| (index, position));
+        let mut transitions = vec![Transition { at_ms: 0, offset: offset(at_epoch, at_epoch_position)? }];
+        for &(position, time, index) in times.iter().filter(|(_, time, _)| *time > 0) {
+            let at_ms = (time as u64).checked_mul(MS_IN_SEC)
+                .ok_or(ParseError { position, kind: ParseErrorKind::OutOfRange })?;
+            let offset = offset(index, position)?;
+            if transitions.last().is_some_and(|t| t.offset != offset) {
+                transitions.push(Transition { at_ms, offset });
+            }
+        }
+        Ok(TzifZone { transitions, rule })
+    }
+}
+
+impl TimeZone for TzifZone {
+    fn offset_at(&self, calendar: &Calendar, ts: u64) -> UtcOffset {
+        let i = self.transitions.partition_point(|t| t.at_ms <= ts);
+        match (&self.rule, i.checked_sub(1)) {
+            (Some(rule), _) if i == self.transitions.len() => rule.offset_at(calendar, ts),
+            (_, Some(prev)) => self.transitions[prev].offset,
+            (_, None) => UtcOffset::default(),
+        }
+    }
+
+    fn next_transition(&self, calendar: &Calendar, ts: u64) -> Option<u64> {
+        match self.transitions.partition_point(|t| t.at_ms <= ts) {
+            i if i < self.transitions.len() => Some(self.transitions[i].at_ms),
+            _ => self.rule.as_ref().and_then(|rule| rule.next_transition(calendar, ts)),
+        }
+    }
+}
+
+/// Counts of the header, with positions for error reporting.
+struct TzifHeader {
+    version:  u8,
+    isutcnt:  usize,
+    isstdcnt: usize,
+    leapcnt:  (usize, usize),
+    timecnt:  usize,
+    typecnt:  (usize, usize),
+    charcnt:  usize,
+}
+
+struct TzifReader<'a> {
+    input:    &'a [u8],
+    position: usize,
+}
+
+impl<'a> TzifReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        match self.input.get(self.position..self.position.saturating_add(n)) {
+            Some(bytes) => {
+                self.position += n;
+                Ok(bytes)
+            }
+            None => Err(ParseError { position: self.input.len(), kind: ParseErrorKind::TooShort }),
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        self.take(4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Big endian signed number of 4 or 8 bytes.
+    fn time(&mut self, size: usize) -> Result<i64, ParseError> {
+        let bytes = self.take(size)?;
+        let sign = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+        Ok(bytes.iter().fold(sign, |acc, &b| (acc << 8) | b as i64))
+    }
+
+    fn header(&mut self) -> Result<TzifHeader, ParseError> {
+        let start = self.position;
+        if self.take(MAGIC.len())? != MAGIC {
+            return Err(ParseError { position: start, kind: ParseErrorKind::Unexpected });
+        }
+        let version = match self.take(1)?[0] {
+            0 => 1,
+            v @ b'2'..=b'3' => v - b'0',
+            _ => return Err(ParseError { position: start + MAGIC.len(), kind: ParseErrorKind::Unsupported }),
+        };
+        self.take(15)?;
+        let mut count = || -> Result<(usize, usize), ParseError> { Ok((self.position, self.u32()? as usize)) };
+        let (_, isutcnt) = count()?;
+        let (_, isstdcnt) = count()?;
+        let leapcnt = count()?;
+        let (_, timecnt) = count()?;
+        let typecnt = count()?;
+        let (_, charcnt) = count()?;
+        Ok(TzifHeader { version, isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt })
+    }
+
+    /// Reads the data block, returning transitions of (position of type index, time, type index), and types of (position, offset seconds, is dst).
+    #[allow(clippy::type_complexity)]
+    fn data(&mut self, header: &TzifHeader, time_size: usize) -> Result<(Vec<(usize, i64, u8)>, Vec<(usize, i32, bool)>), ParseError> {
+        if header.leapcnt.1 > 0 {
+            return Err(ParseError { position: header.leapcnt.0, kind: ParseErrorKind::Unsupported });
+        }
+        if header.typecnt.1 == 0 {
+            return Err(ParseError { position: header.typecnt.0, kind: ParseErrorKind::OutOfRange });
+        }
+        let times = (0..header.timecnt).map(|_| self.time(time_size)).collect::<Result<Vec<_>, _>>()?;
+        let indices_start = self.position;
+        let indices = self.take(header.timecnt)?;
+        let times = times.into_iter().zip(indices).enumerate()
+            .map(|(i, (time, &index))| (indices_start + i, time, index))
+            .collect();
+        let types = (0..header.typecnt.1).map(|_| {
+            let position = self.position;
+            let offset_seconds = self.time(4)? as i32;
+            let is_dst = self.take(2)?[0] != 0;
+            Ok((position, offset_seconds, is_dst))
+        }).collect::<Result<Vec<_>, _>>()?;
+        self.take(header.charcnt + header.isstdcnt + header.isutcnt)?;
+        Ok((times, types))
+    }
+
+    /// Reads POSIX TZ string enclosed in new lines, empty if there is no rule.
+    fn footer(&mut self) -> Result<Option<TzRule>, ParseError> {
+        let start = self.position;
+        if self.take(1)? != b"\n" {
+            return Err(ParseError { position: start, kind: ParseErrorKind::Unexpected });
+        }
+        let rule_start = self.position;
+        let rule_len = self.input[rule_start..].iter().position(|&b| b == b'\n')
+            .ok_or(ParseError { position: self.input.len(), kind: ParseErrorKind::TooShort })?;
+        let rule = self.take(rule_len)?;
+        self.take(1)?;
+        if rule.is_empty() {
+            return Ok(None);
+        }
+        core::str::from_utf8(rule)
+            .map_err(|e| ParseError { position: rule_start + e.valid_up_to(), kind: ParseErrorKind::Unexpected })
+            .and_then(|rule| TzRule::from_posix(rule).map_err(|e| ParseError { position: rule_start + e.position, ..e }))
+            .map(Some)
+    }
+}
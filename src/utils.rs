@@ -1,4 +1,45 @@
+use super::constants::*;
+
 /// Division with round up of result.
 pub fn ceil_div(x: u32, y: u32) -> u32 {
     x / y + u32::from(x % y != 0)
-}
\ No newline at end of file
+}
+
+/// Shifts ms from epoch by an offset in minutes, returning `None` on underflow.
+pub fn shift_by_offset(ts: u64, offset_minutes: i16) -> Option<u64> {
+    let offset_ms = offset_minutes.unsigned_abs() as u64 * MS_IN_MIN;
+    if offset_minutes < 0 {
+        ts.checked_sub(offset_ms)
+    } else {
+        ts.checked_add(offset_ms)
+    }
+}
+
+/// Whether `year` is a leap year of the Gregorian calendar, ie. divisible by 4, except for centuries not divisible by 400.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// assert!(is_leap_year(2024) && is_leap_year(2000));
+/// assert!(!is_leap_year(2022) && !is_leap_year(2100));
+/// ```
+pub const fn is_leap_year(year: u16) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Number of days in a given month, taking into account leap years. Note: panics if `month` is not within 1..=12.
+/// ```rust
+/// # use chrono_light::prelude::*;
+/// assert_eq!(days_in_month(2024, 2), 29);
+/// assert_eq!(days_in_month(2022, 2), 28);
+/// ```
+pub const fn days_in_month(year: u16, month: u8) -> u8 {
+    if is_leap_year(year) {
+        MONTH_FOR_LEAP_YEAR[month as usize - 1]
+    } else {
+        MONTH_FOR_NON_LEAP_YEAR[month as usize - 1]
+    }
+}
+
+/// Number of days in a given year, taking into account leap years.
+pub const fn days_in_year(year: u16) -> u16 {
+    if is_leap_year(year) { 366 } else { 365 }
+}